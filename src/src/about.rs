@@ -0,0 +1,104 @@
+//! Contents of the About window: version, build info, and acknowledgements.
+//!
+//! The window itself is drawn by the app; this module holds what it shows
+//! and the platform glue for opening the data folder.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+/// Application version, from the crate manifest.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Third-party crates Praymodoro is built on, with their licenses.
+///
+/// Every dependency in `Cargo.toml` must be listed; a test checks this.
+pub const THIRD_PARTY: &[(&str, &str)] = &[
+    ("eframe / egui / egui_extras", "MIT OR Apache-2.0"),
+    ("image", "MIT OR Apache-2.0"),
+    ("tray-icon", "MIT OR Apache-2.0"),
+    ("muda", "MIT OR Apache-2.0"),
+    ("global-hotkey", "MIT OR Apache-2.0"),
+    ("notify-rust", "MIT OR Apache-2.0"),
+    ("arboard", "MIT OR Apache-2.0"),
+    ("ureq", "MIT OR Apache-2.0"),
+    ("clap", "MIT OR Apache-2.0"),
+    ("display-info", "Apache-2.0"),
+    ("chrono", "MIT OR Apache-2.0"),
+    ("serde / serde_json", "MIT OR Apache-2.0"),
+    ("schemars", "MIT"),
+    ("directories", "MIT OR Apache-2.0"),
+    ("parking_lot", "MIT OR Apache-2.0"),
+    ("ed25519-dalek", "BSD-3-Clause"),
+    ("getrandom", "MIT OR Apache-2.0"),
+    ("auto-launch", "MIT"),
+    ("rodio", "MIT OR Apache-2.0"),
+    ("toml", "MIT OR Apache-2.0"),
+    ("ratatui (terminal frontend)", "MIT"),
+    ("tauri-winrt-notification (Windows)", "MIT OR Apache-2.0"),
+    ("cocoa (macOS)", "MIT OR Apache-2.0"),
+    ("objc (macOS)", "MIT"),
+];
+
+/// Returns a one-line description of this build, e.g. "release build for macos-aarch64".
+pub fn build_info() -> String {
+    let profile = if cfg!(debug_assertions) {
+        "debug"
+    } else {
+        "release"
+    };
+    format!(
+        "{} build for {}-{}",
+        profile,
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    )
+}
+
+/// Opens `dir` in the platform's file manager, creating it if nothing has been saved yet.
+pub fn open_folder(dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(target_os = "windows") {
+        "explorer"
+    } else {
+        "xdg-open"
+    };
+    Command::new(opener).arg(dir).spawn().map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the crate names in [`THIRD_PARTY`], without platform notes.
+    fn acknowledged() -> Vec<&'static str> {
+        THIRD_PARTY
+            .iter()
+            .flat_map(|(names, _)| names.split(" / "))
+            .map(|name| name.split(" (").next().unwrap_or(name))
+            .collect()
+    }
+
+    #[test]
+    fn every_dependency_is_acknowledged() {
+        let manifest: toml::Table = include_str!("../Cargo.toml").parse().unwrap();
+        let mut tables = vec![&manifest["dependencies"]];
+        if let Some(targets) = manifest.get("target").and_then(|t| t.as_table()) {
+            tables.extend(targets.values().filter_map(|t| t.get("dependencies")));
+        }
+
+        let acknowledged = acknowledged();
+        for table in tables {
+            for name in table.as_table().unwrap().keys() {
+                assert!(
+                    acknowledged.contains(&name.as_str()),
+                    "{} is missing from THIRD_PARTY",
+                    name
+                );
+            }
+        }
+    }
+}
@@ -4,9 +4,33 @@
 //! through a transparent, draggable window that displays saint characters and
 //! a countdown timer.
 
-use crate::settings::save_settings;
-use crate::state::{AppState, PomodoroMode};
-use crate::tray::{TrayAction, TrayManager};
+use crate::about;
+use crate::autostart;
+use crate::calendar;
+use crate::characters::{self, next_character};
+use crate::format;
+use crate::hotkeys::HotkeyManager;
+use crate::journal;
+use crate::notifications::{notify_character_unavailable, notify_sprint_finished};
+use crate::palette;
+use crate::plan::{block_index, save_plan, DayPlan};
+use crate::prayers::{rest_prayer, work_prayer, WORK_PRAYERS};
+use crate::presence;
+use crate::quotes;
+use crate::screen;
+use crate::settings::{
+    save_settings, switch_profile, AppearanceSettings, FeastReminders, MonitorCompanion,
+    OutlineTone, OverlayStyle, Profile, Settings, TimerDisplay, TrayTitle, WindowLayer,
+};
+use crate::state::{
+    AppState, PomodoroMode, RosaryProgress, MAX_SCALE, MIN_SCALE, ROSARY_DECADE_BEADS,
+};
+use crate::stats::save_stats;
+use crate::storage;
+use crate::timer::{check_segments, PomodoroSegment, ScheduleKind, TimerEngine};
+use crate::tray::{format_character_name, TrayAction, TrayManager, SCALE_PRESETS};
+use crate::updater::check_for_updates;
+use chrono::{Local, NaiveDate, Timelike};
 use egui::{Color32, Pos2, Rect, Sense, Vec2};
 use image::imageops::FilterType;
 use parking_lot::Mutex;
@@ -14,20 +38,120 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 /// Base width of the companion window in pixels.
-const BASE_WIDTH: f32 = 160.0;
+pub const BASE_WIDTH: f32 = 160.0;
 
 /// Base height of the companion window in pixels.
-const BASE_HEIGHT: f32 = 395.0;
+pub const BASE_HEIGHT: f32 = 395.0;
 
 /// Maximum width for sprite textures loaded into GPU memory.
 ///
 /// Original sprites are 590x1455, but we resize to 295x728 (half size)
 /// to save GPU memory while maintaining quality at up to 200% scale.
+/// On high-DPI displays where the companion covers more physical pixels
+/// than that, the sprites are loaded at full resolution instead.
 const MAX_SPRITE_WIDTH: u32 = 295;
 
 /// Maximum height for sprite textures loaded into GPU memory.
 const MAX_SPRITE_HEIGHT: u32 = 728;
 
+/// How long a touch must be held on the companion before it starts a window drag.
+const LONG_PRESS_SECONDS: f64 = 0.4;
+
+/// Size of the on-window menu button shown in touch mode, at 100% scale.
+///
+/// Large enough to be a comfortable finger target.
+const MENU_BUTTON_SIZE: f32 = 40.0;
+
+/// How long the window must stay still after moving before it's kept on screen.
+///
+/// Native drags happen outside egui, so the end of a drag is detected by the
+/// window position settling rather than by a pointer release.
+const MOVE_SETTLE_SECONDS: f64 = 0.3;
+
+/// How long the breathing pause lasts at the start of the break overlay.
+const BREATHING_SECONDS: f64 = 30.0;
+
+/// Length of one breath (in and out) in the breathing pause.
+const BREATH_CYCLE_SECONDS: f64 = 8.0;
+
+/// How much one point of scroll changes the window scale, as a fraction.
+const SCROLL_SCALE_RATE: f32 = 0.002;
+
+/// How far each arrow key press nudges the companion, in points.
+const KEY_NUDGE: f32 = 10.0;
+
+/// Scale factor applied by each press of the scale up key (and undone by scale down).
+const KEY_SCALE_STEP: f32 = 1.1;
+
+/// How long scrolling must stop before a scroll-wheel resize is saved.
+const SCALE_SAVE_DELAY_SECONDS: f64 = 0.5;
+
+/// How long the prayer-before-work card waits for "Amen" before closing.
+const WORK_PRAYER_SECONDS: f64 = 10.0;
+
+/// Largest number of pomodoros a sprint can be started with.
+const MAX_SPRINT_TARGET: u32 = 12;
+
+/// Size of the break overlay in its centered-window style.
+const OVERLAY_WINDOW_SIZE: Vec2 = Vec2::new(560.0, 480.0);
+
+/// How often the list of connected monitors is refreshed for per-monitor companions.
+const MONITOR_REFRESH_SECONDS: f64 = 5.0;
+
+/// How long the pointer must rest on the companion before it fades.
+const HOVER_FADE_DELAY_SECONDS: f64 = 1.0;
+
+/// How long the fade on hover takes, in and out.
+const HOVER_FADE_SECONDS: f32 = 0.3;
+
+/// How much of a docked companion stays on screen, in unscaled points.
+const DOCK_SLIVER: f32 = 32.0;
+
+/// How long a docked companion takes to slide in or out.
+const DOCK_SLIDE_SECONDS: f32 = 0.25;
+
+/// How long the crossfade between sprites lasts when the mode or character changes.
+const CROSSFADE_SECONDS: f64 = 0.4;
+
+/// How long the saint's reaction to a click lasts.
+const REACTION_SECONDS: f64 = 0.6;
+
+/// How high the saint hops when clicked, in unscaled pixels.
+const REACTION_HOP: f32 = 12.0;
+
+/// How often the saint speaks up unprompted.
+const QUOTE_INTERVAL_SECONDS: f64 = 15.0 * 60.0;
+
+/// How long a speech bubble stays up.
+const QUOTE_SECONDS: f64 = 8.0;
+
+/// Search state of the command palette.
+#[derive(Default)]
+struct PaletteState {
+    /// Text typed into the search field.
+    query: String,
+    /// Index of the highlighted command among the matches.
+    selected: usize,
+}
+
+/// PIN window contents, for an action held back until the family PIN is entered.
+struct PinPrompt {
+    /// PIN typed so far.
+    entry: String,
+    /// Action to run once the PIN is right.
+    action: TrayAction,
+    /// Whether a wrong PIN was entered.
+    wrong: bool,
+}
+
+/// Form contents of the sprint window, for starting a new sprint.
+struct SprintDraft {
+    /// What the sprint is for.
+    goal: String,
+    /// Number of pomodoros to run.
+    target: u32,
+}
+
 /// The main egui application struct for Praymodoro.
 ///
 /// Manages the UI rendering, sprite caching, tray icon integration, and
@@ -37,22 +161,104 @@ pub struct PrayomodoroApp {
     state: Arc<Mutex<AppState>>,
     /// System tray icon manager.
     tray: Option<TrayManager>,
+    /// Global hotkey manager (`None` if hotkeys couldn't be registered).
+    hotkeys: Option<HotkeyManager>,
     /// Cached character sprite textures (key: "character_sprite").
     textures: HashMap<String, egui::TextureHandle>,
+    /// Sprite currently shown (key: "character_sprite", ignoring animation frames).
+    shown_sprite: String,
+    /// Texture drawn last frame, kept to fade out from when the sprite changes.
+    last_texture: Option<egui::TextureHandle>,
+    /// Texture being faded out and when the fade began (egui seconds).
+    fading_from: Option<(egui::TextureHandle, f64)>,
+    /// Cached number of animation frames per sprite (key: "character_sprite").
+    frame_counts: HashMap<String, usize>,
+    /// Cached whether each sprite has a night variant (key: "character_sprite").
+    night_variants: HashMap<String, bool>,
     /// Cached timer background texture.
     timer_bg: Option<egui::TextureHandle>,
     /// Last character name (used to detect character changes and clear caches).
     last_character: String,
+    /// Whether touch input has been seen on the companion window.
+    touch_detected: bool,
+    /// Time (egui seconds) the current press began, used for tap-and-hold dragging.
+    press_started: Option<f64>,
+    /// Connected monitors, refreshed now and then while per-monitor companions are on.
+    monitors: Vec<screen::Monitor>,
+    /// Time (egui seconds) the monitor list was last refreshed.
+    monitors_checked_at: Option<f64>,
+    /// Where each per-monitor companion was last seen moving and when, by monitor name.
+    companion_moves: HashMap<String, (Pos2, f64)>,
+    /// Edge the companion is docked to, if any.
+    dock: Option<screen::Dock>,
+    /// Position last sent while sliding the docked companion.
+    dock_position: Option<Pos2>,
+    /// Time (egui seconds) the pointer came to rest on the companion.
+    hover_started: Option<f64>,
+    /// Time (egui seconds) the saint was last clicked, while it's still reacting.
+    reacted_at: Option<f64>,
+    /// Text in the speech bubble and when it appeared (egui seconds).
+    quote: Option<(String, f64)>,
+    /// Number of quotes said so far, used to rotate through them.
+    quotes_said: usize,
+    /// Time (egui seconds) the saint next speaks up unprompted.
+    next_quote_at: f64,
+    /// Pomodoros completed today as of the last frame, to celebrate each new one.
+    completed_seen: Option<u32>,
+    /// Day tomorrow's celebration was last checked for, so it's announced once a day.
+    feast_checked: Option<NaiveDate>,
+    /// Plan being edited in the planner window, present while the window is open.
+    planner_draft: Option<DayPlan>,
+    /// Sprint window form, present while the window is open.
+    sprint_draft: Option<SprintDraft>,
+    /// Intention being typed in the add-intention window, present while the window is open.
+    intention_draft: Option<String>,
+    /// Family PIN window, present while the window is open.
+    pin_prompt: Option<PinPrompt>,
+    /// Size (in percent) being entered in the custom size window, present while the window is open.
+    scale_draft: Option<f32>,
+    /// Command palette search, present while the palette is open.
+    palette: Option<PaletteState>,
+    /// Whether the quit confirmation window is open.
+    quit_confirm: bool,
+    /// Settings being edited in the settings window, present while the window is open.
+    settings_draft: Option<Settings>,
+    /// System clipboard, created on first use.
+    ///
+    /// Kept alive for the life of the app because on Linux the copied text is
+    /// only available while its owner exists.
+    clipboard: Option<arboard::Clipboard>,
+    /// Whether the break overlay was dismissed for the current rest period.
+    overlay_dismissed: bool,
+    /// Time (egui seconds) the break overlay opened for the current rest period.
+    overlay_opened_at: Option<f64>,
+    /// Note being typed on the break overlay.
+    break_note: String,
+    /// Number of notes kept in the journal during the current rest period.
+    break_notes_kept: u32,
+    /// Mode seen on the previous frame, used to spot the start of a work period.
+    last_mode: Option<PomodoroMode>,
+    /// Time (egui seconds) the prayer-before-work card opened, present while it's open.
+    work_prayer_opened_at: Option<f64>,
+    /// Window position seen on the previous frame.
+    last_outer_pos: Option<Pos2>,
+    /// Time (egui seconds) the window last moved, cleared once it's been kept on screen.
+    moved_at: Option<f64>,
+    /// Time (egui seconds) of the last unsaved scroll-wheel or keyboard resize.
+    scaled_at: Option<f64>,
+    /// Whether the About window is open.
+    about_open: bool,
 }
 
 impl PrayomodoroApp {
     /// Creates a new Praymodoro application instance.
     ///
-    /// Initializes the system tray icon and sets up the initial character.
-    /// Must be called on the main thread.
-    pub fn new(state: Arc<Mutex<AppState>>) -> Self {
-        // Create tray on main thread
-        let tray = TrayManager::new();
+    /// Initializes the system tray icon and global hotkeys, and sets up the
+    /// initial character. Must be called on the main thread.
+    pub fn new(state: Arc<Mutex<AppState>>, ctx: &egui::Context) -> Self {
+        // Create tray and hotkeys on main thread
+        let tray = TrayManager::new(ctx, &state.lock().settings.menu_layout);
+        let hotkeys = HotkeyManager::new(&state.lock().settings.hotkeys);
 
         let initial_character = {
             let s = state.lock();
@@ -62,22 +268,63 @@ impl PrayomodoroApp {
         Self {
             state,
             tray: Some(tray),
+            hotkeys,
             textures: HashMap::new(),
+            frame_counts: HashMap::new(),
+            night_variants: HashMap::new(),
+            shown_sprite: String::new(),
+            last_texture: None,
+            fading_from: None,
             timer_bg: None,
             last_character: initial_character,
+            touch_detected: false,
+            press_started: None,
+            monitors: Vec::new(),
+            monitors_checked_at: None,
+            companion_moves: HashMap::new(),
+            dock: None,
+            dock_position: None,
+            hover_started: None,
+            reacted_at: None,
+            quote: None,
+            quotes_said: 0,
+            next_quote_at: QUOTE_INTERVAL_SECONDS,
+            completed_seen: None,
+            feast_checked: None,
+            planner_draft: None,
+            sprint_draft: None,
+            intention_draft: None,
+            pin_prompt: None,
+            scale_draft: None,
+            palette: None,
+            quit_confirm: false,
+            settings_draft: None,
+            clipboard: None,
+            overlay_dismissed: false,
+            overlay_opened_at: None,
+            break_note: String::new(),
+            break_notes_kept: 0,
+            last_mode: None,
+            work_prayer_opened_at: None,
+            last_outer_pos: None,
+            moved_at: None,
+            scaled_at: None,
+            about_open: false,
         }
     }
 
     /// Loads a character sprite texture, with caching.
     ///
     /// Searches multiple locations for the sprite asset and resizes it to
-    /// [`MAX_SPRITE_WIDTH`] x [`MAX_SPRITE_HEIGHT`] to conserve GPU memory.
+    /// [`MAX_SPRITE_WIDTH`] x [`MAX_SPRITE_HEIGHT`] to conserve GPU memory,
+    /// unless `full_resolution` is set.
     ///
     /// # Arguments
     ///
     /// * `ctx` - egui context for texture loading
     /// * `character` - Character identifier (e.g., "augustine-of-hippo")
     /// * `sprite` - Sprite name (e.g., "work", "quick-break", "idle")
+    /// * `full_resolution` - Whether to skip the downscale (see [`needs_full_resolution`])
     ///
     /// # Returns
     ///
@@ -87,60 +334,53 @@ impl PrayomodoroApp {
         ctx: &egui::Context,
         character: &str,
         sprite: &str,
+        full_resolution: bool,
     ) -> Option<egui::TextureHandle> {
-        let key = format!("{}_{}", character, sprite);
+        let key = texture_key(character, sprite, full_resolution);
         if let Some(tex) = self.textures.get(&key) {
             return Some(tex.clone());
         }
 
-        // Try to load from assets directory
-        let asset_path = format!("assets/characters/{}/{}.png", character, sprite);
-
-        // First try relative to executable
-        let exe_path = std::env::current_exe().ok()?;
-        let exe_dir = exe_path.parent()?;
-
-        // Try multiple locations
-        let paths_to_try = [
-            exe_dir.join(&asset_path),
-            exe_dir.join("../Resources").join(&asset_path),
-            std::path::PathBuf::from(&asset_path),
-            std::path::PathBuf::from(format!(
-                "../assets/characters/{}/{}.png",
-                character, sprite
-            )),
-            // For development - run from project root
-            std::path::PathBuf::from(format!(
-                "src-egui/assets/characters/{}/{}.png",
-                character, sprite
-            )),
-        ];
-
-        for path in &paths_to_try {
-            if let Ok(image_data) = std::fs::read(path) {
-                if let Ok(image) = image::load_from_memory(&image_data) {
-                    // Resize to save GPU memory (590x1455 -> 295x728)
-                    let resized = if image.width() > MAX_SPRITE_WIDTH || image.height() > MAX_SPRITE_HEIGHT {
-                        image.resize(MAX_SPRITE_WIDTH, MAX_SPRITE_HEIGHT, FilterType::Lanczos3)
-                    } else {
-                        image
-                    };
+        let rgba = read_sprite(character, sprite, full_resolution)?;
+        let size = [rgba.width() as usize, rgba.height() as usize];
+        let pixels = rgba.into_raw();
 
-                    let rgba = resized.to_rgba8();
-                    let size = [rgba.width() as usize, rgba.height() as usize];
-                    let pixels = rgba.into_raw();
+        let color_image = egui::ColorImage::from_rgba_unmultiplied(size, &pixels);
+        let texture = ctx.load_texture(&key, color_image, egui::TextureOptions::default());
 
-                    let color_image = egui::ColorImage::from_rgba_unmultiplied(size, &pixels);
-                    let texture =
-                        ctx.load_texture(&key, color_image, egui::TextureOptions::default());
+        self.textures.insert(key, texture.clone());
+        Some(texture)
+    }
 
-                    self.textures.insert(key, texture.clone());
-                    return Some(texture);
-                }
-            }
+    /// Loads a white silhouette of a character sprite, with caching.
+    ///
+    /// The silhouette keeps the sprite's shape and transparency, so tinting it
+    /// draws the sprite's shadow and outline. It's only loaded while one of
+    /// those is turned on.
+    fn load_silhouette(
+        &mut self,
+        ctx: &egui::Context,
+        character: &str,
+        sprite: &str,
+        full_resolution: bool,
+    ) -> Option<egui::TextureHandle> {
+        let key = texture_key(character, sprite, full_resolution) + "_silhouette";
+        if let Some(tex) = self.textures.get(&key) {
+            return Some(tex.clone());
         }
 
-        None
+        let rgba = read_sprite(character, sprite, full_resolution)?;
+        let size = [rgba.width() as usize, rgba.height() as usize];
+        let pixels: Vec<u8> = rgba
+            .pixels()
+            .flat_map(|pixel| [255, 255, 255, pixel[3]])
+            .collect();
+
+        let color_image = egui::ColorImage::from_rgba_unmultiplied(size, &pixels);
+        let texture = ctx.load_texture(&key, color_image, egui::TextureOptions::default());
+
+        self.textures.insert(key, texture.clone());
+        Some(texture)
     }
 
     /// Loads the timer background texture from embedded assets.
@@ -168,186 +408,2830 @@ impl PrayomodoroApp {
         None
     }
 
+    /// Switches to character `id` and saves it as the chosen character.
+    ///
+    /// Only characters whose sprites exist are accepted; the error names the
+    /// available ones.
+    fn set_character(&self, id: String) -> Result<(), String> {
+        characters::validate(&id)?;
+        self.state.lock().character = id.clone();
+        self.update_settings(|settings| settings.character = id);
+        Ok(())
+    }
+
+    /// Changes the settings with `change` and saves them.
+    ///
+    /// The state lock is released before saving.
+    fn update_settings(&self, change: impl FnOnce(&mut Settings)) {
+        let settings = {
+            let mut s = self.state.lock();
+            change(&mut s.settings);
+            s.settings.clone()
+        };
+        save_settings(&settings);
+    }
+
     /// Handles actions triggered from the system tray menu.
     ///
+    /// Actions guarded by the family PIN (see [`Self::needs_pin`]) wait in the
+    /// PIN window until it's entered.
+    fn handle_tray_action(&mut self, action: TrayAction, ctx: &egui::Context) {
+        if self.needs_pin(&action) {
+            self.pin_prompt = Some(PinPrompt {
+                entry: String::new(),
+                action,
+                wrong: false,
+            });
+        } else {
+            self.run_tray_action(action, ctx);
+        }
+    }
+
+    /// Carries out a tray action.
+    ///
     /// Updates application state and sends viewport commands in response to
     /// user interactions with the tray icon menu.
-    fn handle_tray_action(&mut self, action: TrayAction, ctx: &egui::Context) {
+    fn run_tray_action(&mut self, action: TrayAction, ctx: &egui::Context) {
         match action {
             TrayAction::ToggleVisibility => {
-                let mut s = self.state.lock();
-                s.visible = !s.visible;
-                let visible = s.visible;
-                drop(s);
+                let visible = {
+                    let mut s = self.state.lock();
+                    s.visible = !s.visible;
+                    s.visible
+                };
+                self.update_settings(|settings| settings.window.visible = visible);
                 ctx.send_viewport_cmd(egui::ViewportCommand::Visible(visible));
             }
-            TrayAction::SetCharacter(char_name) => {
+            TrayAction::Snooze(snooze) => {
                 let mut s = self.state.lock();
-                s.character = char_name;
-                s.settings.character = s.character.clone();
+                s.snoozed_until = Some(snooze.until(Local::now()));
+                s.visible = false;
                 drop(s);
-                let s = self.state.lock();
-                save_settings(&s.settings);
+                ctx.send_viewport_cmd(egui::ViewportCommand::Visible(false));
             }
-            TrayAction::SetScale(scale) => {
+            TrayAction::EndSnooze => {
                 let mut s = self.state.lock();
-                s.scale = scale;
-                s.settings.window.scale = s.scale;
-                let new_size = Vec2::new(BASE_WIDTH * s.scale, BASE_HEIGHT * s.scale);
+                s.snoozed_until = None;
+                s.visible = true;
                 drop(s);
-                let s = self.state.lock();
-                save_settings(&s.settings);
+                ctx.send_viewport_cmd(egui::ViewportCommand::Visible(true));
+            }
+            TrayAction::OpenPlanner => {
+                if self.planner_draft.is_none() {
+                    let today = Local::now().date_naive();
+                    self.planner_draft = Some(self.state.lock().plan.for_day(today));
+                }
+            }
+            TrayAction::OpenSprint => {
+                if self.sprint_draft.is_none() {
+                    self.sprint_draft = Some(SprintDraft {
+                        goal: String::new(),
+                        target: 4,
+                    });
+                }
+            }
+            TrayAction::OpenSettings => {
+                if self.settings_draft.is_none() {
+                    self.settings_draft = Some(self.state.lock().settings.clone());
+                }
+            }
+            TrayAction::OpenAbout => {
+                self.about_open = true;
+            }
+            TrayAction::OpenCommandPalette => {
+                self.palette = Some(PaletteState::default());
+            }
+            TrayAction::SetCharacter(char_name) => {
+                if let Err(error) = self.set_character(char_name) {
+                    let settings = self.state.lock().settings.notifications.clone();
+                    notify_character_unavailable(&error, &settings);
+                }
+            }
+            TrayAction::CopyStatus => {
+                let status = self.state.lock().status_line();
+                if self.clipboard.is_none() {
+                    self.clipboard = arboard::Clipboard::new().ok();
+                }
+                if let Some(ref mut clipboard) = self.clipboard {
+                    let _ = clipboard.set_text(status);
+                }
+            }
+            TrayAction::CheckForUpdates => {
+                let settings = self.state.lock().settings.clone();
+                if !settings.offline {
+                    check_for_updates(true, settings.notifications);
+                }
+            }
+            TrayAction::TogglePause => {
+                let mut s = self.state.lock();
+                s.paused = !s.paused;
+            }
+            TrayAction::SkipPeriod => {
+                self.state.lock().skip_requested = true;
+            }
+            TrayAction::RestartPeriod => {
+                self.state.lock().restart_requested = true;
+            }
+            TrayAction::NextCharacter => {
+                let next = next_character(&self.state.lock().character);
+                self.handle_tray_action(TrayAction::SetCharacter(next.to_string()), ctx);
+            }
+            TrayAction::SetScale(scale) => {
+                self.state.lock().scale = scale;
+                self.update_settings(|settings| settings.window.scale = scale);
+                let new_size = Vec2::new(BASE_WIDTH * scale, BASE_HEIGHT * scale);
                 ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(new_size));
             }
+            TrayAction::AcknowledgeMissedPrayers => {
+                self.state.lock().missed_prayers = 0;
+            }
+            TrayAction::OpenCustomScale => {
+                if self.scale_draft.is_none() {
+                    self.scale_draft = Some((self.state.lock().scale * 100.0).round());
+                }
+            }
+            TrayAction::SetLayer(layer) => {
+                self.update_settings(|settings| settings.window.layer = layer);
+                ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(layer.level()));
+            }
+            TrayAction::SetTimerDisplay(display) => {
+                self.update_settings(|settings| settings.timer_display = display);
+            }
+            TrayAction::SetTrayTitle(title) => {
+                self.update_settings(|settings| settings.tray_title = title);
+            }
+            TrayAction::SetProfile(profile) => {
+                let settings = switch_profile(&self.state.lock().settings, profile);
+                self.apply_settings(ctx, settings);
+                // An open settings window would still be editing the old profile
+                self.settings_draft = None;
+            }
+            TrayAction::SetOpacity(opacity) => {
+                self.update_settings(|settings| settings.appearance.opacity = opacity);
+            }
+            TrayAction::SetSpriteShadow(strength) => {
+                self.update_settings(|settings| settings.appearance.sprite_shadow = strength);
+            }
+            TrayAction::SetSpriteOutline(strength) => {
+                self.update_settings(|settings| settings.appearance.sprite_outline = strength);
+            }
+            TrayAction::SetSchedule(schedule) => {
+                self.update_settings(|settings| settings.schedule = schedule);
+            }
+            TrayAction::SetEngine(engine) => {
+                self.update_settings(|settings| settings.engine = engine);
+            }
+            TrayAction::ToggleRosary => {
+                self.update_settings(|settings| settings.rosary_mode = !settings.rosary_mode);
+            }
+            TrayAction::ToggleTouchMode => {
+                self.update_settings(|settings| settings.touch_mode = !settings.touch_mode);
+            }
+            TrayAction::SetOverlayStyle(style) => {
+                self.update_settings(|settings| settings.overlay_style = style);
+            }
+            TrayAction::ToggleBreakOverlay => {
+                self.update_settings(|settings| settings.break_overlay = !settings.break_overlay);
+            }
+            TrayAction::ToggleBreathingPause => {
+                self.update_settings(|settings| {
+                    settings.breathing_pause = !settings.breathing_pause
+                });
+            }
+            TrayAction::ToggleReduceMotion => {
+                self.update_settings(|settings| settings.reduce_motion = !settings.reduce_motion);
+            }
+            TrayAction::SetIntention(intention) => {
+                self.update_settings(|settings| settings.intention = intention);
+            }
+            TrayAction::AddIntention => {
+                if self.intention_draft.is_none() {
+                    self.intention_draft = Some(String::new());
+                }
+            }
+            TrayAction::ToggleStartAtLogin => {
+                let mut s = self.state.lock();
+                let enabled = !s.start_at_login;
+                // The tray check goes back to the old state if registering fails
+                if autostart::set_enabled(enabled).is_ok() {
+                    s.start_at_login = enabled;
+                }
+            }
+            TrayAction::ToggleFlip => {
+                self.update_settings(|settings| settings.window.flip = !settings.window.flip);
+            }
+            TrayAction::ToggleMultiCompanion => {
+                self.update_settings(|settings| {
+                    settings.multi_companion = !settings.multi_companion
+                });
+            }
+            TrayAction::ToggleSpeechBubbles => {
+                self.update_settings(|settings| settings.speech_bubbles = !settings.speech_bubbles);
+            }
+            TrayAction::ToggleSounds => {
+                self.update_settings(|settings| {
+                    settings.notifications.sound = !settings.notifications.sound
+                });
+            }
+            TrayAction::TogglePrayerBeforeWork => {
+                self.update_settings(|settings| {
+                    settings.prayer_before_work = !settings.prayer_before_work
+                });
+            }
             TrayAction::Quit => {
-                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                // Quitting again while the confirmation is open goes through
+                let s = self.state.lock();
+                if s.settings.confirm_quit
+                    && s.mode == PomodoroMode::Work
+                    && !s.paused
+                    && !self.quit_confirm
+                {
+                    self.quit_confirm = true;
+                } else {
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                }
             }
             TrayAction::None => {}
         }
     }
-}
 
-impl eframe::App for PrayomodoroApp {
-    fn clear_color(&self, _visuals: &egui::Visuals) -> [f32; 4] {
-        [0.0, 0.0, 0.0, 0.0] // Fully transparent background
-    }
+    /// Moves the window back on screen after startup or a drag, snapping to edges if enabled.
+    ///
+    /// Saved coordinates can point at a monitor that is no longer connected,
+    /// leaving the companion unreachable. The first frame counts as a move, so
+    /// this also runs once at startup. Once the window rests on screen its
+    /// position is saved for the next launch.
+    fn keep_on_screen(&mut self, ctx: &egui::Context) {
+        let (outer_rect, now) = ctx.input(|i| (i.viewport().outer_rect, i.time));
+        let Some(outer_rect) = outer_rect else {
+            return;
+        };
 
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        // Poll tray events on main thread
-        if let Some(ref mut tray) = self.tray {
-            let action = tray.poll_events(&self.state);
-            self.handle_tray_action(action, ctx);
+        if self.last_outer_pos != Some(outer_rect.min) {
+            self.last_outer_pos = Some(outer_rect.min);
+            self.moved_at = Some(now);
+            return;
         }
 
-        // Check if should quit
-        {
+        let settled = self
+            .moved_at
+            .is_some_and(|moved_at| now - moved_at >= MOVE_SETTLE_SECONDS);
+        if !settled {
+            return;
+        }
+        self.moved_at = None;
+
+        let (snap_distance, edge_dock, scale) = {
             let s = self.state.lock();
-            if s.should_quit {
-                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
-                return;
+            let snap_distance = if s.settings.window.snap_to_edges {
+                s.settings.window.snap_distance
+            } else {
+                0.0
+            };
+            (snap_distance, s.settings.window.edge_dock, s.scale)
+        };
+        let monitors = screen::monitors();
+        match screen::keep_on_screen(outer_rect, &monitors, snap_distance) {
+            // The move settles again at the target, and is saved then
+            Some(target) if target != outer_rect.min => {
+                ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(target));
+            }
+            _ => {
+                // The flush position is saved, so the companion starts undocked next time
+                self.save_position(outer_rect, &monitors);
+                if edge_dock {
+                    self.dock = screen::dock(outer_rect, &monitors, DOCK_SLIVER * scale);
+                    self.dock_position = None;
+                }
             }
         }
+    }
 
-        // Get current state
-        let (mode, formatted_time, character, scale) = {
+    /// Shows a companion on every monitor other than the main companion's.
+    ///
+    /// The extra companions mirror the main one's sprite and timer and can be
+    /// dragged on their own. Each one's position and scale is kept in
+    /// [`Settings::monitor_companions`] by monitor name, starting in the
+    /// monitor's bottom-right corner.
+    fn show_monitor_companions(
+        &mut self,
+        ctx: &egui::Context,
+        texture: Option<&egui::TextureHandle>,
+        timer_text: Option<&str>,
+        period_complete: f32,
+    ) {
+        let (enabled, layer, opacity, main_scale) = {
             let s = self.state.lock();
             (
-                s.mode,
-                s.formatted_time.clone(),
-                s.character.clone(),
+                s.settings.multi_companion,
+                s.settings.window.layer,
+                s.settings.appearance.opacity,
                 s.scale,
             )
         };
-
-        // Check if character changed - if so, clear old textures and request full redraw
-        let character_changed = character != self.last_character;
-        if character_changed {
-            // Clear cached textures for the old character to free GPU memory
-            let old_char = &self.last_character;
-            self.textures.retain(|key, _| !key.starts_with(old_char));
-
-            self.last_character = character.clone();
-            ctx.request_repaint();
+        if !enabled {
+            return;
         }
 
-        // Determine sprite to show
-        let sprite = match mode {
-            PomodoroMode::Work => "work",
-            PomodoroMode::Rest => "quick-break",
+        let now = ctx.input(|i| i.time);
+        if self
+            .monitors_checked_at
+            .is_none_or(|checked_at| now - checked_at >= MONITOR_REFRESH_SECONDS)
+        {
+            self.monitors = screen::monitors();
+            self.monitors_checked_at = Some(now);
+        }
+        let Some(main_rect) = ctx.input(|i| i.viewport().outer_rect) else {
+            return;
         };
+        let home = screen::home_monitor(main_rect, &self.monitors).map(|m| m.name.clone());
+        let others: Vec<screen::Monitor> = self
+            .monitors
+            .iter()
+            .filter(|monitor| Some(&monitor.name) != home.as_ref())
+            .cloned()
+            .collect();
+        let timer_bg = self.load_timer_bg(ctx);
 
-        // Load texture
-        let texture = self.load_texture(ctx, &character, sprite);
+        for monitor in others {
+            let saved = self
+                .state
+                .lock()
+                .settings
+                .monitor_companions
+                .iter()
+                .find(|c| c.monitor == monitor.name)
+                .cloned();
+            let companion = match saved {
+                Some(companion) => companion,
+                None => {
+                    let size = Vec2::new(BASE_WIDTH, BASE_HEIGHT) * main_scale;
+                    let corner = monitor.rect.max - size - Vec2::new(40.0, 80.0);
+                    let companion = MonitorCompanion {
+                        monitor: monitor.name.clone(),
+                        x: corner.x,
+                        y: corner.y,
+                        scale: main_scale,
+                        flip: false,
+                    };
+                    let added = companion.clone();
+                    self.update_settings(|settings| settings.monitor_companions.push(added));
+                    companion
+                }
+            };
+            let scale = companion.scale;
+            let size = Vec2::new(BASE_WIDTH * scale, BASE_HEIGHT * scale);
+            let mut outer_pos = None;
 
-        // Central panel with transparent background
-        egui::CentralPanel::default()
-            .frame(egui::Frame::NONE)
-            .show(ctx, |ui| {
-                // Use expected size based on scale, not available_size which can be wrong on first frame
-                let expected_size = Vec2::new(BASE_WIDTH * scale, BASE_HEIGHT * scale);
-                let available_size = ui.available_size();
-                // Use the larger of expected or available to avoid tiny sprites on startup
-                let size = Vec2::new(
-                    available_size.x.max(expected_size.x),
-                    available_size.y.max(expected_size.y),
-                );
-                let rect = Rect::from_min_size(Pos2::ZERO, size);
+            ctx.show_viewport_immediate(
+                egui::ViewportId::from_hash_of(("monitor_companion", &monitor.name)),
+                egui::ViewportBuilder::default()
+                    .with_title("Praymodoro")
+                    .with_position(Pos2::new(companion.x, companion.y))
+                    .with_inner_size(size)
+                    .with_decorations(false)
+                    .with_transparent(true)
+                    .with_has_shadow(false)
+                    .with_window_level(layer.level())
+                    .with_resizable(false)
+                    .with_taskbar(false),
+                |ctx, _class| {
+                    egui::CentralPanel::default()
+                        .frame(egui::Frame::NONE)
+                        .show(ctx, |ui| {
+                            ui.multiply_opacity(opacity.clamp(0.25, 1.0));
+                            let rect = Rect::from_min_size(Pos2::ZERO, size);
+                            let response = ui.allocate_rect(rect, Sense::drag());
+                            if response.drag_started() {
+                                ctx.send_viewport_cmd(egui::ViewportCommand::StartDrag);
+                            }
 
-                // Handle dragging - use native OS drag for smooth movement
-                let response = ui.allocate_rect(rect, Sense::drag());
+                            if let Some(tex) = texture {
+                                ui.painter().image(
+                                    tex.id(),
+                                    sprite_rect(size, tex, 0.0),
+                                    sprite_uv(companion.flip),
+                                    Color32::WHITE,
+                                );
+                            }
+                            draw_timer(
+                                ui.painter(),
+                                timer_rect(size, scale),
+                                timer_bg.as_ref(),
+                                timer_text,
+                                period_complete,
+                                scale,
+                            );
+                        });
+                    outer_pos = ctx.input(|i| i.viewport().outer_rect).map(|r| r.min);
+                },
+            );
 
-                if response.drag_started() {
-                    // Use native window drag - much smoother than manual position updates
-                    ctx.send_viewport_cmd(egui::ViewportCommand::StartDrag);
-                }
+            // Save where the companion was dragged to once it settles
+            let Some(pos) = outer_pos else {
+                continue;
+            };
+            if pos == Pos2::new(companion.x, companion.y) {
+                self.companion_moves.remove(&monitor.name);
+                continue;
+            }
+            let (last_pos, moved_at) = *self
+                .companion_moves
+                .entry(monitor.name.clone())
+                .or_insert((pos, now));
+            if last_pos != pos {
+                self.companion_moves
+                    .insert(monitor.name.clone(), (pos, now));
+            } else if now - moved_at >= MOVE_SETTLE_SECONDS {
+                self.update_settings(|settings| {
+                    if let Some(saved) = settings
+                        .monitor_companions
+                        .iter_mut()
+                        .find(|c| c.monitor == monitor.name)
+                    {
+                        saved.x = pos.x;
+                        saved.y = pos.y;
+                    }
+                });
+            }
+        }
+    }
 
-                // Clear the entire window area first (fixes ghosting on transparent windows)
-                ui.painter().rect_filled(
-                    rect,
-                    egui::CornerRadius::ZERO,
-                    Color32::TRANSPARENT,
-                );
+    /// Slides a docked companion in while the pointer is on it, and back out after.
+    ///
+    /// Returns whether the companion is docked. Dragging it away, resizing it,
+    /// or turning docking off undocks it.
+    fn slide_dock(&mut self, ctx: &egui::Context, edge_dock: bool, reduce_motion: bool) -> bool {
+        let Some(dock) = self.dock else {
+            return false;
+        };
+        let (outer_rect, hovering, pressed) = ctx.input(|i| {
+            (
+                i.viewport().outer_rect,
+                i.pointer.hover_pos().is_some(),
+                i.pointer.primary_down(),
+            )
+        });
+        let dragged_away = match (outer_rect, self.dock_position) {
+            (Some(rect), Some(position)) => pressed && (rect.min - position).length() > 1.0,
+            _ => false,
+        };
+        let resized = outer_rect.is_some_and(|rect| (rect.size() - dock.size).length() > 1.0);
+        if !edge_dock || dragged_away || resized {
+            self.dock = None;
+            self.dock_position = None;
+            return false;
+        }
 
-                // Draw character sprite
-                if let Some(tex) = texture {
-                    let image_size = tex.size_vec2();
-                    let aspect = image_size.x / image_size.y;
+        let slide_time = if reduce_motion {
+            0.0
+        } else {
+            DOCK_SLIDE_SECONDS
+        };
+        let peek = ctx.animate_bool_with_time(egui::Id::new("dock_peek"), hovering, slide_time);
+        let position = dock.hidden.lerp(dock.shown, peek);
+        if self.dock_position != Some(position) {
+            ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(position));
+            self.dock_position = Some(position);
+        }
+        true
+    }
 
-                    // Scale to fit window while maintaining aspect ratio
-                    let target_height = size.y * 0.85; // Leave room for timer
-                    let target_width = target_height * aspect;
+    /// Puts the character's next quote in the speech bubble.
+    fn say_quote(&mut self, character: &str, now: f64) {
+        self.next_quote_at = now + QUOTE_INTERVAL_SECONDS;
+        if let Some(text) = quotes::quote(character, self.quotes_said) {
+            self.quotes_said += 1;
+            self.quote = Some((text.to_string(), now));
+        }
+    }
 
-                    let sprite_size = Vec2::new(target_width.min(size.x), target_height);
-                    let sprite_pos = Pos2::new(
-                        (size.x - sprite_size.x) / 2.0,
-                        size.y - sprite_size.y,
-                    );
+    /// Returns the announcement of tomorrow's celebration, the first time
+    /// it's asked for each day.
+    fn announce_feast(&mut self, reminders: FeastReminders) -> Option<String> {
+        let today = Local::now().date_naive();
+        if self.feast_checked == Some(today) {
+            return None;
+        }
+        self.feast_checked = Some(today);
+        calendar::announcement(today, reminders.min_rank()?)
+    }
 
-                    ui.painter().image(
-                        tex.id(),
-                        Rect::from_min_size(sprite_pos, sprite_size),
-                        Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0)),
-                        Color32::WHITE,
-                    );
-                }
+    /// Saves the window position to settings if it has changed.
+    ///
+    /// The monitor the window is on is saved with it, so the next launch can
+    /// put the window back there even if the monitors were rearranged.
+    fn save_position(&mut self, window: Rect, monitors: &[screen::Monitor]) {
+        let pos = window.min;
+        {
+            let mut s = self.state.lock();
+            if s.window_position == Some((pos.x, pos.y)) {
+                return;
+            }
+            s.window_position = Some((pos.x, pos.y));
+        }
+        self.update_settings(|settings| {
+            settings.window.x = pos.x;
+            settings.window.y = pos.y;
+            if let Some(monitor) = screen::home_monitor(window, monitors) {
+                let offset = pos - monitor.rect.min;
+                settings.window.monitor = monitor.name.clone();
+                settings.window.monitor_x = offset.x;
+                settings.window.monitor_y = offset.y;
+            }
+        });
+    }
 
-                // Draw timer at bottom with parchment background
-                // Original: 130px × 49px, positioned at bottom: 20px
-                let timer_width = 130.0 * scale;
-                let timer_height = 49.0 * scale;
-                let timer_bottom_margin = 20.0 * scale;
-
-                let timer_rect = Rect::from_min_size(
-                    Pos2::new(
-                        (size.x - timer_width) / 2.0,
-                        size.y - timer_height - timer_bottom_margin,
-                    ),
-                    Vec2::new(timer_width, timer_height),
-                );
+    /// Handles the in-window keys from [`Settings::keymap`] while the companion has focus.
+    ///
+    /// Arrow keys nudge the window right away. Returns the action to run for
+    /// the pause and hide keys, and the factor the scale keys resize by.
+    fn handle_keys(&mut self, ctx: &egui::Context) -> (TrayAction, f32) {
+        let focused = ctx.input(|i| i.viewport().focused.unwrap_or(false));
+        if !focused || ctx.wants_keyboard_input() {
+            return (TrayAction::None, 1.0);
+        }
+        let keymap = self.state.lock().settings.keymap.clone();
+        let pressed = |binding: &str| {
+            egui::Key::from_name(binding.trim())
+                .is_some_and(|key| ctx.input(|i| i.key_pressed(key)))
+        };
 
-                // Draw timer background image
-                if let Some(timer_tex) = self.load_timer_bg(ctx) {
-                    ui.painter().image(
-                        timer_tex.id(),
-                        timer_rect,
-                        Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0)),
-                        Color32::WHITE,
-                    );
-                }
+        let mut nudge = Vec2::ZERO;
+        for (binding, direction) in [
+            (&keymap.nudge_left, Vec2::new(-1.0, 0.0)),
+            (&keymap.nudge_right, Vec2::new(1.0, 0.0)),
+            (&keymap.nudge_up, Vec2::new(0.0, -1.0)),
+            (&keymap.nudge_down, Vec2::new(0.0, 1.0)),
+        ] {
+            if pressed(binding) {
+                nudge += direction * KEY_NUDGE;
+            }
+        }
+        if nudge != Vec2::ZERO {
+            if let Some(outer_rect) = ctx.input(|i| i.viewport().outer_rect) {
+                // The move is saved by `keep_on_screen` once it settles
+                self.dock = None;
+                ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(outer_rect.min + nudge));
+            }
+        }
 
-                // Timer text - dark brown like original (#4a3728), serif font
-                let timer_color = Color32::from_rgb(74, 55, 40);
-                let font_size = 26.0 * scale;
-                ui.painter().text(
-                    timer_rect.center(),
-                    egui::Align2::CENTER_CENTER,
-                    &formatted_time,
-                    egui::FontId::new(font_size, egui::FontFamily::Name("serif".into())),
-                    timer_color,
-                );
-            });
+        let mut scale = 1.0;
+        if pressed(&keymap.scale_up) {
+            scale *= KEY_SCALE_STEP;
+        }
+        if pressed(&keymap.scale_down) {
+            scale /= KEY_SCALE_STEP;
+        }
+
+        let action = if pressed(&keymap.pause) {
+            TrayAction::TogglePause
+        } else if pressed(&keymap.hide) {
+            TrayAction::ToggleVisibility
+        } else {
+            TrayAction::None
+        };
+        (action, scale)
+    }
+
+    /// Resizes the companion by `factor`, from the scroll wheel or scale keys.
+    ///
+    /// The new scale is applied right away, but saving it waits until
+    /// resizing has stopped for [`SCALE_SAVE_DELAY_SECONDS`] so a long scroll
+    /// doesn't write the settings file on every frame.
+    fn resize_by(&mut self, ctx: &egui::Context, factor: f32) {
+        let now = ctx.input(|i| i.time);
+
+        if factor != 1.0 {
+            let mut s = self.state.lock();
+            let scale = (s.scale * factor).clamp(MIN_SCALE, MAX_SCALE);
+            if scale != s.scale {
+                s.scale = scale;
+                s.settings.window.scale = scale;
+                drop(s);
+                let new_size = Vec2::new(BASE_WIDTH * scale, BASE_HEIGHT * scale);
+                ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(new_size));
+                self.scaled_at = Some(now);
+            }
+        }
 
-        // Request repaint frequently to keep UI responsive
-        ctx.request_repaint_after(std::time::Duration::from_millis(100));
+        if let Some(scaled_at) = self.scaled_at {
+            if now - scaled_at >= SCALE_SAVE_DELAY_SECONDS {
+                let settings = self.state.lock().settings.clone();
+                save_settings(&settings);
+                self.scaled_at = None;
+            } else {
+                ctx.request_repaint();
+            }
+        }
     }
+
+    /// Shows the fullscreen break overlay during rest periods.
+    ///
+    /// The overlay is a separate borderless, always-on-top viewport showing the
+    /// prayer and remaining time. Depending on [`OverlayStyle`] it covers the
+    /// monitor and dims the screen, or is a window centered on it. It closes
+    /// automatically when work resumes, or early if dismissed (the next rest
+    /// period opens it again). Nothing is shown while snoozed.
+    ///
+    /// With the breathing pause enabled, the prayer is preceded by a gently
+    /// breathing circle and "be still" text for [`BREATHING_SECONDS`]. The circle
+    /// holds still when reduce motion is on.
+    ///
+    /// A one-line note typed on the overlay is kept in today's journal (see
+    /// [`journal`]) when Enter is pressed.
+    fn show_break_overlay(&mut self, ctx: &egui::Context, mode: PomodoroMode, time: &str) {
+        if mode == PomodoroMode::Work {
+            self.overlay_dismissed = false;
+            self.overlay_opened_at = None;
+            self.break_note.clear();
+            self.break_notes_kept = 0;
+            return;
+        }
+        let (enabled, style, breathing_pause, reduce_motion, character, intention, simple) = {
+            let s = self.state.lock();
+            (
+                // Snoozing silences the overlay along with everything else
+                s.settings.break_overlay && s.snoozed_until.is_none(),
+                s.settings.overlay_style,
+                s.settings.breathing_pause,
+                s.settings.reduce_motion,
+                s.character.clone(),
+                s.settings.intention.clone(),
+                s.settings.simple_prayers,
+            )
+        };
+        if self.overlay_dismissed || !enabled {
+            return;
+        }
+        self.state.lock().rest_seen = true;
+
+        let monitor_size = ctx
+            .input(|i| i.viewport().monitor_size)
+            .unwrap_or(Vec2::new(1920.0, 1080.0));
+        let (position, size, frame) = match style {
+            OverlayStyle::Fullscreen => (
+                Pos2::ZERO,
+                monitor_size,
+                egui::Frame::NONE.fill(Color32::from_black_alpha(190)),
+            ),
+            OverlayStyle::Window => {
+                let size = OVERLAY_WINDOW_SIZE.min(monitor_size);
+                (
+                    ((monitor_size - size) / 2.0).to_pos2(),
+                    size,
+                    egui::Frame::NONE
+                        .fill(Color32::from_black_alpha(220))
+                        .corner_radius(12.0)
+                        .inner_margin(16.0),
+                )
+            }
+        };
+        let prayer = rest_prayer(Local::now(), &character, simple);
+        let now = ctx.input(|i| i.time);
+        let elapsed = now - *self.overlay_opened_at.get_or_insert(now);
+        let breathing = breathing_pause && elapsed < BREATHING_SECONDS;
+        let mut dismissed = false;
+        let mut keep_note = false;
+
+        ctx.show_viewport_immediate(
+            egui::ViewportId::from_hash_of("break_overlay"),
+            egui::ViewportBuilder::default()
+                .with_title("Praymodoro - Time to Pray")
+                .with_position(position)
+                .with_inner_size(size)
+                .with_decorations(false)
+                .with_transparent(true)
+                .with_always_on_top()
+                .with_taskbar(false),
+            |ctx, _class| {
+                egui::CentralPanel::default().frame(frame).show(ctx, |ui| {
+                    ui.vertical_centered(|ui| {
+                        if style == OverlayStyle::Fullscreen {
+                            ui.add_space(ui.available_height() * 0.3);
+                        }
+                        ui.label(
+                            egui::RichText::new("Time to Pray")
+                                .size(40.0)
+                                .family(egui::FontFamily::Name("serif".into()))
+                                .color(Color32::WHITE),
+                        );
+                        if !intention.is_empty() {
+                            ui.add_space(4.0);
+                            ui.label(
+                                egui::RichText::new(format!("For {}", intention))
+                                    .size(18.0)
+                                    .italics()
+                                    .color(Color32::from_gray(200)),
+                            );
+                        }
+                        ui.add_space(24.0);
+                        if breathing {
+                            draw_breathing_circle(ui, elapsed, reduce_motion);
+                            ui.add_space(8.0);
+                            ui.label(
+                                egui::RichText::new("Be still, and know that I am God.")
+                                    .size(22.0)
+                                    .italics()
+                                    .color(Color32::from_gray(220)),
+                            );
+                        } else {
+                            ui.label(
+                                egui::RichText::new(prayer.title)
+                                    .size(22.0)
+                                    .color(Color32::from_gray(220)),
+                            );
+                            ui.add_space(8.0);
+                            ui.set_max_width(640.0);
+                            ui.label(
+                                egui::RichText::new(prayer.text)
+                                    .size(20.0)
+                                    .color(Color32::from_gray(230)),
+                            );
+                        }
+                        ui.add_space(32.0);
+                        ui.label(
+                            egui::RichText::new(time)
+                                .size(48.0)
+                                .family(egui::FontFamily::Name("serif".into()))
+                                .color(Color32::WHITE),
+                        );
+                        ui.add_space(24.0);
+                        let note = ui.add(
+                            egui::TextEdit::singleline(&mut self.break_note)
+                                .hint_text("A thought to keep for later")
+                                .desired_width(320.0),
+                        );
+                        keep_note =
+                            note.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                        let hint = match self.break_notes_kept {
+                            0 => "Press Enter to keep it in today's journal".to_string(),
+                            1 => "Kept in today's journal".to_string(),
+                            kept => format!("{} notes kept in today's journal", kept),
+                        };
+                        ui.label(
+                            egui::RichText::new(hint)
+                                .size(12.0)
+                                .color(Color32::from_gray(160)),
+                        );
+                        ui.add_space(12.0);
+                        if ui.button("Dismiss").clicked() {
+                            dismissed = true;
+                        }
+                    });
+                });
+
+                if ctx.input(|i| i.key_pressed(egui::Key::Escape) || i.viewport().close_requested())
+                {
+                    dismissed = true;
+                }
+            },
+        );
+
+        if breathing && !reduce_motion {
+            ctx.request_repaint();
+        }
+        if dismissed {
+            self.overlay_dismissed = true;
+        }
+        let note = self.break_note.trim().to_string();
+        if keep_note && !note.is_empty() {
+            journal::add_note(Local::now().naive_local(), note);
+            self.break_note.clear();
+            self.break_notes_kept += 1;
+        }
+    }
+
+    /// Shows the prayer-before-work card at the start of each work period.
+    ///
+    /// The card is a small always-on-top window with the configured work
+    /// prayer. Only clicking "Amen" (or pressing Enter) counts the prayer in
+    /// the stats; the card also closes by itself after
+    /// [`WORK_PRAYER_SECONDS`], or when closed some other way, without
+    /// counting anything. Nothing is shown while snoozed.
+    fn show_work_prayer(&mut self, ctx: &egui::Context, mode: PomodoroMode) {
+        let work_started = self.last_mode == Some(PomodoroMode::Rest) && mode == PomodoroMode::Work;
+        self.last_mode = Some(mode);
+
+        let (enabled, snoozed, prayer_id) = {
+            let s = self.state.lock();
+            (
+                s.settings.prayer_before_work,
+                s.snoozed_until.is_some(),
+                s.settings.work_prayer.clone(),
+            )
+        };
+        let now = ctx.input(|i| i.time);
+        if work_started && enabled && !snoozed {
+            self.work_prayer_opened_at = Some(now);
+        }
+        // A snooze starting while the card is open puts it away uncounted
+        if snoozed {
+            self.work_prayer_opened_at = None;
+        }
+        let Some(opened_at) = self.work_prayer_opened_at else {
+            return;
+        };
+
+        let prayer = work_prayer(&prayer_id);
+        let mut amen = false;
+        let mut closed = now - opened_at >= WORK_PRAYER_SECONDS;
+
+        ctx.show_viewport_immediate(
+            egui::ViewportId::from_hash_of("work_prayer"),
+            egui::ViewportBuilder::default()
+                .with_title("Prayer Before Work")
+                .with_inner_size([380.0, 240.0])
+                .with_always_on_top(),
+            |ctx, _class| {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    ui.vertical_centered(|ui| {
+                        ui.label(
+                            egui::RichText::new(prayer.title)
+                                .size(18.0)
+                                .family(egui::FontFamily::Name("serif".into())),
+                        );
+                        ui.add_space(8.0);
+                        ui.label(prayer.text);
+                        ui.add_space(12.0);
+                        if ui.button("Amen").clicked()
+                            || ui.input(|i| i.key_pressed(egui::Key::Enter))
+                        {
+                            amen = true;
+                        }
+                    });
+                });
+
+                if ctx.input(|i| i.viewport().close_requested()) {
+                    closed = true;
+                }
+            },
+        );
+
+        if amen {
+            let stats = {
+                let mut s = self.state.lock();
+                s.stats.record_work_prayer(Local::now().date_naive());
+                s.stats.clone()
+            };
+            save_stats(&stats);
+        }
+        if amen || closed {
+            self.work_prayer_opened_at = None;
+        } else {
+            ctx.request_repaint_after(std::time::Duration::from_secs(1));
+        }
+    }
+
+    /// Shows the planner window while a plan is being edited.
+    ///
+    /// Lists the two work blocks of each remaining hour of the day with a label
+    /// field for each. Saving replaces today's plan and persists it to disk.
+    fn show_planner(&mut self, ctx: &egui::Context) {
+        let Some(draft) = self.planner_draft.as_mut() else {
+            return;
+        };
+
+        let mut close = false;
+        let mut save = false;
+        let current_hour = Local::now().hour();
+
+        ctx.show_viewport_immediate(
+            egui::ViewportId::from_hash_of("planner"),
+            egui::ViewportBuilder::default()
+                .with_title("Today's Plan")
+                .with_inner_size([360.0, 480.0]),
+            |ctx, _class| {
+                egui::TopBottomPanel::bottom("planner_buttons").show(ctx, |ui| {
+                    ui.horizontal(|ui| {
+                        save = ui.button("Save").clicked();
+                        close = ui.button("Cancel").clicked();
+                    });
+                });
+
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    ui.label("Label the work blocks you plan for the rest of today.");
+                    ui.add_space(8.0);
+
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        egui::Grid::new("planner_grid")
+                            .num_columns(3)
+                            .striped(true)
+                            .show(ui, |ui| {
+                                ui.label("");
+                                ui.label(":00");
+                                ui.label(":30");
+                                ui.end_row();
+
+                                for hour in current_hour..24 {
+                                    ui.label(format!("{:02}:00", hour));
+                                    for minute in [0, 30] {
+                                        let label = draft
+                                            .blocks
+                                            .entry(block_index(hour, minute))
+                                            .or_default();
+                                        ui.text_edit_singleline(label);
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                });
+
+                if ctx.input(|i| i.viewport().close_requested()) {
+                    close = true;
+                }
+            },
+        );
+
+        if save {
+            draft.blocks.retain(|_, label| !label.trim().is_empty());
+            self.state.lock().plan = draft.clone();
+            save_plan(draft);
+            close = true;
+        }
+        if close {
+            self.planner_draft = None;
+        }
+    }
+
+    /// Shows the settings window while settings are being edited.
+    ///
+    /// Edits a copy of the settings; saving applies it to the running app and
+    /// persists it with [`save_settings`], cancelling discards it. The window
+    /// position and visibility aren't edited here, so moves made while the
+    /// window is open are kept.
+    fn show_settings(&mut self, ctx: &egui::Context) {
+        let Some(draft) = self.settings_draft.as_mut() else {
+            return;
+        };
+
+        let mut close = false;
+        let mut save = false;
+
+        ctx.show_viewport_immediate(
+            egui::ViewportId::from_hash_of("settings"),
+            egui::ViewportBuilder::default()
+                .with_title("Praymodoro Settings")
+                .with_inner_size([420.0, 560.0]),
+            |ctx, _class| {
+                egui::TopBottomPanel::bottom("settings_buttons").show(ctx, |ui| {
+                    ui.horizontal(|ui| {
+                        save = ui.button("Save").clicked();
+                        close = ui.button("Cancel").clicked();
+                    });
+                });
+
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        settings_form(ui, draft);
+                    });
+                });
+
+                if ctx.input(|i| i.viewport().close_requested()) {
+                    close = true;
+                }
+            },
+        );
+
+        if save {
+            let settings = draft.clone();
+            self.apply_settings(ctx, settings);
+            close = true;
+        }
+        if close {
+            self.settings_draft = None;
+        }
+    }
+
+    /// Applies edited settings to the running app and saves them.
+    fn apply_settings(&mut self, ctx: &egui::Context, mut settings: Settings) {
+        let mut s = self.state.lock();
+
+        // Keep runtime window state that isn't edited in the settings window
+        settings.window.x = s.settings.window.x;
+        settings.window.y = s.settings.window.y;
+        settings.window.monitor = s.settings.window.monitor.clone();
+        settings.window.monitor_x = s.settings.window.monitor_x;
+        settings.window.monitor_y = s.settings.window.monitor_y;
+        settings.window.visible = s.settings.window.visible;
+        for companion in &mut settings.monitor_companions {
+            if let Some(current) = s
+                .settings
+                .monitor_companions
+                .iter()
+                .find(|c| c.monitor == companion.monitor)
+            {
+                companion.x = current.x;
+                companion.y = current.y;
+            }
+        }
+        for current in &s.settings.monitor_companions {
+            if !settings
+                .monitor_companions
+                .iter()
+                .any(|c| c.monitor == current.monitor)
+            {
+                settings.monitor_companions.push(current.clone());
+            }
+        }
+        s.character = settings.character.clone();
+        s.scale = settings.window.scale;
+        s.settings = settings;
+        let saved = s.settings.clone();
+
+        let new_size = Vec2::new(BASE_WIDTH * s.scale, BASE_HEIGHT * s.scale);
+        let level = s.settings.window.layer.level();
+        drop(s);
+        save_settings(&saved);
+        ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(new_size));
+        ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(level));
+    }
+
+    /// Shows the command palette while it's open.
+    ///
+    /// Typing filters the commands, Up/Down move the highlight, Enter runs the
+    /// highlighted command, and Escape closes the palette. Returns the chosen
+    /// action, or [`TrayAction::None`].
+    fn show_command_palette(&mut self, ctx: &egui::Context) -> TrayAction {
+        let Some(search) = self.palette.as_mut() else {
+            return TrayAction::None;
+        };
+
+        let commands = palette::commands(&self.state.lock());
+        let mut chosen = TrayAction::None;
+        let mut close = false;
+
+        ctx.show_viewport_immediate(
+            egui::ViewportId::from_hash_of("command_palette"),
+            egui::ViewportBuilder::default()
+                .with_title("Praymodoro Commands")
+                .with_inner_size([360.0, 320.0])
+                .with_always_on_top(),
+            |ctx, _class| {
+                let matches = palette::search(&search.query, &commands);
+                let (up, down, enter, escape) = ctx.input(|i| {
+                    (
+                        i.key_pressed(egui::Key::ArrowUp),
+                        i.key_pressed(egui::Key::ArrowDown),
+                        i.key_pressed(egui::Key::Enter),
+                        i.key_pressed(egui::Key::Escape),
+                    )
+                });
+                if down {
+                    search.selected += 1;
+                }
+                if up {
+                    search.selected = search.selected.saturating_sub(1);
+                }
+                search.selected = search.selected.min(matches.len().saturating_sub(1));
+
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    let field = ui.add(
+                        egui::TextEdit::singleline(&mut search.query)
+                            .hint_text("Type a command…")
+                            .desired_width(f32::INFINITY),
+                    );
+                    field.request_focus();
+                    if field.changed() {
+                        search.selected = 0;
+                    }
+                    ui.add_space(4.0);
+
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        for (i, command) in matches.iter().enumerate() {
+                            let row = ui.selectable_label(i == search.selected, &command.label);
+                            if i == search.selected && (up || down) {
+                                row.scroll_to_me(None);
+                            }
+                            if row.clicked() {
+                                chosen = command.action.clone();
+                            }
+                        }
+                    });
+                });
+
+                if enter {
+                    if let Some(command) = matches.get(search.selected) {
+                        chosen = command.action.clone();
+                    }
+                }
+                if escape || ctx.input(|i| i.viewport().close_requested()) {
+                    close = true;
+                }
+            },
+        );
+
+        if close || !matches!(chosen, TrayAction::None) {
+            self.palette = None;
+        }
+        chosen
+    }
+
+    /// Shows the About window while it's open.
+    ///
+    /// Lists the version and build, acknowledges the third-party crates, and
+    /// offers to open the folder settings and stats are saved in.
+    fn show_about(&mut self, ctx: &egui::Context) {
+        if !self.about_open {
+            return;
+        }
+
+        let data_dir = storage::location();
+        let mut close = false;
+
+        ctx.show_viewport_immediate(
+            egui::ViewportId::from_hash_of("about"),
+            egui::ViewportBuilder::default()
+                .with_title("About Praymodoro")
+                .with_inner_size([340.0, 420.0]),
+            |ctx, _class| {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    ui.heading("Praymodoro");
+                    ui.label(format!("Version {}", about::VERSION));
+                    ui.label(about::build_info());
+                    ui.add_space(8.0);
+                    ui.label("A prayerful Pomodoro timer with saint companions.");
+
+                    ui.add_space(8.0);
+                    ui.separator();
+                    ui.label("Built with");
+                    egui::ScrollArea::vertical()
+                        .max_height(200.0)
+                        .show(ui, |ui| {
+                            egui::Grid::new("about_licenses")
+                                .num_columns(2)
+                                .show(ui, |ui| {
+                                    for (name, license) in about::THIRD_PARTY {
+                                        ui.label(*name);
+                                        ui.weak(*license);
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        let open = ui.add_enabled(
+                            data_dir.is_some(),
+                            egui::Button::new("Open Config Folder"),
+                        );
+                        if open.clicked() {
+                            if let Some(dir) = &data_dir {
+                                let _ = about::open_folder(dir);
+                            }
+                        }
+                        close = ui.button("Close").clicked();
+                    });
+                });
+
+                if ctx.input(|i| i.viewport().close_requested()) {
+                    close = true;
+                }
+            },
+        );
+
+        if close {
+            self.about_open = false;
+        }
+    }
+
+    /// Returns whether `action` is held back until the family PIN is entered.
+    ///
+    /// With a PIN set, the family profile can't be left and its settings
+    /// can't be opened or its schedule changed without it.
+    fn needs_pin(&self, action: &TrayAction) -> bool {
+        let s = self.state.lock();
+        let locked = s.settings.profile == Profile::Family && !s.settings.family_pin.is_empty();
+        let guarded = match action {
+            TrayAction::SetProfile(profile) => *profile != Profile::Family,
+            TrayAction::OpenSettings | TrayAction::SetSchedule(_) | TrayAction::SetEngine(_) => {
+                true
+            }
+            _ => false,
+        };
+        locked && guarded
+    }
+
+    /// Shows the family PIN window while it's open.
+    ///
+    /// The held-back action runs once the right PIN is entered.
+    fn show_pin_prompt(&mut self, ctx: &egui::Context) {
+        let Some(prompt) = self.pin_prompt.as_mut() else {
+            return;
+        };
+
+        let mut close = false;
+        let mut submit = false;
+
+        ctx.show_viewport_immediate(
+            egui::ViewportId::from_hash_of("family_pin"),
+            egui::ViewportBuilder::default()
+                .with_title("Family PIN")
+                .with_inner_size([280.0, 120.0]),
+            |ctx, _class| {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    ui.label("Enter the family PIN");
+                    let field =
+                        ui.add(egui::TextEdit::singleline(&mut prompt.entry).password(true));
+                    field.request_focus();
+                    if prompt.wrong {
+                        ui.colored_label(ui.visuals().error_fg_color, "That PIN isn't right.");
+                    }
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        submit = ui.button("OK").clicked();
+                        close = ui.button("Cancel").clicked();
+                    });
+                    if field.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                        submit = true;
+                    }
+                });
+
+                if ctx.input(|i| i.key_pressed(egui::Key::Escape) || i.viewport().close_requested())
+                {
+                    close = true;
+                }
+            },
+        );
+
+        let mut unlocked = false;
+        if submit {
+            unlocked = prompt.entry == self.state.lock().settings.family_pin;
+            prompt.entry.clear();
+            prompt.wrong = !unlocked;
+        }
+        if unlocked || close {
+            if let Some(prompt) = self.pin_prompt.take().filter(|_| unlocked) {
+                self.run_tray_action(prompt.action, ctx);
+            }
+        }
+    }
+
+    /// Shows the add-intention window while it's open.
+    ///
+    /// An added intention is saved and chosen for the coming rests.
+    fn show_intention_input(&mut self, ctx: &egui::Context) {
+        let Some(draft) = self.intention_draft.as_mut() else {
+            return;
+        };
+
+        let mut close = false;
+        let mut add = false;
+
+        ctx.show_viewport_immediate(
+            egui::ViewportId::from_hash_of("add_intention"),
+            egui::ViewportBuilder::default()
+                .with_title("Add Intention")
+                .with_inner_size([320.0, 110.0]),
+            |ctx, _class| {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    ui.label("Pray for…");
+                    let field = ui.text_edit_singleline(draft);
+                    field.request_focus();
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        add = ui.button("Add").clicked();
+                        close = ui.button("Cancel").clicked();
+                    });
+                    if field.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                        add = true;
+                    }
+                });
+
+                if ctx.input(|i| i.key_pressed(egui::Key::Escape) || i.viewport().close_requested())
+                {
+                    close = true;
+                }
+            },
+        );
+
+        let intention = draft.trim().to_string();
+        if add && !intention.is_empty() {
+            let settings = {
+                let mut s = self.state.lock();
+                if !s.settings.intentions.contains(&intention) {
+                    s.settings.intentions.push(intention.clone());
+                }
+                s.settings.intention = intention;
+                s.settings.clone()
+            };
+            save_settings(&settings);
+            close = true;
+        }
+        if close {
+            self.intention_draft = None;
+        }
+    }
+
+    /// Shows the quit confirmation window while it's open.
+    ///
+    /// Asked when quitting mid-work with [`Settings::confirm_quit`] on; the
+    /// app only closes if the user chooses to quit anyway.
+    fn show_quit_confirm(&mut self, ctx: &egui::Context) {
+        if !self.quit_confirm {
+            return;
+        }
+
+        let mut close = false;
+        let mut quit = false;
+
+        ctx.show_viewport_immediate(
+            egui::ViewportId::from_hash_of("quit_confirm"),
+            egui::ViewportBuilder::default()
+                .with_title("Quit Praymodoro")
+                .with_inner_size([280.0, 90.0]),
+            |ctx, _class| {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    ui.label("Finish this pomodoro first?");
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        close = ui.button("Keep Working").clicked();
+                        quit = ui.button("Quit Anyway").clicked();
+                    });
+                });
+
+                if ctx.input(|i| i.key_pressed(egui::Key::Escape) || i.viewport().close_requested())
+                {
+                    close = true;
+                }
+            },
+        );
+
+        if close || quit {
+            self.quit_confirm = false;
+        }
+        if quit {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+        }
+    }
+
+    /// Shows the custom size window while it's open.
+    ///
+    /// Returns [`TrayAction::SetScale`] when a size is applied, so it's
+    /// applied and saved like the presets.
+    fn show_custom_scale(&mut self, ctx: &egui::Context) -> TrayAction {
+        let Some(percent) = self.scale_draft.as_mut() else {
+            return TrayAction::None;
+        };
+
+        let mut close = false;
+        let mut apply = false;
+
+        ctx.show_viewport_immediate(
+            egui::ViewportId::from_hash_of("custom_scale"),
+            egui::ViewportBuilder::default()
+                .with_title("Custom Size")
+                .with_inner_size([240.0, 90.0]),
+            |ctx, _class| {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Size");
+                        ui.add(
+                            egui::DragValue::new(percent)
+                                .range(MIN_SCALE * 100.0..=MAX_SCALE * 100.0)
+                                .suffix("%"),
+                        );
+                    });
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        apply = ui.button("Apply").clicked();
+                        close = ui.button("Cancel").clicked();
+                    });
+                });
+
+                if ctx.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    apply = true;
+                }
+                if ctx.input(|i| i.key_pressed(egui::Key::Escape) || i.viewport().close_requested())
+                {
+                    close = true;
+                }
+            },
+        );
+
+        let scale = (*percent / 100.0).clamp(MIN_SCALE, MAX_SCALE);
+        if apply || close {
+            self.scale_draft = None;
+        }
+        if apply {
+            TrayAction::SetScale(scale)
+        } else {
+            TrayAction::None
+        }
+    }
+
+    /// Shows the sprint window while it's open.
+    ///
+    /// With a sprint in progress it shows the sprint's progress and lets it be
+    /// ended early; otherwise it starts a new sprint from a goal and a number of
+    /// pomodoros. The last finished sprint's report is shown underneath.
+    fn show_sprint(&mut self, ctx: &egui::Context) {
+        let Some(draft) = self.sprint_draft.as_mut() else {
+            return;
+        };
+
+        let (active, last) = {
+            let s = self.state.lock();
+            (
+                s.stats.sprint.clone(),
+                s.stats.sprint_history.last().cloned(),
+            )
+        };
+        let mut close = false;
+        let mut start = false;
+        let mut end = false;
+
+        ctx.show_viewport_immediate(
+            egui::ViewportId::from_hash_of("sprint"),
+            egui::ViewportBuilder::default()
+                .with_title("Sprint")
+                .with_inner_size([320.0, 220.0]),
+            |ctx, _class| {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    if let Some(sprint) = &active {
+                        ui.heading(if sprint.goal.trim().is_empty() {
+                            "Sprint"
+                        } else {
+                            sprint.goal.trim()
+                        });
+                        ui.label(format!("Pomodoros: {}", sprint.progress()));
+                        ui.label(format!("Focus: {}%", sprint.focus_percent()));
+                        ui.label(format!("Prayers kept: {}", sprint.prayers_kept));
+                        ui.add_space(8.0);
+                        ui.horizontal(|ui| {
+                            end = ui.button("End Sprint").clicked();
+                            close = ui.button("Close").clicked();
+                        });
+                    } else {
+                        ui.label("Chain pomodoros toward a single goal.");
+                        ui.add_space(8.0);
+                        egui::Grid::new("sprint_grid")
+                            .num_columns(2)
+                            .show(ui, |ui| {
+                                ui.label("Goal");
+                                ui.text_edit_singleline(&mut draft.goal);
+                                ui.end_row();
+
+                                ui.label("Pomodoros");
+                                ui.add(
+                                    egui::DragValue::new(&mut draft.target)
+                                        .range(1..=MAX_SPRINT_TARGET),
+                                );
+                                ui.end_row();
+                            });
+                        ui.add_space(8.0);
+                        ui.horizontal(|ui| {
+                            start = ui.button("Start").clicked();
+                            close = ui.button("Cancel").clicked();
+                        });
+                    }
+
+                    if let Some(last) = &last {
+                        ui.add_space(8.0);
+                        ui.separator();
+                        ui.label(format!("Last sprint — {}", last.report()));
+                    }
+                });
+
+                if ctx.input(|i| i.viewport().close_requested()) {
+                    close = true;
+                }
+            },
+        );
+
+        let now = Local::now().naive_local();
+        if start {
+            let stats = {
+                let mut s = self.state.lock();
+                s.stats
+                    .start_sprint(draft.goal.trim().to_string(), draft.target, now);
+                s.stats.clone()
+            };
+            save_stats(&stats);
+            close = true;
+        }
+        if end {
+            let mut s = self.state.lock();
+            if let Some(sprint) = s.stats.end_sprint(now) {
+                let stats = s.stats.clone();
+                let settings = s.settings.notifications.clone();
+                drop(s);
+                save_stats(&stats);
+                notify_sprint_finished(&sprint, &settings);
+            }
+            close = true;
+        }
+        if close {
+            self.sprint_draft = None;
+        }
+    }
+}
+
+impl eframe::App for PrayomodoroApp {
+    fn clear_color(&self, _visuals: &egui::Visuals) -> [f32; 4] {
+        [0.0, 0.0, 0.0, 0.0] // Fully transparent background
+    }
+
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Poll tray events on main thread
+        if let Some(ref mut tray) = self.tray {
+            for action in tray.poll_events(&self.state) {
+                self.handle_tray_action(action, ctx);
+            }
+        }
+
+        // Poll global hotkeys
+        if let Some(ref hotkeys) = self.hotkeys {
+            for action in hotkeys.poll_events() {
+                self.handle_tray_action(action, ctx);
+            }
+        }
+
+        // Check if should quit
+        {
+            let s = self.state.lock();
+            if s.should_quit {
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                return;
+            }
+        }
+
+        // Get current state
+        let (mode, formatted_time, character, scale, rosary, touch_setting, sprint) = {
+            let s = self.state.lock();
+            (
+                s.mode,
+                s.formatted_time.clone(),
+                s.character.clone(),
+                s.scale,
+                s.rosary,
+                s.settings.touch_mode,
+                s.stats.sprint.as_ref().map(|sprint| sprint.progress()),
+            )
+        };
+
+        // Switch to touch-friendly interaction once touch input is seen
+        if !self.touch_detected {
+            self.touch_detected = ctx.input(|i| {
+                i.events
+                    .iter()
+                    .any(|event| matches!(event, egui::Event::Touch { .. }))
+            });
+        }
+        let touch_mode = touch_setting || self.touch_detected;
+        let mut menu_action = TrayAction::None;
+        let mut scroll = 0.0;
+
+        // Check if character changed - if so, clear old textures and request full redraw
+        let character_changed = character != self.last_character;
+        if character_changed {
+            // Clear cached textures for the old character to free GPU memory
+            let old_char = &self.last_character;
+            self.textures.retain(|key, _| !key.starts_with(old_char));
+            self.frame_counts
+                .retain(|key, _| !key.starts_with(old_char));
+            self.night_variants
+                .retain(|key, _| !key.starts_with(old_char));
+
+            self.last_character = character.clone();
+            ctx.request_repaint();
+        }
+
+        let (
+            appearance,
+            window,
+            reduce_motion,
+            speech_bubbles,
+            feast_reminders,
+            timer_text,
+            period_complete,
+        ) = {
+            let s = self.state.lock();
+            (
+                s.settings.appearance.clone(),
+                s.settings.window.clone(),
+                s.settings.reduce_motion,
+                s.settings.speech_bubbles,
+                s.settings.feast_reminders,
+                s.settings
+                    .timer_display
+                    .text(s.remaining_seconds, s.period_seconds),
+                format::complete_fraction(s.remaining_seconds, s.period_seconds),
+            )
+        };
+
+        // A click makes the saint hop, or strike their idle pose when motion is reduced
+        let now = ctx.input(|i| i.time);
+
+        // A completed work period gets the same hop, and a word about the day's count
+        let completed_today = self.state.lock().stats.completed_today;
+        if self
+            .completed_seen
+            .is_some_and(|seen| completed_today > seen)
+        {
+            self.reacted_at = Some(now);
+            if speech_bubbles {
+                let count = match completed_today {
+                    1 => "your first pomodoro".to_string(),
+                    n => format!("{} pomodoros", n),
+                };
+                self.quote = Some((format!("Well done! That's {} today.", count), now));
+            }
+        }
+        self.completed_seen = Some(completed_today);
+        let reaction = self
+            .reacted_at
+            .map(|at| (now - at) / REACTION_SECONDS)
+            .filter(|progress| *progress < 1.0);
+        if reaction.is_some() {
+            ctx.request_repaint();
+        } else {
+            self.reacted_at = None;
+        }
+
+        // Fade out of the way when the pointer lingers over the companion
+        let hovering = ctx.input(|i| i.pointer.hover_pos().is_some() && !i.pointer.any_down());
+        if !hovering {
+            self.hover_started = None;
+        }
+        let lingering =
+            hovering && now - *self.hover_started.get_or_insert(now) >= HOVER_FADE_DELAY_SECONDS;
+        let hover_target = if appearance.hover_fade && lingering {
+            appearance.hover_fade_opacity.clamp(0.1, 1.0)
+        } else {
+            1.0
+        };
+        let fade_time = if reduce_motion {
+            0.0
+        } else {
+            HOVER_FADE_SECONDS
+        };
+        let hover_opacity =
+            ctx.animate_value_with_time(egui::Id::new("hover_fade"), hover_target, fade_time);
+
+        let hop = match reaction {
+            Some(progress) if !reduce_motion => {
+                (progress * std::f64::consts::PI).sin() as f32 * REACTION_HOP * scale
+            }
+            _ => 0.0,
+        };
+
+        // Determine sprite to show
+        let sprite = if reaction.is_some() && reduce_motion {
+            "idle"
+        } else {
+            match mode {
+                PomodoroMode::Work => "work",
+                PomodoroMode::Rest => "quick-break",
+            }
+        };
+
+        // Switch to the candle-lit night variant in the evening, if the character has one
+        let night = appearance.is_night(Local::now().hour())
+            && *self
+                .night_variants
+                .entry(format!("{}_{}", character, sprite))
+                .or_insert_with(|| {
+                    characters::has_sprite(&character, &format!("{}-night", sprite))
+                });
+        let sprite = if night {
+            format!("{}-night", sprite)
+        } else {
+            sprite.to_string()
+        };
+        let sprite = sprite.as_str();
+
+        let shown_sprite = format!("{}_{}", character, sprite);
+
+        // Pick the animation frame, holding the first one when motion is reduced
+        let frames = *self
+            .frame_counts
+            .entry(format!("{}_{}", character, sprite))
+            .or_insert_with(|| characters::frame_count(&character, sprite));
+        let sprite = if frames == 0 {
+            sprite.to_string()
+        } else if reduce_motion || frames == 1 {
+            format!("{}_0", sprite)
+        } else {
+            let frame_rate = characters::frame_rate(&character);
+            let frame = (now * frame_rate as f64) as usize % frames;
+            ctx.request_repaint_after(std::time::Duration::from_secs_f32(1.0 / frame_rate));
+            format!("{}_{}", sprite, frame)
+        };
+
+        // Load texture, at full resolution if the downscaled one would look soft
+        let full_resolution = needs_full_resolution(ctx, scale);
+        let texture = self.load_texture(ctx, &character, &sprite, full_resolution);
+
+        // Crossfade from the last texture when the mode or character changes
+        if shown_sprite != self.shown_sprite {
+            self.shown_sprite = shown_sprite;
+            self.fading_from = self
+                .last_texture
+                .take()
+                .filter(|_| !reduce_motion)
+                .map(|tex| (tex, now));
+        }
+        self.last_texture = texture.clone();
+        let fade = match self.fading_from {
+            Some((ref tex, started)) if now - started < CROSSFADE_SECONDS => {
+                ctx.request_repaint();
+                Some((tex.clone(), ((now - started) / CROSSFADE_SECONDS) as f32))
+            }
+            _ => {
+                self.fading_from = None;
+                None
+            }
+        };
+        let silhouette = if appearance.sprite_shadow > 0.0 || appearance.sprite_outline > 0.0 {
+            self.load_silhouette(ctx, &character, &sprite, full_resolution)
+        } else {
+            None
+        };
+
+        let mirror_texture = texture.clone();
+
+        // Central panel with transparent background
+        egui::CentralPanel::default()
+            .frame(egui::Frame::NONE)
+            .show(ctx, |ui| {
+                // Use expected size based on scale, not available_size which can be wrong on first frame
+                let expected_size = Vec2::new(BASE_WIDTH * scale, BASE_HEIGHT * scale);
+                let available_size = ui.available_size();
+                // Use the larger of expected or available to avoid tiny sprites on startup
+                let size = Vec2::new(
+                    available_size.x.max(expected_size.x),
+                    available_size.y.max(expected_size.y),
+                );
+                let rect = Rect::from_min_size(Pos2::ZERO, size);
+
+                // Fade everything painted below (sprite, effects, and timer), never to invisible
+                ui.multiply_opacity(appearance.opacity.clamp(0.25, 1.0) * hover_opacity);
+
+                // Handle dragging - use native OS drag for smooth movement
+                // (clicks are sensed too, for the right-click menu)
+                let response = ui.allocate_rect(rect, Sense::click_and_drag());
+
+                // Scroll over the companion to resize it
+                if response.hovered() {
+                    scroll = ctx.input(|i| i.smooth_scroll_delta.y);
+                }
+
+                if touch_mode {
+                    // Tap-and-hold to drag, so a stray tap doesn't move the window
+                    if response.is_pointer_button_down_on() {
+                        let now = ctx.input(|i| i.time);
+                        let started = *self.press_started.get_or_insert(now);
+                        if now - started >= LONG_PRESS_SECONDS {
+                            ctx.send_viewport_cmd(egui::ViewportCommand::StartDrag);
+                            self.press_started = None;
+                        } else {
+                            ctx.request_repaint();
+                        }
+                    } else {
+                        self.press_started = None;
+                    }
+                } else if response.drag_started() {
+                    // Use native window drag - much smoother than manual position updates
+                    ctx.send_viewport_cmd(egui::ViewportCommand::StartDrag);
+                }
+
+                // Clear the entire window area first (fixes ghosting on transparent windows)
+                ui.painter().rect_filled(
+                    rect,
+                    egui::CornerRadius::ZERO,
+                    Color32::TRANSPARENT,
+                );
+
+                let place_sprite = |tex: &egui::TextureHandle| sprite_rect(size, tex, hop);
+                let uv = sprite_uv(window.flip);
+
+                // Fade out the previous sprite while the new one fades in
+                let fade_in = match fade {
+                    Some((ref old, progress)) => {
+                        ui.painter().image(
+                            old.id(),
+                            place_sprite(old),
+                            uv,
+                            Color32::WHITE.gamma_multiply(1.0 - progress),
+                        );
+                        progress
+                    }
+                    None => 1.0,
+                };
+
+                // Draw character sprite
+                if let Some(tex) = texture {
+                    let sprite_rect = place_sprite(&tex);
+                    if let Some(ref silhouette) = silhouette {
+                        draw_sprite_effects(
+                            ui.painter(),
+                            silhouette,
+                            sprite_rect,
+                            uv,
+                            scale,
+                            &appearance,
+                        );
+                    }
+
+                    ui.painter().image(
+                        tex.id(),
+                        sprite_rect,
+                        uv,
+                        Color32::WHITE.gamma_multiply(fade_in),
+                    );
+                }
+
+                // Draw timer at bottom with parchment background
+                let timer_rect = timer_rect(size, scale);
+                let timer_bg = self.load_timer_bg(ctx);
+                if appearance.timer_effects {
+                    draw_timer_effects(ui.painter(), timer_rect, scale, &appearance);
+                }
+                draw_timer(
+                    ui.painter(),
+                    timer_rect,
+                    timer_bg.as_ref(),
+                    timer_text.as_deref(),
+                    period_complete,
+                    scale,
+                );
+
+                // Draw rosary bead counter above the timer
+                if let Some(rosary) = rosary {
+                    draw_rosary_beads(ui.painter(), rosary, timer_rect, scale);
+                }
+
+                // Draw sprint progress below the timer
+                if let Some(ref sprint) = sprint {
+                    draw_sprint_progress(ui.painter(), sprint, timer_rect, scale);
+                }
+
+                if response.clicked() {
+                    self.reacted_at = Some(now);
+                }
+
+                // Show the exact size while scrolling to resize
+                if self.scaled_at.is_some() {
+                    draw_scale_badge(ui.painter(), rect, scale);
+                }
+
+                // Announce tomorrow's feast once a day, and otherwise speak on
+                // click and now and then on its own
+                if let Some(text) = self.announce_feast(feast_reminders) {
+                    self.quote = Some((text, now));
+                } else if speech_bubbles && (response.clicked() || now >= self.next_quote_at) {
+                    self.say_quote(&character, now);
+                }
+                match &self.quote {
+                    Some((text, said_at)) if now - said_at < QUOTE_SECONDS => {
+                        draw_speech_bubble(ui.painter(), text, rect, scale);
+                    }
+                    _ => self.quote = None,
+                }
+
+                // Right-click menu, for when the tray is hidden or unavailable
+                egui::Popup::context_menu(&response).show(|ui| {
+                    menu_action = companion_menu(ui, &character, scale);
+                });
+
+                // On-window menu button for touch screens, where the tray is hard to reach
+                if touch_mode {
+                    let button_size = MENU_BUTTON_SIZE * scale;
+                    let button_rect = Rect::from_min_size(
+                        Pos2::new(size.x - button_size, 0.0),
+                        Vec2::splat(button_size),
+                    );
+                    let button = ui.allocate_rect(button_rect, Sense::click());
+                    draw_menu_button(ui.painter(), button_rect);
+
+                    egui::Popup::menu(&button).show(|ui| {
+                        menu_action = companion_menu(ui, &character, scale);
+                    });
+                }
+            });
+
+        self.handle_tray_action(menu_action, ctx);
+        let (key_action, key_scale) = self.handle_keys(ctx);
+        self.handle_tray_action(key_action, ctx);
+        self.resize_by(ctx, (scroll * SCROLL_SCALE_RATE).exp() * key_scale);
+        if !self.slide_dock(ctx, window.edge_dock, reduce_motion) {
+            self.keep_on_screen(ctx);
+        }
+        self.show_monitor_companions(
+            ctx,
+            mirror_texture.as_ref(),
+            timer_text.as_deref(),
+            period_complete,
+        );
+        self.show_planner(ctx);
+        self.show_sprint(ctx);
+        self.show_intention_input(ctx);
+        self.show_pin_prompt(ctx);
+        let scale_action = self.show_custom_scale(ctx);
+        self.handle_tray_action(scale_action, ctx);
+        self.show_quit_confirm(ctx);
+        self.show_settings(ctx);
+        self.show_about(ctx);
+        let palette_action = self.show_command_palette(ctx);
+        self.handle_tray_action(palette_action, ctx);
+        self.show_break_overlay(ctx, mode, &formatted_time);
+        self.show_work_prayer(ctx, mode);
+
+        // The timer thread repaints when the time shown changes; this slow
+        // fallback keeps polling the tray and hotkeys while it's paused
+        ctx.request_repaint_after(std::time::Duration::from_secs(1));
+    }
+}
+
+/// Draws a row of rosary beads above the timer showing decade progress.
+///
+/// The first (larger) bead is the Our Father, followed by ten Hail Mary beads.
+/// Beads already prayed are filled; the current bead is highlighted.
+fn draw_rosary_beads(
+    painter: &egui::Painter,
+    rosary: RosaryProgress,
+    timer_rect: Rect,
+    scale: f32,
+) {
+    let bead_color = Color32::from_rgb(74, 55, 40);
+    let current_color = Color32::from_rgb(196, 160, 82);
+    let spacing = 11.0 * scale;
+    let radius = 3.5 * scale;
+    let center_y = timer_rect.top() - 8.0 * scale;
+    let row_width = spacing * (ROSARY_DECADE_BEADS - 1) as f32;
+    let start_x = timer_rect.center().x - row_width / 2.0;
+
+    for bead in 0..ROSARY_DECADE_BEADS {
+        let center = Pos2::new(start_x + spacing * bead as f32, center_y);
+        let radius = if bead == 0 { radius * 1.4 } else { radius };
+        if bead < rosary.bead {
+            painter.circle_filled(center, radius, bead_color);
+        } else if bead == rosary.bead {
+            painter.circle_filled(center, radius, current_color);
+        } else {
+            painter.circle_stroke(center, radius, egui::Stroke::new(1.0 * scale, bead_color));
+        }
+    }
+}
+
+/// Returns the texture cache key for a sprite at the given resolution.
+fn texture_key(character: &str, sprite: &str, full_resolution: bool) -> String {
+    if full_resolution {
+        format!("{}_{}_full", character, sprite)
+    } else {
+        format!("{}_{}", character, sprite)
+    }
+}
+
+/// Returns whether the companion covers more physical pixels than the
+/// downscaled sprites have, as on a 200% display at larger sizes.
+fn needs_full_resolution(ctx: &egui::Context, scale: f32) -> bool {
+    let physical_width = BASE_WIDTH * scale * ctx.pixels_per_point();
+    physical_width > MAX_SPRITE_WIDTH as f32
+}
+
+/// Reads a character sprite from the first asset location that has it.
+///
+/// Unless `full_resolution` is set, the image is resized to
+/// [`MAX_SPRITE_WIDTH`] x [`MAX_SPRITE_HEIGHT`] if it's larger.
+fn read_sprite(character: &str, sprite: &str, full_resolution: bool) -> Option<image::RgbaImage> {
+    characters::sprite_paths(character, sprite)
+        .iter()
+        .find_map(|path| {
+            let image_data = std::fs::read(path).ok()?;
+            let image = image::load_from_memory(&image_data).ok()?;
+            // Resize to save GPU memory (590x1455 -> 295x728)
+            let oversized = image.width() > MAX_SPRITE_WIDTH || image.height() > MAX_SPRITE_HEIGHT;
+            let resized = if oversized && !full_resolution {
+                image.resize(MAX_SPRITE_WIDTH, MAX_SPRITE_HEIGHT, FilterType::Lanczos3)
+            } else {
+                image
+            };
+            Some(resized.to_rgba8())
+        })
+}
+
+/// Draws the sprite's drop shadow and outline behind it, using its silhouette.
+///
+/// The shadow is several faint offset copies of the silhouette, for a soft
+/// edge; the outline is the silhouette nudged in eight directions. Both are
+/// painted in the window, so the window-level shadow can stay off.
+fn draw_sprite_effects(
+    painter: &egui::Painter,
+    silhouette: &egui::TextureHandle,
+    sprite_rect: Rect,
+    uv: Rect,
+    scale: f32,
+    appearance: &AppearanceSettings,
+) {
+    if appearance.sprite_shadow > 0.0 {
+        let offset = Vec2::new(3.0, 4.0) * scale;
+        let spread = 2.0 * scale;
+        let color = Color32::from_black_alpha((appearance.sprite_shadow * 40.0) as u8);
+        for blur in [
+            Vec2::ZERO,
+            Vec2::new(spread, 0.0),
+            Vec2::new(-spread, 0.0),
+            Vec2::new(0.0, spread),
+            Vec2::new(0.0, -spread),
+        ] {
+            painter.image(
+                silhouette.id(),
+                sprite_rect.translate(offset + blur),
+                uv,
+                color,
+            );
+        }
+    }
+
+    if appearance.sprite_outline > 0.0 {
+        let width = 1.5 * scale;
+        let color = appearance.outline_tone.color(appearance.sprite_outline);
+        for i in 0..8 {
+            let angle = i as f32 * std::f32::consts::FRAC_PI_4;
+            let nudge = Vec2::angled(angle) * width;
+            painter.image(silhouette.id(), sprite_rect.translate(nudge), uv, color);
+        }
+    }
+}
+
+/// Draws the sprite's shadow and outline behind the timer pill too, so it
+/// stands out on light wallpapers.
+///
+/// The pill is drawn as a rounded rectangle: faint offset copies for the
+/// shadow, and a stroke just outside its edge for the outline.
+fn draw_timer_effects(
+    painter: &egui::Painter,
+    timer_rect: Rect,
+    scale: f32,
+    appearance: &AppearanceSettings,
+) {
+    let radius = timer_rect.height() / 2.0;
+
+    if appearance.sprite_shadow > 0.0 {
+        let offset = Vec2::new(3.0, 4.0) * scale;
+        let color = Color32::from_black_alpha((appearance.sprite_shadow * 40.0) as u8);
+        for spread in [0.0, 2.0 * scale] {
+            painter.rect_filled(
+                timer_rect.translate(offset).expand(spread),
+                radius + spread,
+                color,
+            );
+        }
+    }
+
+    if appearance.sprite_outline > 0.0 {
+        let width = 1.5 * scale;
+        let color = appearance.outline_tone.color(appearance.sprite_outline);
+        painter.rect_stroke(
+            timer_rect,
+            radius,
+            egui::Stroke::new(width, color),
+            egui::StrokeKind::Outside,
+        );
+    }
+}
+
+/// Draws the breathing pause circle, which swells and shrinks with each breath.
+///
+/// `elapsed` is seconds since the pause began. With `reduce_motion` the circle
+/// is drawn at rest size without animating.
+fn draw_breathing_circle(ui: &mut egui::Ui, elapsed: f64, reduce_motion: bool) {
+    let (rect, _) = ui.allocate_exact_size(Vec2::splat(200.0), Sense::hover());
+    let breath = if reduce_motion {
+        0.5
+    } else {
+        // Eases from 0 to 1 and back over one cycle
+        let phase = elapsed / BREATH_CYCLE_SECONDS * std::f64::consts::TAU;
+        ((1.0 - phase.cos()) / 2.0) as f32
+    };
+    let radius = 50.0 + 40.0 * breath;
+
+    let painter = ui.painter();
+    painter.circle_filled(
+        rect.center(),
+        radius,
+        Color32::from_rgba_unmultiplied(232, 220, 196, 60),
+    );
+    painter.circle_stroke(
+        rect.center(),
+        radius,
+        egui::Stroke::new(2.0, Color32::from_rgb(232, 220, 196)),
+    );
+}
+
+/// Returns where the sprite goes in a companion window of `size`.
+///
+/// The sprite keeps its aspect ratio and stands on the bottom edge, leaving
+/// room above for the speech bubble; `hop` lifts it for the click reaction.
+fn sprite_rect(size: Vec2, texture: &egui::TextureHandle, hop: f32) -> Rect {
+    let image_size = texture.size_vec2();
+    let aspect = image_size.x / image_size.y;
+
+    // Scale to fit window while maintaining aspect ratio
+    let target_height = size.y * 0.85; // Leave room for timer
+    let target_width = target_height * aspect;
+
+    let sprite_size = Vec2::new(target_width.min(size.x), target_height);
+    let sprite_pos = Pos2::new((size.x - sprite_size.x) / 2.0, size.y - sprite_size.y - hop);
+    Rect::from_min_size(sprite_pos, sprite_size)
+}
+
+/// Returns the texture coordinates for the sprite, mirrored when `flip` is set.
+fn sprite_uv(flip: bool) -> Rect {
+    if flip {
+        Rect::from_min_max(Pos2::new(1.0, 0.0), Pos2::new(0.0, 1.0))
+    } else {
+        Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0))
+    }
+}
+
+/// Returns where the timer pill goes in a companion window of `size`.
+///
+/// Original: 130px × 49px, positioned at bottom: 20px.
+fn timer_rect(size: Vec2, scale: f32) -> Rect {
+    let timer_width = 130.0 * scale;
+    let timer_height = 49.0 * scale;
+    let timer_bottom_margin = 20.0 * scale;
+
+    Rect::from_min_size(
+        Pos2::new(
+            (size.x - timer_width) / 2.0,
+            size.y - timer_height - timer_bottom_margin,
+        ),
+        Vec2::new(timer_width, timer_height),
+    )
+}
+
+/// Draws the timer pill: its parchment background, then the time left, or a
+/// progress ring when the display shows no numbers.
+fn draw_timer(
+    painter: &egui::Painter,
+    timer_rect: Rect,
+    background: Option<&egui::TextureHandle>,
+    text: Option<&str>,
+    complete: f32,
+    scale: f32,
+) {
+    if let Some(background) = background {
+        painter.image(
+            background.id(),
+            timer_rect,
+            Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0)),
+            Color32::WHITE,
+        );
+    }
+
+    // Timer text - dark brown like original (#4a3728), serif font
+    let timer_color = Color32::from_rgb(74, 55, 40);
+    if let Some(text) = text {
+        let font_size = 26.0 * scale;
+        painter.text(
+            timer_rect.center(),
+            egui::Align2::CENTER_CENTER,
+            text,
+            egui::FontId::new(font_size, egui::FontFamily::Name("serif".into())),
+            timer_color,
+        );
+    } else {
+        draw_progress_ring(
+            painter,
+            timer_rect.center(),
+            timer_rect.height() * 0.3,
+            complete,
+            timer_color,
+            scale,
+        );
+    }
+}
+
+/// Draws a ring that fills clockwise from the top as the period completes.
+///
+/// Used by the soft-focus timer display in place of numbers.
+fn draw_progress_ring(
+    painter: &egui::Painter,
+    center: Pos2,
+    radius: f32,
+    complete: f32,
+    color: Color32,
+    scale: f32,
+) {
+    let width = 3.0 * scale;
+    painter.circle_stroke(
+        center,
+        radius,
+        egui::Stroke::new(width, color.gamma_multiply(0.25)),
+    );
+
+    // Approximate the arc with a segment every few degrees
+    let steps = (complete * 72.0).ceil() as usize;
+    if steps == 0 {
+        return;
+    }
+    let points = (0..=steps)
+        .map(|i| {
+            let angle = std::f32::consts::TAU * complete * i as f32 / steps as f32;
+            center + radius * Vec2::new(angle.sin(), -angle.cos())
+        })
+        .collect();
+    painter.add(egui::Shape::line(points, egui::Stroke::new(width, color)));
+}
+
+/// Draws the current size (e.g. "115%") on a small tab at the top of the window.
+fn draw_scale_badge(painter: &egui::Painter, rect: Rect, scale: f32) {
+    let badge = Rect::from_center_size(
+        Pos2::new(rect.center().x, rect.top() + 14.0 * scale),
+        Vec2::new(56.0, 20.0) * scale,
+    );
+    painter.rect_filled(badge, 10.0 * scale, Color32::from_black_alpha(160));
+    painter.text(
+        badge.center(),
+        egui::Align2::CENTER_CENTER,
+        format!("{}%", (scale * 100.0).round()),
+        egui::FontId::proportional(12.0 * scale),
+        Color32::WHITE,
+    );
+}
+
+/// Draws a speech bubble with `text` at the top of the window, its tail
+/// pointing down at the saint.
+fn draw_speech_bubble(painter: &egui::Painter, text: &str, rect: Rect, scale: f32) {
+    let ink = Color32::from_rgb(74, 55, 40);
+    let fill = Color32::from_rgb(250, 243, 224);
+    let stroke = egui::Stroke::new(1.0 * scale, ink);
+    let padding = 6.0 * scale;
+    let tail = 6.0 * scale;
+
+    let galley = painter.layout(
+        text.to_string(),
+        egui::FontId::proportional(10.0 * scale),
+        ink,
+        rect.width() - 2.0 * (padding + 2.0 * scale),
+    );
+    let bubble_size = galley.size() + Vec2::splat(2.0 * padding);
+    let bubble = Rect::from_min_size(
+        Pos2::new(
+            rect.center().x - bubble_size.x / 2.0,
+            rect.top() + 2.0 * scale,
+        ),
+        bubble_size,
+    );
+
+    painter.rect(bubble, 6.0 * scale, fill, stroke, egui::StrokeKind::Inside);
+    let left = Pos2::new(bubble.center().x - tail, bubble.bottom() - stroke.width);
+    let right = Pos2::new(bubble.center().x + tail, bubble.bottom() - stroke.width);
+    let tip = Pos2::new(bubble.center().x, bubble.bottom() + tail);
+    painter.add(egui::Shape::convex_polygon(
+        vec![left, tip, right],
+        fill,
+        egui::Stroke::NONE,
+    ));
+    painter.line_segment([left, tip], stroke);
+    painter.line_segment([tip, right], stroke);
+    painter.galley(bubble.min + Vec2::splat(padding), galley, ink);
+}
+
+/// Draws sprint progress (e.g. "2/4") on a small parchment tab below the timer.
+fn draw_sprint_progress(painter: &egui::Painter, progress: &str, timer_rect: Rect, scale: f32) {
+    let tab_size = Vec2::new(44.0 * scale, 16.0 * scale);
+    let tab_rect = Rect::from_center_size(
+        Pos2::new(
+            timer_rect.center().x,
+            timer_rect.bottom() + tab_size.y / 2.0 + 2.0 * scale,
+        ),
+        tab_size,
+    );
+
+    painter.rect_filled(
+        tab_rect,
+        egui::CornerRadius::same((tab_size.y / 2.0) as u8),
+        Color32::from_rgb(232, 220, 196),
+    );
+    painter.text(
+        tab_rect.center(),
+        egui::Align2::CENTER_CENTER,
+        progress,
+        egui::FontId::new(11.0 * scale, egui::FontFamily::Name("serif".into())),
+        Color32::from_rgb(74, 55, 40),
+    );
+}
+
+/// Draws the "≡" menu button used in touch mode.
+fn draw_menu_button(painter: &egui::Painter, rect: Rect) {
+    let color = Color32::from_rgb(74, 55, 40);
+    painter.circle_filled(
+        rect.center(),
+        rect.width() * 0.4,
+        Color32::from_rgba_unmultiplied(245, 235, 210, 220),
+    );
+
+    let half_width = rect.width() * 0.18;
+    let gap = rect.height() * 0.12;
+    let stroke = egui::Stroke::new(rect.width() * 0.05, color);
+    for offset in [-gap, 0.0, gap] {
+        let y = rect.center().y + offset;
+        painter.line_segment(
+            [
+                Pos2::new(rect.center().x - half_width, y),
+                Pos2::new(rect.center().x + half_width, y),
+            ],
+            stroke,
+        );
+    }
+}
+
+/// Edits the custom schedule's division of the hour in place.
+///
+/// Each period starts where the one before it ends, so only end minutes are
+/// edited. Problems found by [`check_segments`] are shown below the periods.
+fn schedule_editor(ui: &mut egui::Ui, segments: &mut Vec<PomodoroSegment>) {
+    let mut removed = None;
+    egui::Grid::new("settings_custom_schedule")
+        .num_columns(3)
+        .show(ui, |ui| {
+            let mut start = 0;
+            for (index, segment) in segments.iter_mut().enumerate() {
+                segment.start_minute = start;
+                ui.horizontal(|ui| {
+                    ui.label(format!(":{:02} to", start));
+                    ui.add(
+                        egui::DragValue::new(&mut segment.end_minute)
+                            .range(start..=60)
+                            .prefix(":"),
+                    );
+                });
+                egui::ComboBox::from_id_salt(("custom_segment", index))
+                    .selected_text(match segment.mode {
+                        PomodoroMode::Work => "Work",
+                        PomodoroMode::Rest => "Prayer",
+                    })
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut segment.mode, PomodoroMode::Work, "Work");
+                        ui.selectable_value(&mut segment.mode, PomodoroMode::Rest, "Prayer");
+                    });
+                if ui.small_button("✕").clicked() {
+                    removed = Some(index);
+                }
+                ui.end_row();
+                start = segment.end_minute;
+            }
+        });
+    if let Some(index) = removed {
+        segments.remove(index);
+    }
+    if ui.button("Add Period").clicked() {
+        let last = segments.last();
+        segments.push(PomodoroSegment {
+            start_minute: last.map_or(0, |segment| segment.end_minute),
+            end_minute: 60,
+            mode: match last.map(|segment| segment.mode) {
+                Some(PomodoroMode::Work) => PomodoroMode::Rest,
+                _ => PomodoroMode::Work,
+            },
+        });
+    }
+    if let Err(error) = check_segments(segments) {
+        ui.colored_label(
+            ui.visuals().error_fg_color,
+            format!(
+                "The hourly schedule is followed until this is fixed: {}.",
+                error
+            ),
+        );
+    }
+}
+
+/// Renders the preferences form of the settings window, editing `settings` in place.
+fn settings_form(ui: &mut egui::Ui, settings: &mut Settings) {
+    ui.heading("Timer");
+    egui::Grid::new("settings_timer")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Schedule");
+            egui::ComboBox::from_id_salt("schedule")
+                .selected_text(settings.schedule.label())
+                .show_ui(ui, |ui| {
+                    for schedule in ScheduleKind::ALL {
+                        ui.selectable_value(&mut settings.schedule, *schedule, schedule.label());
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Timing");
+            egui::ComboBox::from_id_salt("engine")
+                .selected_text(settings.engine.label())
+                .show_ui(ui, |ui| {
+                    for engine in TimerEngine::ALL {
+                        ui.selectable_value(&mut settings.engine, *engine, engine.label());
+                    }
+                });
+            ui.end_row();
+        });
+    if settings.schedule == ScheduleKind::Custom {
+        schedule_editor(ui, &mut settings.custom_schedule);
+    }
+
+    ui.add_space(8.0);
+    ui.heading("Companion");
+    egui::Grid::new("settings_companion")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Character");
+            egui::ComboBox::from_id_salt("character")
+                .selected_text(format_character_name(&settings.character))
+                .show_ui(ui, |ui| {
+                    for char_name in characters::available() {
+                        ui.selectable_value(
+                            &mut settings.character,
+                            char_name.clone(),
+                            format_character_name(char_name),
+                        );
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Size");
+            ui.add(
+                egui::Slider::new(&mut settings.window.scale, MIN_SCALE..=MAX_SCALE)
+                    .custom_formatter(|scale, _| format!("{}%", (scale * 100.0).round())),
+            );
+            ui.end_row();
+
+            ui.label("Layer");
+            egui::ComboBox::from_id_salt("layer")
+                .selected_text(settings.window.layer.label())
+                .show_ui(ui, |ui| {
+                    for layer in WindowLayer::ALL {
+                        ui.selectable_value(&mut settings.window.layer, *layer, layer.label());
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Timer display");
+            egui::ComboBox::from_id_salt("timer_display")
+                .selected_text(settings.timer_display.label())
+                .show_ui(ui, |ui| {
+                    for display in TimerDisplay::ALL {
+                        ui.selectable_value(&mut settings.timer_display, *display, display.label());
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Menu bar title");
+            egui::ComboBox::from_id_salt("tray_title")
+                .selected_text(settings.tray_title.label())
+                .show_ui(ui, |ui| {
+                    for title in TrayTitle::ALL {
+                        ui.selectable_value(&mut settings.tray_title, *title, title.label());
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Opacity");
+            ui.add(
+                egui::Slider::new(&mut settings.appearance.opacity, 0.25..=1.0)
+                    .custom_formatter(|opacity, _| format!("{}%", (opacity * 100.0).round())),
+            );
+            ui.end_row();
+
+            ui.checkbox(&mut settings.appearance.hover_fade, "Fade on hover to");
+            ui.add_enabled(
+                settings.appearance.hover_fade,
+                egui::Slider::new(&mut settings.appearance.hover_fade_opacity, 0.1..=1.0)
+                    .custom_formatter(|opacity, _| format!("{}%", (opacity * 100.0).round())),
+            );
+            ui.end_row();
+
+            ui.checkbox(&mut settings.appearance.night_sprites, "Night sprites from");
+            ui.add_enabled(
+                settings.appearance.night_sprites,
+                egui::DragValue::new(&mut settings.appearance.evening_hour)
+                    .range(12..=23)
+                    .suffix(":00"),
+            );
+            ui.end_row();
+
+            ui.label("Shadow");
+            ui.add(egui::Slider::new(
+                &mut settings.appearance.sprite_shadow,
+                0.0..=1.0,
+            ));
+            ui.end_row();
+
+            ui.label("Outline");
+            ui.add(egui::Slider::new(
+                &mut settings.appearance.sprite_outline,
+                0.0..=1.0,
+            ));
+            ui.end_row();
+
+            ui.label("Outline color");
+            egui::ComboBox::from_id_salt("outline_tone")
+                .selected_text(settings.appearance.outline_tone.label())
+                .show_ui(ui, |ui| {
+                    for tone in OutlineTone::ALL {
+                        ui.selectable_value(
+                            &mut settings.appearance.outline_tone,
+                            *tone,
+                            tone.label(),
+                        );
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Timer");
+            ui.checkbox(&mut settings.appearance.timer_effects, "Shadow and outline");
+            ui.end_row();
+        });
+    ui.checkbox(&mut settings.window.snap_to_edges, "Snap to screen edges");
+    ui.checkbox(&mut settings.window.edge_dock, "Dock to side edges");
+    ui.checkbox(&mut settings.window.flip, "Face the other way");
+    ui.checkbox(&mut settings.touch_mode, "Touch mode");
+    ui.checkbox(&mut settings.speech_bubbles, "Saint quotes");
+    ui.horizontal(|ui| {
+        ui.label("Announce tomorrow's");
+        egui::ComboBox::from_id_salt("feast_reminders")
+            .selected_text(settings.feast_reminders.label())
+            .show_ui(ui, |ui| {
+                for choice in FeastReminders::ALL {
+                    ui.selectable_value(&mut settings.feast_reminders, *choice, choice.label());
+                }
+            });
+    });
+    ui.checkbox(&mut settings.multi_companion, "Companion on every monitor");
+    if settings.multi_companion && !settings.monitor_companions.is_empty() {
+        egui::Grid::new("settings_monitor_companions")
+            .num_columns(3)
+            .show(ui, |ui| {
+                for companion in &mut settings.monitor_companions {
+                    ui.label(&companion.monitor);
+                    ui.add(
+                        egui::Slider::new(&mut companion.scale, MIN_SCALE..=MAX_SCALE)
+                            .custom_formatter(|scale, _| format!("{}%", (scale * 100.0).round())),
+                    );
+                    ui.checkbox(&mut companion.flip, "Flip");
+                    ui.end_row();
+                }
+            });
+    }
+
+    ui.add_space(8.0);
+    ui.heading("Prayer");
+    ui.checkbox(&mut settings.rosary_mode, "Rosary during rest");
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut settings.break_overlay, "Break overlay:");
+        egui::ComboBox::from_id_salt("overlay_style")
+            .selected_text(settings.overlay_style.label())
+            .show_ui(ui, |ui| {
+                for style in OverlayStyle::ALL {
+                    ui.selectable_value(&mut settings.overlay_style, *style, style.label());
+                }
+            });
+    });
+    ui.checkbox(&mut settings.breathing_pause, "Breathing pause");
+    ui.checkbox(&mut settings.simple_prayers, "Simple prayers only");
+    if !settings.intentions.is_empty() {
+        ui.label("Intentions");
+        let mut removed = None;
+        for (index, intention) in settings.intentions.iter().enumerate() {
+            ui.horizontal(|ui| {
+                if ui.small_button("✕").clicked() {
+                    removed = Some(index);
+                }
+                ui.label(intention);
+            });
+        }
+        if let Some(index) = removed {
+            let intention = settings.intentions.remove(index);
+            if settings.intention == intention {
+                settings.intention.clear();
+            }
+        }
+    }
+    ui.checkbox(&mut settings.reduce_motion, "Reduce motion");
+    ui.checkbox(&mut settings.confirm_quit, "Confirm quitting during work");
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut settings.prayer_before_work, "Prayer before work:");
+        egui::ComboBox::from_id_salt("work_prayer")
+            .selected_text(work_prayer(&settings.work_prayer).title)
+            .show_ui(ui, |ui| {
+                for prayer in WORK_PRAYERS {
+                    ui.selectable_value(
+                        &mut settings.work_prayer,
+                        prayer.id.to_string(),
+                        prayer.title,
+                    );
+                }
+            });
+    });
+
+    ui.add_space(8.0);
+    ui.heading("Talks");
+    let lecture = &mut settings.lecture;
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut lecture.enabled, "Play a talk during breaks of");
+        ui.add_enabled(
+            lecture.enabled,
+            egui::DragValue::new(&mut lecture.min_break_minutes)
+                .range(1..=60)
+                .suffix(" min"),
+        );
+        ui.label("or more");
+    });
+    ui.add_enabled(
+        lecture.enabled,
+        egui::TextEdit::singleline(&mut lecture.file).hint_text("Path to an MP3, Ogg, or WAV file"),
+    );
+    ui.label("It picks up where it left off and stops when work resumes.");
+
+    ui.add_space(8.0);
+    ui.heading("Notifications");
+    ui.checkbox(&mut settings.notifications.rest_start, "When prayer begins");
+    ui.checkbox(&mut settings.notifications.work_start, "When work begins");
+    ui.checkbox(
+        &mut settings.notifications.minute_warning,
+        "A minute before the end",
+    );
+    ui.checkbox(&mut settings.notifications.sound, "Play a sound");
+
+    ui.add_space(8.0);
+    ui.heading("Sabbath");
+    let sabbath = &mut settings.sabbath;
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut sabbath.reminder, "Remind me on Saturday from");
+        ui.add_enabled(
+            sabbath.reminder,
+            egui::DragValue::new(&mut sabbath.reminder_hour)
+                .range(0..=23)
+                .suffix(":00"),
+        );
+    });
+    ui.checkbox(
+        &mut sabbath.sunday_rest,
+        "Put the companion away on Sundays",
+    );
+
+    ui.add_space(8.0);
+    ui.heading("Network");
+    ui.checkbox(
+        &mut settings.offline,
+        "Stay offline (no update checks or status sharing)",
+    );
+
+    if settings.profile == Profile::Family {
+        ui.add_space(8.0);
+        ui.heading("Family");
+        ui.horizontal(|ui| {
+            ui.label("PIN");
+            ui.add(
+                egui::TextEdit::singleline(&mut settings.family_pin)
+                    .password(true)
+                    .desired_width(80.0),
+            );
+        });
+        ui.label("Asked for before leaving this profile or changing its settings.");
+    }
+
+    ui.add_space(8.0);
+    ui.heading("Accountability Partner");
+    let sharing = &mut settings.presence;
+    ui.checkbox(&mut sharing.enabled, "Share my status with a partner");
+    ui.add_enabled_ui(sharing.enabled, |ui| {
+        egui::Grid::new("settings_presence")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("Publish to");
+                ui.add(
+                    egui::TextEdit::singleline(&mut sharing.publish_to)
+                        .hint_text("Synced folder or URL"),
+                );
+                ui.end_row();
+
+                ui.label("Partner's status");
+                ui.add(
+                    egui::TextEdit::singleline(&mut sharing.partner_from)
+                        .hint_text("Folder, file, or URL"),
+                );
+                ui.end_row();
+
+                ui.label("Partner's key");
+                ui.text_edit_singleline(&mut sharing.partner_key);
+                ui.end_row();
+
+                // Reading the key touches storage, so keep it for the life of the window
+                let key_id = egui::Id::new("presence_public_key");
+                let key = ui
+                    .data_mut(|data| data.get_temp::<String>(key_id))
+                    .unwrap_or_else(presence::public_key);
+                ui.label("My key");
+                ui.horizontal(|ui| {
+                    ui.monospace(format!("{}…", &key[..16]));
+                    if ui.button("Copy").clicked() {
+                        ui.ctx().copy_text(key.clone());
+                    }
+                    if ui.button("New Key").clicked() {
+                        ui.data_mut(|data| data.insert_temp(key_id, presence::regenerate_key()));
+                    } else {
+                        ui.data_mut(|data| data.insert_temp(key_id, key));
+                    }
+                });
+                ui.end_row();
+            });
+    });
+    ui.label("Give your partner your key so their Praymodoro can trust your status.");
+
+    ui.add_space(8.0);
+    ui.heading("Shortcuts");
+    egui::Grid::new("settings_hotkeys")
+        .num_columns(2)
+        .show(ui, |ui| {
+            let hotkeys = &mut settings.hotkeys;
+            for (label, binding) in [
+                ("Show/hide", &mut hotkeys.toggle_visibility),
+                ("Skip period", &mut hotkeys.skip_period),
+                ("Pause", &mut hotkeys.pause),
+                ("Next character", &mut hotkeys.next_character),
+                ("Command palette", &mut hotkeys.command_palette),
+            ] {
+                ui.label(label);
+                ui.text_edit_singleline(binding);
+                ui.end_row();
+            }
+        });
+    ui.label("Shortcut changes take effect after a restart.");
+
+    ui.add_space(8.0);
+    ui.heading("In-Window Keys");
+    egui::Grid::new("settings_keymap")
+        .num_columns(2)
+        .show(ui, |ui| {
+            let keymap = &mut settings.keymap;
+            for (label, binding) in [
+                ("Pause", &mut keymap.pause),
+                ("Hide", &mut keymap.hide),
+                ("Larger", &mut keymap.scale_up),
+                ("Smaller", &mut keymap.scale_down),
+                ("Nudge left", &mut keymap.nudge_left),
+                ("Nudge right", &mut keymap.nudge_right),
+                ("Nudge up", &mut keymap.nudge_up),
+                ("Nudge down", &mut keymap.nudge_down),
+            ] {
+                ui.label(label);
+                ui.text_edit_singleline(binding);
+                ui.end_row();
+            }
+        });
+    ui.label("Keys work while the companion has focus, e.g. Space, H, Plus, ArrowLeft.");
+}
+
+/// Renders the in-window companion menu, mirroring the tray menu actions.
+///
+/// Returns the action chosen by the user, or [`TrayAction::None`].
+fn companion_menu(ui: &mut egui::Ui, character: &str, scale: f32) -> TrayAction {
+    let mut action = TrayAction::None;
+
+    ui.menu_button("Size", |ui| {
+        for size in SCALE_PRESETS {
+            let label = format!("{}%", (size * 100.0) as i32);
+            if ui.radio((*size - scale).abs() < 0.01, label).clicked() {
+                action = TrayAction::SetScale(*size);
+                ui.close();
+            }
+        }
+        if ui.button("Custom…").clicked() {
+            action = TrayAction::OpenCustomScale;
+            ui.close();
+        }
+    });
+
+    ui.menu_button("Character", |ui| {
+        for char_name in characters::available() {
+            if ui
+                .radio(*char_name == character, format_character_name(char_name))
+                .clicked()
+            {
+                action = TrayAction::SetCharacter(char_name.clone());
+                ui.close();
+            }
+        }
+    });
+
+    ui.separator();
+
+    if ui.button("Face the Other Way").clicked() {
+        action = TrayAction::ToggleFlip;
+        ui.close();
+    }
+    if ui.button("Hide Character").clicked() {
+        action = TrayAction::ToggleVisibility;
+        ui.close();
+    }
+    if ui.button("Quit").clicked() {
+        action = TrayAction::Quit;
+        ui.close();
+    }
+
+    action
 }
@@ -0,0 +1,34 @@
+//! Launching Praymodoro when the user logs in.
+//!
+//! Registration is kept by the operating system (a launch agent on macOS, the
+//! registry on Windows, an XDG autostart entry on Linux) rather than in the
+//! settings, so it's read back from there at startup.
+
+use auto_launch::{AutoLaunch, AutoLaunchBuilder};
+
+/// Returns the login item for the running executable, or `None` if its path can't be found.
+fn launcher() -> Option<AutoLaunch> {
+    let exe = std::env::current_exe().ok()?;
+    AutoLaunchBuilder::new()
+        .set_app_name("Praymodoro")
+        .set_app_path(exe.to_str()?)
+        .set_use_launch_agent(true)
+        .build()
+        .ok()
+}
+
+/// Returns whether Praymodoro is registered to start at login.
+pub fn is_enabled() -> bool {
+    launcher().is_some_and(|launcher| launcher.is_enabled().unwrap_or(false))
+}
+
+/// Registers or unregisters Praymodoro to start at login.
+pub fn set_enabled(enabled: bool) -> Result<(), String> {
+    let launcher = launcher().ok_or("can't find the Praymodoro executable")?;
+    let result = if enabled {
+        launcher.enable()
+    } else {
+        launcher.disable()
+    };
+    result.map_err(|e| e.to_string())
+}
@@ -0,0 +1,246 @@
+//! A small liturgical calendar of the major feasts of the Roman Rite.
+//!
+//! Fixed-date feasts come from a table; the feasts that move with Easter are
+//! worked out for each year. Only the better-known celebrations are listed,
+//! along with the memorials of the built-in saints. Transfers (e.g. when a
+//! solemnity falls on a Sunday of Advent or Lent) are not applied.
+
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+
+/// How a celebration ranks in the liturgical calendar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FeastRank {
+    /// A memorial, usually of a saint.
+    Memorial,
+    /// A feast.
+    Feast,
+    /// A solemnity, the highest rank.
+    Solemnity,
+}
+
+/// A celebration on a particular day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Feast {
+    /// Name as it reads after "the feast of", e.g. "St. Thomas More".
+    pub name: &'static str,
+    /// Rank of the celebration.
+    pub rank: FeastRank,
+}
+
+/// A season of the liturgical year.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Season {
+    /// From the fourth Sunday before Christmas to Christmas Eve.
+    Advent,
+    /// From Christmas to the Baptism of the Lord.
+    Christmas,
+    /// From Ash Wednesday to Holy Saturday, counting the Triduum.
+    Lent,
+    /// From Easter to Pentecost.
+    Easter,
+    /// The rest of the year.
+    OrdinaryTime,
+}
+
+impl Season {
+    /// Returns the season's name.
+    pub fn label(self) -> &'static str {
+        match self {
+            Season::Advent => "Advent",
+            Season::Christmas => "Christmas",
+            Season::Lent => "Lent",
+            Season::Easter => "Easter",
+            Season::OrdinaryTime => "Ordinary Time",
+        }
+    }
+}
+
+/// Celebrations on a fixed date, as (month, day, name, rank).
+const FIXED_FEASTS: &[(u32, u32, &str, FeastRank)] = &[
+    (1, 1, "Mary, Mother of God", FeastRank::Solemnity),
+    (1, 25, "the Conversion of St. Paul", FeastRank::Feast),
+    (1, 28, "St. Thomas Aquinas", FeastRank::Memorial),
+    (2, 2, "the Presentation of the Lord", FeastRank::Feast),
+    (2, 22, "the Chair of St. Peter", FeastRank::Feast),
+    (3, 17, "St. Patrick", FeastRank::Memorial),
+    (3, 19, "St. Joseph", FeastRank::Solemnity),
+    (3, 25, "the Annunciation of the Lord", FeastRank::Solemnity),
+    (4, 25, "St. Mark", FeastRank::Feast),
+    (5, 3, "Sts. Philip and James", FeastRank::Feast),
+    (5, 14, "St. Matthias", FeastRank::Feast),
+    (5, 31, "the Visitation", FeastRank::Feast),
+    (6, 22, "St. Thomas More", FeastRank::Memorial),
+    (
+        6,
+        24,
+        "the Nativity of St. John the Baptist",
+        FeastRank::Solemnity,
+    ),
+    (6, 29, "Sts. Peter and Paul", FeastRank::Solemnity),
+    (7, 3, "St. Thomas the Apostle", FeastRank::Feast),
+    (7, 22, "St. Mary Magdalene", FeastRank::Feast),
+    (7, 25, "St. James", FeastRank::Feast),
+    (8, 6, "the Transfiguration", FeastRank::Feast),
+    (8, 10, "St. Lawrence", FeastRank::Feast),
+    (8, 15, "the Assumption", FeastRank::Solemnity),
+    (8, 24, "St. Bartholomew", FeastRank::Feast),
+    (8, 27, "St. Monica", FeastRank::Memorial),
+    (8, 28, "St. Augustine", FeastRank::Memorial),
+    (9, 8, "the Nativity of Mary", FeastRank::Feast),
+    (9, 14, "the Exaltation of the Holy Cross", FeastRank::Feast),
+    (9, 21, "St. Matthew", FeastRank::Feast),
+    (9, 29, "the Archangels", FeastRank::Feast),
+    (10, 18, "St. Luke", FeastRank::Feast),
+    (10, 28, "Sts. Simon and Jude", FeastRank::Feast),
+    (11, 1, "All Saints", FeastRank::Solemnity),
+    (
+        11,
+        9,
+        "the Dedication of the Lateran Basilica",
+        FeastRank::Feast,
+    ),
+    (11, 30, "St. Andrew", FeastRank::Feast),
+    (12, 8, "the Immaculate Conception", FeastRank::Solemnity),
+    (12, 25, "the Nativity of the Lord", FeastRank::Solemnity),
+    (12, 26, "St. Stephen", FeastRank::Feast),
+    (12, 27, "St. John the Apostle", FeastRank::Feast),
+    (12, 28, "the Holy Innocents", FeastRank::Feast),
+];
+
+/// Celebrations that move with Easter, as (days after Easter, name, rank).
+const MOVABLE_FEASTS: &[(u64, &str, FeastRank)] = &[
+    (0, "Easter", FeastRank::Solemnity),
+    (39, "the Ascension", FeastRank::Solemnity),
+    (49, "Pentecost", FeastRank::Solemnity),
+    (56, "the Holy Trinity", FeastRank::Solemnity),
+    (60, "Corpus Christi", FeastRank::Solemnity),
+    (68, "the Sacred Heart", FeastRank::Solemnity),
+];
+
+/// Returns the date of Easter Sunday in `year`, by the Gregorian computus.
+pub fn easter(year: i32) -> NaiveDate {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32).expect("Easter is a valid date")
+}
+
+/// Returns the liturgical season `date` falls in.
+pub fn season_on(date: NaiveDate) -> Season {
+    let year = date.year();
+    let easter = easter(year);
+    let christmas = NaiveDate::from_ymd_opt(year, 12, 25).expect("Christmas is a valid date");
+    // The first Sunday of Advent is the fourth Sunday before Christmas
+    let advent = christmas
+        - Days::new(u64::from(christmas.weekday().num_days_from_sunday()))
+        - Days::new(if christmas.weekday() == Weekday::Sun {
+            28
+        } else {
+            21
+        });
+    // The Baptism of the Lord is the Sunday after Epiphany
+    let epiphany = NaiveDate::from_ymd_opt(year, 1, 6).expect("Epiphany is a valid date");
+    let baptism = epiphany + Days::new(u64::from(7 - epiphany.weekday().num_days_from_sunday()));
+
+    if date <= baptism || date >= christmas {
+        Season::Christmas
+    } else if date >= advent {
+        Season::Advent
+    } else if date >= easter - Days::new(46) && date < easter {
+        Season::Lent
+    } else if date >= easter && date <= easter + Days::new(49) {
+        Season::Easter
+    } else {
+        Season::OrdinaryTime
+    }
+}
+
+/// Returns the highest-ranking celebration on `date`, if any.
+pub fn feast_on(date: NaiveDate) -> Option<Feast> {
+    let fixed = FIXED_FEASTS
+        .iter()
+        .filter(|(month, day, _, _)| *month == date.month() && *day == date.day())
+        .map(|(_, _, name, rank)| Feast { name, rank: *rank });
+
+    let easter = easter(date.year());
+    let movable = MOVABLE_FEASTS
+        .iter()
+        .filter(|(offset, _, _)| easter.checked_add_days(Days::new(*offset)) == Some(date))
+        .map(|(_, name, rank)| Feast { name, rank: *rank });
+
+    fixed.chain(movable).max_by_key(|feast| feast.rank)
+}
+
+/// Returns the saint's announcement of tomorrow's celebration, if there is
+/// one of at least `min_rank`.
+pub fn announcement(today: NaiveDate, min_rank: FeastRank) -> Option<String> {
+    let feast = feast_on(today.succ_opt()?).filter(|feast| feast.rank >= min_rank)?;
+    Some(match feast.rank {
+        FeastRank::Solemnity => format!("Tomorrow is the solemnity of {}.", feast.name),
+        FeastRank::Feast => format!("Tomorrow is the feast of {}.", feast.name),
+        FeastRank::Memorial => format!("Tomorrow we remember {}.", feast.name),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn easter_dates() {
+        assert_eq!(easter(2024), date(2024, 3, 31));
+        assert_eq!(easter(2025), date(2025, 4, 20));
+        assert_eq!(easter(2026), date(2026, 4, 5));
+        assert_eq!(easter(2038), date(2038, 4, 25));
+    }
+
+    #[test]
+    fn movable_feasts_follow_easter() {
+        assert_eq!(feast_on(date(2026, 5, 24)).unwrap().name, "Pentecost");
+        assert_eq!(feast_on(date(2026, 6, 4)).unwrap().name, "Corpus Christi");
+    }
+
+    #[test]
+    fn seasons() {
+        assert_eq!(season_on(date(2026, 1, 11)), Season::Christmas);
+        assert_eq!(season_on(date(2026, 1, 12)), Season::OrdinaryTime);
+        assert_eq!(season_on(date(2026, 2, 17)), Season::OrdinaryTime);
+        assert_eq!(season_on(date(2026, 2, 18)), Season::Lent);
+        assert_eq!(season_on(date(2026, 4, 5)), Season::Easter);
+        assert_eq!(season_on(date(2026, 5, 24)), Season::Easter);
+        assert_eq!(season_on(date(2026, 5, 25)), Season::OrdinaryTime);
+        assert_eq!(season_on(date(2026, 11, 29)), Season::Advent);
+        assert_eq!(season_on(date(2026, 11, 28)), Season::OrdinaryTime);
+        assert_eq!(season_on(date(2022, 11, 27)), Season::Advent);
+        assert_eq!(season_on(date(2026, 12, 25)), Season::Christmas);
+    }
+
+    #[test]
+    fn announces_tomorrow_at_or_above_the_rank() {
+        assert_eq!(
+            announcement(date(2026, 6, 21), FeastRank::Memorial).as_deref(),
+            Some("Tomorrow we remember St. Thomas More.")
+        );
+        assert_eq!(announcement(date(2026, 6, 21), FeastRank::Feast), None);
+        assert_eq!(
+            announcement(date(2026, 8, 14), FeastRank::Solemnity).as_deref(),
+            Some("Tomorrow is the solemnity of the Assumption.")
+        );
+        assert_eq!(announcement(date(2026, 8, 16), FeastRank::Memorial), None);
+    }
+}
@@ -0,0 +1,205 @@
+//! Character registry discovered from the `assets/characters` directory.
+//!
+//! Each subdirectory containing a `work.png` sprite is a character. The list
+//! is scanned once at startup; if no asset directory can be found, the
+//! built-in [`AVAILABLE_CHARACTERS`] are used so the menus are never empty.
+//!
+//! A character directory may also hold a `manifest.toml`:
+//!
+//! ```toml
+//! devotions = ["augustine-restless-heart"]
+//! frame_rate = 4
+//! ```
+//!
+//! `devotions` lists [`crate::prayers::Prayer`] ids associated with the saint,
+//! which come up more often during rest while that saint is shown.
+//!
+//! Sprites may be animated by numbering their frames instead of providing a
+//! single image: `work_0.png`, `work_1.png`, and so on, played at the
+//! manifest's `frame_rate` (frames per second).
+
+use crate::state::AVAILABLE_CHARACTERS;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Characters discovered at startup.
+static CHARACTERS: OnceLock<Vec<String>> = OnceLock::new();
+
+/// Manifests of characters that have one, read on first use.
+static MANIFESTS: OnceLock<HashMap<String, Manifest>> = OnceLock::new();
+
+/// Frame rate of animated sprites whose manifest doesn't set one.
+const DEFAULT_FRAME_RATE: f32 = 4.0;
+
+/// File name of a character's manifest within its directory.
+pub const MANIFEST_FILE: &str = "manifest.toml";
+
+/// Contents of a character's `manifest.toml`.
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct Manifest {
+    /// Ids of prayers associated with the character.
+    pub devotions: Vec<String>,
+    /// Frames per second of the character's animated sprites.
+    pub frame_rate: Option<f32>,
+}
+
+/// Returns the directories that may contain character assets, in search order.
+///
+/// Covers the macOS app bundle, the executable's directory, and the working
+/// directory during development.
+pub fn character_dirs() -> Vec<PathBuf> {
+    let asset_path = PathBuf::from("assets/characters");
+    let mut dirs = Vec::new();
+
+    if let Some(exe_dir) = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(PathBuf::from))
+    {
+        dirs.push(exe_dir.join(&asset_path));
+        dirs.push(exe_dir.join("../Resources").join(&asset_path));
+    }
+
+    dirs.push(asset_path);
+    dirs.push(PathBuf::from("../assets/characters"));
+    // For development - run from project root
+    dirs.push(PathBuf::from("src-egui/assets/characters"));
+    dirs
+}
+
+/// Returns the candidate paths for a character's sprite, in search order.
+pub fn sprite_paths(character: &str, sprite: &str) -> Vec<PathBuf> {
+    character_dirs()
+        .into_iter()
+        .map(|dir| dir.join(character).join(format!("{}.png", sprite)))
+        .collect()
+}
+
+/// Scans the first existing character directory for characters.
+fn discover() -> Vec<String> {
+    for dir in character_dirs() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+
+        let mut found: Vec<String> = entries
+            .flatten()
+            .filter(|entry| {
+                entry.path().join("work.png").is_file() || entry.path().join("work_0.png").is_file()
+            })
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .collect();
+
+        if !found.is_empty() {
+            // Keep built-in characters in their usual order, then any extras alphabetically
+            found.sort_by_key(|name| {
+                let builtin = AVAILABLE_CHARACTERS
+                    .iter()
+                    .position(|b| *b == name.as_str());
+                (builtin.unwrap_or(usize::MAX), name.clone())
+            });
+            return found;
+        }
+    }
+
+    AVAILABLE_CHARACTERS
+        .iter()
+        .map(|name| name.to_string())
+        .collect()
+}
+
+/// Returns the available character identifiers.
+pub fn available() -> &'static [String] {
+    CHARACTERS.get_or_init(discover)
+}
+
+/// Reads the manifest of character `id` from the first directory that has one.
+fn read_manifest(id: &str) -> Option<Manifest> {
+    character_dirs().into_iter().find_map(|dir| {
+        let contents = std::fs::read_to_string(dir.join(id).join(MANIFEST_FILE)).ok()?;
+        toml::from_str(&contents).ok()
+    })
+}
+
+/// Returns the manifest of character `id`, if it has one.
+fn manifest(id: &str) -> Option<&'static Manifest> {
+    MANIFESTS
+        .get_or_init(|| {
+            available()
+                .iter()
+                .filter_map(|name| Some((name.clone(), read_manifest(name)?)))
+                .collect()
+        })
+        .get(id)
+}
+
+/// Returns the prayer ids character `id` declares as devotions.
+///
+/// Empty if the character has no manifest.
+pub fn devotions(id: &str) -> &'static [String] {
+    manifest(id).map_or(&[], |manifest| manifest.devotions.as_slice())
+}
+
+/// Returns the frames per second of character `id`'s animated sprites.
+pub fn frame_rate(id: &str) -> f32 {
+    manifest(id)
+        .and_then(|manifest| manifest.frame_rate)
+        .filter(|rate| *rate > 0.0)
+        .unwrap_or(DEFAULT_FRAME_RATE)
+}
+
+/// Returns the number of numbered frames (`sprite_0.png`, ...) of a sprite.
+///
+/// Zero means the sprite is a single still image.
+pub fn frame_count(character: &str, sprite: &str) -> usize {
+    character_dirs()
+        .into_iter()
+        .map(|dir| dir.join(character))
+        .find(|dir| dir.join(format!("{}_0.png", sprite)).is_file())
+        .map_or(0, |dir| frames_in(&dir, sprite))
+}
+
+/// Returns the number of numbered frames of `sprite` in the character directory `dir`.
+pub fn frames_in(dir: &Path, sprite: &str) -> usize {
+    (0..)
+        .take_while(|i| dir.join(format!("{}_{}.png", sprite, i)).is_file())
+        .count()
+}
+
+/// Returns whether a character has `sprite`, as a single image or animation frames.
+pub fn has_sprite(character: &str, sprite: &str) -> bool {
+    sprite_paths(character, sprite)
+        .iter()
+        .any(|path| path.is_file())
+        || frame_count(character, sprite) > 0
+}
+
+/// Checks that `id` is an available character.
+///
+/// The error message lists the available characters, for display to the user.
+pub fn validate(id: &str) -> Result<(), String> {
+    if available().iter().any(|name| name == id) {
+        Ok(())
+    } else {
+        Err(format!(
+            "unknown character `{}` (available: {})",
+            id,
+            available().join(", ")
+        ))
+    }
+}
+
+/// Returns the character after `current`, wrapping around.
+///
+/// Unknown identifiers cycle to the first character.
+pub fn next_character(current: &str) -> &'static str {
+    let characters = available();
+    let index = characters
+        .iter()
+        .position(|name| name == current)
+        .map_or(0, |i| (i + 1) % characters.len());
+    &characters[index]
+}
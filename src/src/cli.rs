@@ -0,0 +1,416 @@
+//! Command-line flags for scripting how the companion starts.
+//!
+//! Flags override the saved settings for this launch:
+//!
+//! ```text
+//! praymodoro --hidden --character thomas-more --scale 1.5 --schedule liturgy-of-the-hours
+//! ```
+//!
+//! Subcommands help with editing files by hand instead of starting the app:
+//!
+//! ```text
+//! praymodoro schema settings > settings.schema.json
+//! praymodoro validate ~/.config/praymodoro/settings.json
+//! praymodoro report --month 2026-10 --output october.html
+//! praymodoro replay # checks the last day of timer events against the engine
+//! praymodoro tui    # built with --features tui
+//! ```
+
+use crate::characters::{self, Manifest, MANIFEST_FILE};
+use crate::events::{self, EventLog};
+use crate::prayers::REST_PRAYERS;
+use crate::report;
+use crate::settings::{self, Settings};
+use crate::state::{MAX_SCALE, MIN_SCALE};
+use crate::stats;
+use crate::timer::{self, check_segments, ScheduleKind};
+#[cfg(feature = "tui")]
+use crate::tui;
+use chrono::{Datelike, Days, Local, NaiveDate};
+use clap::{Parser, Subcommand, ValueEnum};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// Praymodoro command-line arguments.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// Start with the companion window hidden (use the tray to show it).
+    #[arg(long)]
+    pub hidden: bool,
+
+    /// Saint character to show (e.g. thomas-more).
+    #[arg(long, value_parser = parse_character)]
+    pub character: Option<String>,
+
+    /// Window scale, from 0.5 (50%) to 2.0 (200%).
+    #[arg(long, value_parser = parse_scale)]
+    pub scale: Option<f32>,
+
+    /// Timer schedule to follow.
+    #[arg(long, value_enum)]
+    pub schedule: Option<ScheduleKind>,
+
+    /// Keep settings and stats in this directory instead of the user config
+    /// directory, e.g. next to the executable for a portable install.
+    #[arg(long, value_name = "DIR")]
+    pub data_dir: Option<PathBuf>,
+
+    /// Print today's statistics as JSON and exit.
+    #[arg(long)]
+    pub stats: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Subcommands that run instead of starting the app.
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Print the JSON Schema of a file format.
+    Schema {
+        /// File format to describe.
+        #[arg(value_enum)]
+        kind: FileKind,
+    },
+    /// Check a settings file or character manifest for errors.
+    Validate {
+        /// File to check.
+        file: PathBuf,
+        /// File format, guessed from the file name if omitted.
+        #[arg(long, value_enum)]
+        kind: Option<FileKind>,
+    },
+    /// Write a printable monthly report of focus and prayer as HTML.
+    Report {
+        /// Month to report on, as YYYY-MM (defaults to the current month).
+        #[arg(long, value_parser = parse_month)]
+        month: Option<NaiveDate>,
+        /// File to write the report to instead of standard output.
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Feed the timer's event log back through the engine and report where it
+    /// differs from what was recorded.
+    Replay {
+        /// Event log to replay instead of the saved one.
+        file: Option<PathBuf>,
+    },
+    /// Run the timer in the terminal instead of on the desktop.
+    #[cfg(feature = "tui")]
+    Tui,
+}
+
+/// File formats that can be described and validated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum FileKind {
+    /// `settings.json`.
+    Settings,
+    /// A character's `manifest.toml`.
+    Character,
+}
+
+impl FileKind {
+    /// Guesses the format of `path` from its file name.
+    fn of(path: &Path) -> Self {
+        if path.file_name().is_some_and(|name| name == MANIFEST_FILE) {
+            FileKind::Character
+        } else {
+            FileKind::Settings
+        }
+    }
+}
+
+/// Runs a subcommand, returning the process exit code.
+pub fn run(command: Command) -> i32 {
+    match command {
+        Command::Schema { kind } => {
+            let schema = match kind {
+                FileKind::Settings => schemars::schema_for!(Settings),
+                FileKind::Character => schemars::schema_for!(Manifest),
+            };
+            match serde_json::to_string_pretty(&schema) {
+                Ok(json) => {
+                    println!("{}", json);
+                    0
+                }
+                Err(_) => 1,
+            }
+        }
+        Command::Validate { file, kind } => {
+            let kind = kind.unwrap_or_else(|| FileKind::of(&file));
+            match validate(&file, kind) {
+                Ok(()) => {
+                    println!("{}: ok", file.display());
+                    0
+                }
+                Err(errors) => {
+                    for error in errors {
+                        eprintln!("{}: {}", file.display(), error);
+                    }
+                    1
+                }
+            }
+        }
+        Command::Report { month, output } => {
+            let today = Local::now().date_naive();
+            let first = month.unwrap_or(today - Days::new(u64::from(today.day0())));
+            let last = report::month_days(first).last().unwrap_or(first);
+            let html = report::monthly(first, &stats::load_stats().days_between(first, last));
+            match output {
+                Some(path) => match std::fs::write(&path, html) {
+                    Ok(()) => 0,
+                    Err(error) => {
+                        eprintln!("{}: {}", path.display(), error);
+                        1
+                    }
+                },
+                None => {
+                    print!("{}", html);
+                    0
+                }
+            }
+        }
+        Command::Replay { file } => {
+            let log = match file {
+                Some(path) => match read_events(&path) {
+                    Ok(log) => log,
+                    Err(error) => {
+                        eprintln!("{}: {}", path.display(), error);
+                        return 1;
+                    }
+                },
+                None => events::load_events(),
+            };
+            let custom = settings::load_settings().custom_schedule;
+            let found = log.discrepancies(&timer::replay(&log, &custom));
+            for discrepancy in &found {
+                println!("{}", discrepancy);
+            }
+            println!(
+                "{} events replayed, {} discrepancies",
+                log.events.len(),
+                found.len()
+            );
+            if found.is_empty() {
+                0
+            } else {
+                1
+            }
+        }
+        #[cfg(feature = "tui")]
+        Command::Tui => tui::run(),
+    }
+}
+
+/// Reads an event log saved by the timer.
+fn read_events(path: &Path) -> Result<EventLog, String> {
+    let contents = std::fs::read_to_string(path).map_err(|error| error.to_string())?;
+    serde_json::from_str(&contents).map_err(|error| error.to_string())
+}
+
+/// Checks that `path` is a valid file of the given kind.
+///
+/// Unlike loading, which skips what it doesn't understand, validation
+/// reports unknown keys and out-of-range values. Parse errors include the
+/// line and column of the problem.
+fn validate(path: &Path, kind: FileKind) -> Result<(), Vec<String>> {
+    let contents = std::fs::read_to_string(path).map_err(|error| vec![error.to_string()])?;
+    let errors = match kind {
+        FileKind::Settings => validate_settings(&contents)?,
+        FileKind::Character => {
+            validate_manifest(&contents, path.parent().unwrap_or(Path::new(".")))?
+        }
+    };
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Returns the problems in the contents of a `settings.json`.
+fn validate_settings(contents: &str) -> Result<Vec<String>, Vec<String>> {
+    let written: Value = serde_json::from_str(contents).map_err(|error| vec![error.to_string()])?;
+    let settings: Settings =
+        serde_json::from_str(contents).map_err(|error| vec![error.to_string()])?;
+    let known = serde_json::to_value(&settings).map_err(|error| vec![error.to_string()])?;
+
+    let mut errors = Vec::new();
+    unknown_keys(&written, &known, "", &mut errors);
+    if let Err(error) = check_scale(settings.window.scale) {
+        errors.push(format!("window.scale: {}", error));
+    }
+    for (i, companion) in settings.monitor_companions.iter().enumerate() {
+        if let Err(error) = check_scale(companion.scale) {
+            errors.push(format!("monitor_companions[{}].scale: {}", i, error));
+        }
+    }
+    if let Err(error) = check_segments(&settings.custom_schedule) {
+        errors.push(format!("custom_schedule: {}", error));
+    }
+    if settings.sabbath.reminder_hour > 23 {
+        errors.push("sabbath.reminder_hour: must be between 0 and 23".to_string());
+    }
+    if let Err(error) = characters::validate(&settings.character) {
+        errors.push(format!("character: {}", error));
+    }
+    Ok(errors)
+}
+
+/// Returns the problems in the contents of the `manifest.toml` in `dir`.
+fn validate_manifest(contents: &str, dir: &Path) -> Result<Vec<String>, Vec<String>> {
+    let written: Value = toml::from_str(contents).map_err(|error| vec![error.to_string()])?;
+    let manifest: Manifest = toml::from_str(contents).map_err(|error| vec![error.to_string()])?;
+    let known = serde_json::to_value(&manifest).map_err(|error| vec![error.to_string()])?;
+
+    let mut errors = Vec::new();
+    unknown_keys(&written, &known, "", &mut errors);
+    for devotion in &manifest.devotions {
+        if !REST_PRAYERS.iter().any(|prayer| prayer.id == devotion) {
+            errors.push(format!("devotions: unknown prayer `{}`", devotion));
+        }
+    }
+    if let Some(rate) = manifest.frame_rate {
+        if rate <= 0.0 {
+            errors.push("frame_rate: must be greater than 0".to_string());
+        }
+    }
+
+    let still = |sprite: &str| dir.join(format!("{}.png", sprite)).is_file();
+    if !still("work") && characters::frames_in(dir, "work") == 0 {
+        errors.push(format!(
+            "no `work.png` or `work_0.png` sprite in {}",
+            dir.display()
+        ));
+    }
+    if manifest.frame_rate.is_some()
+        && ["work", "quick-break", "idle"]
+            .iter()
+            .all(|sprite| characters::frames_in(dir, sprite) == 0)
+    {
+        errors.push("frame_rate: no animated sprites (`work_0.png`, ...) to play".to_string());
+    }
+    Ok(errors)
+}
+
+/// Reports keys in `written` that the parsed file, re-serialized as `known`, lacks.
+///
+/// Loading ignores keys it doesn't know, so a misspelled key silently falls
+/// back to its default; this finds them, at any depth.
+fn unknown_keys(written: &Value, known: &Value, path: &str, errors: &mut Vec<String>) {
+    match (written, known) {
+        (Value::Object(written), Value::Object(known)) => {
+            for (key, value) in written {
+                let key_path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                match known.get(key) {
+                    Some(known) => unknown_keys(value, known, &key_path, errors),
+                    None => errors.push(format!("unknown key `{}`", key_path)),
+                }
+            }
+        }
+        (Value::Array(written), Value::Array(known)) => {
+            for (i, (value, known)) in written.iter().zip(known).enumerate() {
+                unknown_keys(value, known, &format!("{}[{}]", path, i), errors);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Checks a `--character` value against the discovered characters.
+fn parse_character(value: &str) -> Result<String, String> {
+    characters::validate(value)?;
+    Ok(value.to_string())
+}
+
+/// Parses a `--month` value into the first day of the month.
+fn parse_month(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(&format!("{}-01", value), "%Y-%m-%d")
+        .map_err(|_| format!("`{}` is not a month like 2026-10", value))
+}
+
+/// Parses and range-checks a `--scale` value.
+fn parse_scale(value: &str) -> Result<f32, String> {
+    let scale: f32 = value
+        .parse()
+        .map_err(|_| format!("`{}` is not a number", value))?;
+    check_scale(scale)
+}
+
+/// Checks that `scale` is within the supported window scales.
+fn check_scale(scale: f32) -> Result<f32, String> {
+    if (MIN_SCALE..=MAX_SCALE).contains(&scale) {
+        Ok(scale)
+    } else {
+        Err(format!(
+            "scale must be between {} and {}",
+            MIN_SCALE, MAX_SCALE
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_with_defaults_are_valid() {
+        let json = serde_json::to_string(&Settings::default()).unwrap();
+        assert_eq!(validate_settings(&json), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn misspelled_settings_keys_are_reported() {
+        let json = r#"{ "charcter": "thomas-more", "window": { "scael": 1.0 } }"#;
+        assert_eq!(
+            validate_settings(json),
+            Ok(vec![
+                "unknown key `charcter`".to_string(),
+                "unknown key `window.scael`".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn out_of_range_scale_is_reported() {
+        let json = r#"{ "window": { "scale": 3.0 } }"#;
+        assert_eq!(
+            validate_settings(json),
+            Ok(vec![
+                "window.scale: scale must be between 0.5 and 2".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn manifest_problems_are_reported() {
+        let manifest = "devotions = [\"no-such-prayer\"]\nframe_rat = 4\n";
+        let errors = validate_manifest(manifest, Path::new("no-such-character")).unwrap();
+        assert!(errors.contains(&"unknown key `frame_rat`".to_string()));
+        assert!(errors.contains(&"devotions: unknown prayer `no-such-prayer`".to_string()));
+        assert!(errors
+            .iter()
+            .any(|error| error.starts_with("no `work.png`")));
+    }
+
+    #[test]
+    fn shipped_manifests_are_valid() {
+        for character in crate::state::AVAILABLE_CHARACTERS {
+            let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("assets/characters")
+                .join(character);
+            let contents = std::fs::read_to_string(dir.join(MANIFEST_FILE)).unwrap();
+            assert_eq!(
+                validate_manifest(&contents, &dir),
+                Ok(Vec::new()),
+                "{}",
+                character
+            );
+        }
+    }
+}
@@ -0,0 +1,268 @@
+//! A log of the timer's last day, for tracking down reported timing bugs.
+//!
+//! The timer records every new period, pause, skip, restart, and change of
+//! schedule or engine, but not its ticks. Events more than a day older than
+//! the newest are dropped as new ones arrive. The log is saved as
+//! `events.json`, and `praymodoro replay` feeds it back through the engine
+//! with [`timer::replay`](crate::timer::replay) to compare what the engine
+//! does now with what was recorded.
+
+use crate::format;
+use crate::state::PomodoroMode;
+use crate::storage;
+use crate::timer::{Replay, ReplayedPeriod, ScheduleKind, TimerEngine};
+use chrono::{NaiveDateTime, TimeDelta};
+use serde::{Deserialize, Serialize};
+
+/// How many hours of events the log keeps.
+const LOG_HOURS: i64 = 24;
+
+/// Most events the log keeps, however short the span they cover.
+const LOG_LIMIT: usize = 5000;
+
+/// How far, in seconds, a replay may drift from the log before it counts as a
+/// discrepancy. The timer ticks about once a second, not on the second.
+const REPLAY_TOLERANCE: i64 = 2;
+
+/// What happened to the timer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EventKind {
+    /// The timer started, as the app was launched.
+    Started,
+    /// A new period began.
+    PeriodStarted,
+    /// The countdown was paused.
+    Paused,
+    /// The countdown was resumed.
+    Resumed,
+    /// The current period was skipped.
+    Skipped,
+    /// The current period was restarted.
+    Restarted,
+    /// A different schedule or engine was chosen.
+    SettingsChanged,
+}
+
+impl EventKind {
+    /// Returns a short description for the `replay` output.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventKind::Started => "started",
+            EventKind::PeriodStarted => "period started",
+            EventKind::Paused => "paused",
+            EventKind::Resumed => "resumed",
+            EventKind::Skipped => "skipped",
+            EventKind::Restarted => "restarted",
+            EventKind::SettingsChanged => "settings changed",
+        }
+    }
+}
+
+/// An event, with the controls the timer had and the period it showed
+/// right after.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimerEvent {
+    /// Local time of the event, to the second.
+    pub at: NaiveDateTime,
+    /// What happened.
+    pub kind: EventKind,
+    /// Schedule in the settings.
+    pub schedule: ScheduleKind,
+    /// Engine in the settings.
+    pub engine: TimerEngine,
+    /// Whether the countdown was paused.
+    pub paused: bool,
+    /// Mode of the period shown.
+    pub mode: PomodoroMode,
+    /// Seconds remaining in the period shown.
+    pub remaining: i32,
+}
+
+/// The timer's recent events, oldest first.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EventLog {
+    /// Events of the last day.
+    pub events: Vec<TimerEvent>,
+}
+
+impl EventLog {
+    /// Adds `event`, dropping events more than a day older than it.
+    pub fn record(&mut self, event: TimerEvent) {
+        let cutoff = event.at - TimeDelta::hours(LOG_HOURS);
+        self.events.retain(|old| old.at >= cutoff);
+        self.events.push(event);
+        let excess = self.events.len().saturating_sub(LOG_LIMIT);
+        self.events.drain(..excess);
+    }
+
+    /// Describes where `replay` of this log differs from what was recorded.
+    ///
+    /// An event differs if the replay shows another mode, or a remaining time
+    /// off by more than a couple of seconds, right after it. A change of mode
+    /// differs if only one of the log and the replay has it.
+    pub fn discrepancies(&self, replay: &Replay) -> Vec<String> {
+        let mut found = Vec::new();
+        for (event, shown) in self.events.iter().zip(&replay.at_events) {
+            if event.mode != shown.mode
+                || (event.remaining - shown.remaining).abs() > REPLAY_TOLERANCE as i32
+            {
+                found.push(format!(
+                    "{}  {}: logged {} {}, replayed {} {}",
+                    event.at,
+                    event.kind.as_str(),
+                    event.mode.as_str(),
+                    format::clock(event.remaining),
+                    shown.mode.as_str(),
+                    format::clock(shown.remaining),
+                ));
+            }
+        }
+
+        let near =
+            |a: NaiveDateTime, b: NaiveDateTime| (a - b).num_seconds().abs() <= REPLAY_TOLERANCE;
+        let logged: Vec<&TimerEvent> = self
+            .events
+            .iter()
+            .filter(|event| event.kind == EventKind::PeriodStarted)
+            .collect();
+        for event in &logged {
+            let replayed =
+                |change: &ReplayedPeriod| change.mode == event.mode && near(change.at, event.at);
+            if !replay.changes.iter().any(replayed) {
+                found.push(format!(
+                    "{}  logged a {} period the replay doesn't start",
+                    event.at,
+                    event.mode.as_str(),
+                ));
+            }
+        }
+        for change in &replay.changes {
+            let recorded =
+                |event: &&TimerEvent| event.mode == change.mode && near(event.at, change.at);
+            if !logged.iter().any(recorded) {
+                found.push(format!(
+                    "{}  replay starts a {} period missing from the log",
+                    change.at,
+                    change.mode.as_str(),
+                ));
+            }
+        }
+        found
+    }
+}
+
+/// Loads the event log from storage, or an empty log if there is none.
+pub fn load_events() -> EventLog {
+    storage::load("events.json").unwrap_or_default()
+}
+
+/// Queues the event log to be saved; failures are retried and reported by
+/// [`storage::save`].
+pub fn save_events(log: &EventLog) {
+    storage::save("events.json", log);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(hour: u32, minute: u32, second: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, 16)
+            .unwrap()
+            .and_hms_opt(hour, minute, second)
+            .unwrap()
+    }
+
+    fn event(at: NaiveDateTime, kind: EventKind, mode: PomodoroMode, remaining: i32) -> TimerEvent {
+        TimerEvent {
+            at,
+            kind,
+            schedule: ScheduleKind::Hourly,
+            engine: TimerEngine::ClockAligned,
+            paused: false,
+            mode,
+            remaining,
+        }
+    }
+
+    #[test]
+    fn record_drops_events_older_than_a_day() {
+        let mut log = EventLog::default();
+        log.record(event(
+            at(9, 0, 0),
+            EventKind::Started,
+            PomodoroMode::Work,
+            1500,
+        ));
+        log.record(event(
+            at(9, 30, 0),
+            EventKind::Paused,
+            PomodoroMode::Work,
+            1500,
+        ));
+        let next_day = at(9, 15, 0) + TimeDelta::hours(24);
+        log.record(event(next_day, EventKind::Resumed, PomodoroMode::Work, 600));
+
+        let kinds: Vec<EventKind> = log.events.iter().map(|event| event.kind).collect();
+        assert_eq!(kinds, [EventKind::Paused, EventKind::Resumed]);
+    }
+
+    #[test]
+    fn record_keeps_at_most_the_limit() {
+        let mut log = EventLog::default();
+        for second in 0..LOG_LIMIT as i64 + 10 {
+            let at = at(0, 0, 0) + TimeDelta::seconds(second);
+            log.record(event(at, EventKind::Paused, PomodoroMode::Work, 1500));
+        }
+
+        assert_eq!(log.events.len(), LOG_LIMIT);
+        assert_eq!(log.events[0].at, at(0, 0, 10));
+    }
+
+    #[test]
+    fn a_late_period_is_a_discrepancy() {
+        let mut log = EventLog::default();
+        log.record(event(
+            at(9, 24, 0),
+            EventKind::Started,
+            PomodoroMode::Work,
+            60,
+        ));
+        log.record(event(
+            at(9, 25, 10),
+            EventKind::PeriodStarted,
+            PomodoroMode::Rest,
+            290,
+        ));
+        let replay = Replay {
+            at_events: vec![
+                ReplayedPeriod {
+                    at: at(9, 24, 0),
+                    mode: PomodoroMode::Work,
+                    remaining: 60,
+                },
+                ReplayedPeriod {
+                    at: at(9, 25, 10),
+                    mode: PomodoroMode::Rest,
+                    remaining: 290,
+                },
+            ],
+            changes: vec![ReplayedPeriod {
+                at: at(9, 25, 0),
+                mode: PomodoroMode::Rest,
+                remaining: 300,
+            }],
+        };
+
+        assert_eq!(
+            log.discrepancies(&replay),
+            [
+                "2026-10-16 09:25:10  logged a rest period the replay doesn't start",
+                "2026-10-16 09:25:00  replay starts a rest period missing from the log",
+            ]
+        );
+    }
+}
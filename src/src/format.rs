@@ -0,0 +1,99 @@
+//! Duration formatting shared by the companion, tray, stats, and notifications.
+//!
+//! Two styles are provided: a clock style for countdowns, and a compact
+//! spoken style for sentences such as notifications and sprint reports. Both
+//! switch to hours once a duration reaches an hour instead of showing
+//! minutes past 60 (e.g. "61:01"). The companion can also show a countdown
+//! as whole minutes or as a percentage of the period.
+
+/// Seconds in an hour.
+const HOUR: i32 = 3600;
+
+/// Formats seconds as a countdown clock.
+///
+/// Under an hour this is `MM:SS`; from an hour on it's `H:MM:SS`. Negative
+/// values are shown as zero.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(clock(90), "01:30");
+/// assert_eq!(clock(3661), "1:01:01");
+/// ```
+pub fn clock(seconds: i32) -> String {
+    let seconds = seconds.max(0);
+    let hours = seconds / HOUR;
+    let mins = seconds % HOUR / 60;
+    let secs = seconds % 60;
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, mins, secs)
+    } else {
+        format!("{:02}:{:02}", mins, secs)
+    }
+}
+
+/// Formats seconds as a compact spoken duration.
+///
+/// Seconds are only shown for durations under a minute. Negative values are
+/// shown as zero.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(spoken(45), "45 s");
+/// assert_eq!(spoken(25 * 60), "25 m");
+/// assert_eq!(spoken(3661), "1 h 01 m");
+/// ```
+pub fn spoken(seconds: i32) -> String {
+    let seconds = seconds.max(0);
+    let hours = seconds / HOUR;
+    let mins = seconds % HOUR / 60;
+    if hours > 0 {
+        format!("{} h {:02} m", hours, mins)
+    } else if mins > 0 {
+        format!("{} m", mins)
+    } else {
+        format!("{} s", seconds)
+    }
+}
+
+/// Formats seconds as a countdown in whole minutes, rounding up.
+///
+/// Rounding up means "1m" is shown until the very end rather than "0m".
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(minutes(23 * 60 + 10), "24m");
+/// assert_eq!(minutes(3661), "1h 02m");
+/// ```
+pub fn minutes(seconds: i32) -> String {
+    let mins = (seconds.max(0) + 59) / 60;
+    if mins >= 60 {
+        format!("{}h {:02}m", mins / 60, mins % 60)
+    } else {
+        format!("{}m", mins)
+    }
+}
+
+/// Formats how much of a period is complete as a percentage.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(percent(15 * 60, 25 * 60), "40%");
+/// ```
+pub fn percent(remaining: i32, duration: i32) -> String {
+    format!(
+        "{}%",
+        (complete_fraction(remaining, duration) * 100.0).floor()
+    )
+}
+
+/// Returns how much of a period is complete, from 0.0 to 1.0.
+pub fn complete_fraction(remaining: i32, duration: i32) -> f32 {
+    if duration <= 0 {
+        return 0.0;
+    }
+    (1.0 - remaining as f32 / duration as f32).clamp(0.0, 1.0)
+}
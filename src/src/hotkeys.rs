@@ -0,0 +1,74 @@
+//! Global keyboard shortcuts.
+//!
+//! Registers system-wide hotkeys that work even when the companion window
+//! isn't focused. Bindings come from [`HotkeySettings`]; by default:
+//! - `Ctrl+Alt+H` (`Cmd+Alt+H` on macOS) - Show/hide the companion
+//! - `Ctrl+Alt+S` - Skip the current period
+//! - `Ctrl+Alt+P` - Pause/resume the countdown
+//! - `Ctrl+Alt+N` - Cycle to the next saint character
+//! - `Ctrl+Alt+K` - Open the command palette
+
+use crate::settings::HotkeySettings;
+use crate::tray::TrayAction;
+use global_hotkey::hotkey::HotKey;
+use global_hotkey::{GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState};
+
+/// Owns the registered global hotkeys and translates their events into actions.
+pub struct HotkeyManager {
+    _manager: GlobalHotKeyManager,
+    /// Registered hotkeys and the action each one triggers.
+    bindings: Vec<(HotKey, TrayAction)>,
+}
+
+impl HotkeyManager {
+    /// Registers the global hotkeys configured in `settings`.
+    ///
+    /// Must be called on the main thread. Bindings that are empty, fail to
+    /// parse, or are already taken by another application are skipped.
+    /// Returns `None` if the platform doesn't support global hotkeys.
+    pub fn new(settings: &HotkeySettings) -> Option<Self> {
+        let manager = GlobalHotKeyManager::new().ok()?;
+
+        let requested = [
+            (&settings.toggle_visibility, TrayAction::ToggleVisibility),
+            (&settings.skip_period, TrayAction::SkipPeriod),
+            (&settings.pause, TrayAction::TogglePause),
+            (&settings.next_character, TrayAction::NextCharacter),
+            (&settings.command_palette, TrayAction::OpenCommandPalette),
+        ];
+
+        let mut bindings = Vec::new();
+        for (binding, action) in requested {
+            if binding.trim().is_empty() {
+                continue;
+            }
+            if let Ok(hotkey) = binding.parse::<HotKey>() {
+                if manager.register(hotkey).is_ok() {
+                    bindings.push((hotkey, action));
+                }
+            }
+        }
+
+        Some(Self {
+            _manager: manager,
+            bindings,
+        })
+    }
+
+    /// Polls for hotkey presses.
+    ///
+    /// Should be called frequently (typically in the main UI update loop).
+    /// Returns the actions of every press since the last call.
+    pub fn poll_events(&self) -> Vec<TrayAction> {
+        GlobalHotKeyEvent::receiver()
+            .try_iter()
+            .filter(|event| event.state == HotKeyState::Pressed)
+            .filter_map(|event| {
+                self.bindings
+                    .iter()
+                    .find(|(hotkey, _)| event.id == hotkey.id())
+                    .map(|(_, action)| action.clone())
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,85 @@
+//! Notes jotted on the break overlay during prayer.
+//!
+//! Each day with notes has one journal entry holding its notes in the order
+//! they were written. The journal is saved as `journal.json`.
+
+use crate::storage;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
+
+/// A note and when it was written.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JournalNote {
+    /// Local time the note was written.
+    pub time: NaiveTime,
+    /// Text of the note.
+    pub text: String,
+}
+
+/// The notes of a single day.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JournalEntry {
+    /// Local date the notes were written on.
+    pub date: NaiveDate,
+    /// Notes, oldest first.
+    pub notes: Vec<JournalNote>,
+}
+
+/// All journal entries, oldest first.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Journal {
+    /// One entry for each day with notes.
+    pub entries: Vec<JournalEntry>,
+}
+
+impl Journal {
+    /// Adds `text` to the entry for the day of `now`, starting one if needed.
+    pub fn add(&mut self, now: NaiveDateTime, text: String) {
+        let note = JournalNote {
+            time: now.time(),
+            text,
+        };
+        match self.entries.last_mut() {
+            Some(entry) if entry.date == now.date() => entry.notes.push(note),
+            _ => self.entries.push(JournalEntry {
+                date: now.date(),
+                notes: vec![note],
+            }),
+        }
+    }
+}
+
+/// Adds a note written at `now` to the saved journal.
+pub fn add_note(now: NaiveDateTime, text: String) {
+    let mut journal: Journal = storage::load("journal.json").unwrap_or_default();
+    journal.add(now, text);
+    storage::save("journal.json", &journal);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, day)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn notes_are_grouped_by_day() {
+        let mut journal = Journal::default();
+        journal.add(at(16, 9), "Call Anne".to_string());
+        journal.add(at(16, 14), "Psalm 46".to_string());
+        journal.add(at(17, 10), "Rest".to_string());
+
+        assert_eq!(journal.entries.len(), 2);
+        assert_eq!(journal.entries[0].notes.len(), 2);
+        assert_eq!(journal.entries[0].notes[1].text, "Psalm 46");
+        assert_eq!(journal.entries[1].date, at(17, 0).date());
+    }
+}
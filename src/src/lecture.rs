@@ -0,0 +1,199 @@
+//! A long audio talk heard a little at a time during long breaks.
+//!
+//! When enabled, a local audio file (e.g. a retreat talk) plays during rest
+//! periods of at least [`LectureSettings::min_break_minutes`] and stops as soon
+//! as work resumes, the timer is paused, or the companion is snoozed. The
+//! position reached is saved as `lecture.json` every few seconds while it
+//! plays, so the next long break picks up where the last one left off, even
+//! after a restart. Choosing a different file, or reaching the end, starts it
+//! from the beginning.
+
+use crate::settings::LectureSettings;
+use crate::state::{AppState, PomodoroMode};
+use crate::storage;
+use parking_lot::Mutex;
+use rodio::{Decoder, OutputStream, Sink, Source};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::BufReader;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How often the timer state is checked to start or stop the talk.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often the position is saved while the talk plays.
+const SAVE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Storage document holding the position reached.
+const POSITION_DOCUMENT: &str = "lecture.json";
+
+/// How far into which file playback has reached.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct LecturePosition {
+    /// Path of the file the position belongs to.
+    file: String,
+    /// Seconds played so far.
+    seconds: f64,
+}
+
+/// The talk playing during the current break.
+struct Playback {
+    /// Audio output, which must outlive the sink playing into it.
+    _stream: OutputStream,
+    /// Sink playing the talk.
+    sink: Sink,
+    /// Path of the file being played.
+    file: String,
+    /// Position playback started from.
+    from: Duration,
+    /// When playback started.
+    started: Instant,
+}
+
+impl Playback {
+    /// Starts playing `file` from `from` on the default audio output.
+    fn start(file: &str, from: Duration) -> Result<Self, String> {
+        let reader = BufReader::new(File::open(file).map_err(|error| error.to_string())?);
+        let source = Decoder::new(reader).map_err(|error| error.to_string())?;
+        let (stream, handle) = OutputStream::try_default().map_err(|error| error.to_string())?;
+        let sink = Sink::try_new(&handle).map_err(|error| error.to_string())?;
+        sink.append(source.skip_duration(from));
+        Ok(Self {
+            _stream: stream,
+            sink,
+            file: file.to_string(),
+            from,
+            started: Instant::now(),
+        })
+    }
+
+    /// Returns the position reached so far.
+    fn position(&self) -> LecturePosition {
+        LecturePosition {
+            file: self.file.clone(),
+            seconds: (self.from + self.started.elapsed()).as_secs_f64(),
+        }
+    }
+
+    /// Returns whether the talk has played to the end.
+    fn finished(&self) -> bool {
+        self.sink.empty()
+    }
+
+    /// Stops playback, returning the position reached.
+    fn stop(self) -> LecturePosition {
+        self.sink.stop();
+        self.position()
+    }
+}
+
+/// Returns whether the talk should be playing during a period of `mode`
+/// lasting `period_seconds`.
+fn should_play(
+    settings: &LectureSettings,
+    mode: PomodoroMode,
+    period_seconds: i32,
+    paused: bool,
+) -> bool {
+    settings.enabled
+        && !settings.file.trim().is_empty()
+        && mode == PomodoroMode::Rest
+        && period_seconds >= settings.min_break_minutes as i32 * 60
+        && !paused
+}
+
+/// Returns where to resume `file` from.
+fn resume_from(file: &str) -> Duration {
+    storage::load::<LecturePosition>(POSITION_DOCUMENT)
+        .filter(|position| position.file == file)
+        .map(|position| Duration::from_secs_f64(position.seconds.max(0.0)))
+        .unwrap_or_default()
+}
+
+/// Plays the talk during long breaks and saves its position, forever.
+///
+/// Does nothing until a talk is enabled in Settings. A file that can't be
+/// played is reported once per break rather than retried every second, and a
+/// talk that reaches its end starts over at the next long break.
+pub fn run_lecture(state: Arc<Mutex<AppState>>) {
+    let mut playback: Option<Playback> = None;
+    let mut saved_at = Instant::now();
+    // Set after a failure or the end of the talk, until the break is over
+    let mut done_for_break = false;
+    loop {
+        let (settings, play, quitting) = {
+            let s = state.lock();
+            let settings = s.settings.lecture.clone();
+            let play = should_play(&settings, s.mode, s.period_seconds, s.paused)
+                && s.snoozed_until.is_none();
+            (settings, play, s.should_quit)
+        };
+
+        if quitting {
+            if let Some(stopped) = playback.take() {
+                storage::save(POSITION_DOCUMENT, &stopped.stop());
+            }
+            return;
+        }
+
+        if play && playback.is_none() && !done_for_break {
+            let file = settings.file.trim();
+            match Playback::start(file, resume_from(file)) {
+                Ok(started) => {
+                    playback = Some(started);
+                    saved_at = Instant::now();
+                }
+                Err(error) => {
+                    eprintln!("Couldn't play {}: {}", file, error);
+                    done_for_break = true;
+                }
+            }
+        } else if !play {
+            done_for_break = false;
+            if let Some(stopped) = playback.take() {
+                storage::save(POSITION_DOCUMENT, &stopped.stop());
+            }
+        } else if let Some(playing) = playback.take_if(|playing| playing.finished()) {
+            // Heard to the end (or resumed past it): start over next time
+            let position = LecturePosition {
+                file: playing.file,
+                seconds: 0.0,
+            };
+            storage::save(POSITION_DOCUMENT, &position);
+            done_for_break = true;
+        } else if let Some(playing) = playback.as_ref() {
+            if saved_at.elapsed() >= SAVE_INTERVAL {
+                storage::save(POSITION_DOCUMENT, &playing.position());
+                saved_at = Instant::now();
+            }
+        }
+
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plays_only_during_long_unpaused_breaks() {
+        let settings = LectureSettings {
+            enabled: true,
+            file: "talk.mp3".to_string(),
+            min_break_minutes: 10,
+        };
+        assert!(should_play(&settings, PomodoroMode::Rest, 15 * 60, false));
+        assert!(!should_play(&settings, PomodoroMode::Rest, 5 * 60, false));
+        assert!(!should_play(&settings, PomodoroMode::Rest, 15 * 60, true));
+        assert!(!should_play(&settings, PomodoroMode::Work, 25 * 60, false));
+
+        let no_file = LectureSettings {
+            file: " ".to_string(),
+            ..settings
+        };
+        assert!(!should_play(&no_file, PomodoroMode::Rest, 15 * 60, false));
+    }
+}
@@ -6,13 +6,39 @@
 
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod about;
 mod app;
+mod autostart;
+mod calendar;
+mod characters;
+mod cli;
+mod events;
+mod format;
+mod hotkeys;
+mod journal;
+mod lecture;
+mod notifications;
+mod palette;
+mod plan;
+mod prayers;
+mod presence;
+mod quotes;
+mod report;
+mod sabbath;
+mod screen;
 mod settings;
 mod state;
+mod stats;
+mod storage;
 mod timer;
 mod tray;
+#[cfg(feature = "tui")]
+mod tui;
+mod updater;
 
-use app::PrayomodoroApp;
+use app::{PrayomodoroApp, BASE_HEIGHT, BASE_WIDTH};
+use clap::Parser;
+use cli::Cli;
 use parking_lot::Mutex;
 use state::AppState;
 use std::sync::Arc;
@@ -52,19 +78,68 @@ fn load_app_icon() -> egui::IconData {
 
 /// Application entry point.
 ///
-/// Initializes the application state, spawns the timer thread, and launches
-/// the egui window with a transparent, draggable interface.
+/// Parses command-line flags, initializes the application state, spawns the
+/// timer thread, and launches the egui window with a transparent, draggable
+/// interface.
 fn main() {
+    let cli = Cli::parse();
+    if let Some(dir) = cli.data_dir.clone() {
+        storage::install(Box::new(storage::FileStorage::new(dir)));
+    }
+    if let Some(command) = cli.command {
+        std::process::exit(cli::run(command));
+    }
+    if cli.stats {
+        let summary = stats::load_stats().summary(chrono::Local::now().date_naive());
+        if let Ok(json) = serde_json::to_string_pretty(&summary) {
+            println!("{}", json);
+        }
+        return;
+    }
+
     // Initialize shared state
     let state = Arc::new(Mutex::new(AppState::new()));
 
-    // Load settings
-    {
+    // Load settings, then apply command-line overrides for this launch
+    let (visible, scale, layer, position) = {
         let mut s = state.lock();
         s.settings = settings::load_settings();
+        if characters::validate(&s.settings.character).is_err() {
+            // The saved character's assets are gone; fall back to the first available one
+            s.settings.character = characters::available()[0].clone();
+        }
         s.character = s.settings.character.clone();
         s.scale = s.settings.window.scale;
-    }
+        s.visible = s.settings.window.visible;
+        s.window_position = Some((s.settings.window.x, s.settings.window.y));
+        s.stats = stats::load_stats();
+        s.plan = plan::load_plan();
+        s.start_at_login = autostart::is_enabled();
+
+        if cli.hidden {
+            s.visible = false;
+        }
+        if let Some(character) = cli.character {
+            s.character = character;
+        }
+        if let Some(scale) = cli.scale {
+            s.scale = scale;
+        }
+        if let Some(schedule) = cli.schedule {
+            s.settings.schedule = schedule;
+        }
+
+        // Go back to the monitor the companion was last on, wherever it is now
+        let window = &s.settings.window;
+        let position = screen::restore_position(
+            egui::Pos2::new(window.x, window.y),
+            &window.monitor,
+            egui::Vec2::new(window.monitor_x, window.monitor_y),
+            &screen::monitors(),
+        );
+
+        (s.visible, s.scale, s.settings.window.layer, position)
+    };
 
     // Start timer thread
     let state_for_timer = Arc::clone(&state);
@@ -72,17 +147,40 @@ fn main() {
         timer::run_timer(state_for_timer);
     });
 
+    // Start presence thread (idle unless enabled in Settings)
+    let state_for_presence = Arc::clone(&state);
+    std::thread::spawn(move || {
+        presence::run_presence(state_for_presence);
+    });
+
+    // Start the talk player thread (idle unless enabled in Settings)
+    let state_for_lecture = Arc::clone(&state);
+    std::thread::spawn(move || {
+        lecture::run_lecture(state_for_lecture);
+    });
+
+    // Quietly check for a newer release
+    let (offline, notifications) = {
+        let s = state.lock();
+        (s.settings.offline, s.settings.notifications.clone())
+    };
+    if !offline {
+        updater::check_for_updates(false, notifications);
+    }
+
     // Load app icon
     let icon = load_app_icon();
 
     // Run the egui app (tray will be created inside the app on the main thread)
     let native_options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
-            .with_inner_size([160.0, 395.0])
+            .with_inner_size([BASE_WIDTH * scale, BASE_HEIGHT * scale])
+            .with_position(position)
+            .with_visible(visible)
             .with_decorations(false)
             .with_transparent(true)
             .with_has_shadow(false) // Prevents ghosting on macOS transparent windows
-            .with_always_on_top()
+            .with_window_level(layer.level())
             .with_resizable(false)
             .with_title("Praymodoro")
             .with_icon(icon),
@@ -114,8 +212,14 @@ fn main() {
                 .insert(egui::FontFamily::Name("serif".into()), vec!["serif".to_owned()]);
             cc.egui_ctx.set_fonts(fonts);
 
-            Ok(Box::new(PrayomodoroApp::new(state_for_app)))
+            // Let the timer thread repaint the window when the time changes
+            state_for_app.lock().repaint = Some(cc.egui_ctx.clone());
+
+            Ok(Box::new(PrayomodoroApp::new(state_for_app, &cc.egui_ctx)))
         }),
     )
     .expect("Failed to run eframe");
+
+    // Let the last saves reach the disk before exiting
+    storage::flush();
 }
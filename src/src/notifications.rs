@@ -0,0 +1,217 @@
+//! Native desktop notifications for period and schedule changes.
+//!
+//! The companion is easy to miss behind a fullscreen window, so the timer
+//! announces each switch between work and prayer with an OS notification.
+//! On Windows that notification is a toast with a progress bar, updated in
+//! place as the period runs instead of followed by a separate warning.
+
+use crate::format;
+use crate::settings::NotificationSettings;
+use crate::state::PomodoroMode;
+use crate::stats::Sprint;
+use crate::timer::ScheduleChange;
+#[cfg(all(unix, not(target_os = "macos")))]
+use notify_rust::Hint;
+use notify_rust::Notification;
+#[cfg(target_os = "windows")]
+use tauri_winrt_notification::{Progress, Toast};
+
+/// Sound played with period notifications, named as the platform expects.
+#[cfg(target_os = "macos")]
+const SOUND_NAME: &str = "Glass";
+#[cfg(target_os = "windows")]
+const SOUND_NAME: &str = "Default";
+#[cfg(all(unix, not(target_os = "macos")))]
+const SOUND_NAME: &str = "message-new-instant";
+
+/// Tag of the period toast, so progress updates replace it in place.
+#[cfg(target_os = "windows")]
+const PROGRESS_TAG: &str = "period";
+
+/// Returns whether notifications for the start of `mode` are enabled.
+fn period_enabled(mode: PomodoroMode, settings: &NotificationSettings) -> bool {
+    match mode {
+        PomodoroMode::Rest => settings.rest_start,
+        PomodoroMode::Work => settings.work_start,
+    }
+}
+
+/// Shows a notification announcing that `mode` has just begun.
+///
+/// `duration` is the length of the new period in seconds. Does nothing if
+/// notifications for that mode are disabled, and plays a chime unless sounds
+/// are off. Errors from the platform notification service are ignored.
+pub fn notify_period_change(mode: PomodoroMode, duration: i32, settings: &NotificationSettings) {
+    let (summary, body) = match mode {
+        PomodoroMode::Rest => (
+            "Time to pray",
+            format!(
+                "Set your work aside and rest in prayer for {}.",
+                format::spoken(duration)
+            ),
+        ),
+        PomodoroMode::Work => (
+            "Back to work",
+            format!("Offer up the next {} of work.", format::spoken(duration)),
+        ),
+    };
+
+    if !period_enabled(mode, settings) {
+        return;
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let sound = if settings.sound {
+            SOUND_NAME.parse().ok()
+        } else {
+            None
+        };
+        let _ = Toast::new(Toast::POWERSHELL_APP_ID)
+            .title(summary)
+            .text1(&body)
+            .sound(sound)
+            .progress(&period_progress(duration, duration, false))
+            .show();
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    notify(summary, &body, settings);
+}
+
+/// Returns the progress bar of the period toast with `remaining` of
+/// `duration` seconds left.
+#[cfg(target_os = "windows")]
+fn period_progress(remaining: i32, duration: i32, paused: bool) -> Progress {
+    Progress {
+        tag: PROGRESS_TAG.to_string(),
+        title: String::new(),
+        status: if paused {
+            "Paused".to_string()
+        } else {
+            format!("{} left", format::spoken(remaining))
+        },
+        value: format::complete_fraction(remaining, duration),
+        value_string: format::clock(remaining),
+    }
+}
+
+/// Moves the progress bar of the current period's toast.
+///
+/// Does nothing if the toast has been dismissed or notifications for `mode`
+/// are disabled.
+#[cfg(target_os = "windows")]
+pub fn update_period_progress(
+    mode: PomodoroMode,
+    remaining: i32,
+    duration: i32,
+    paused: bool,
+    settings: &NotificationSettings,
+) {
+    if period_enabled(mode, settings) {
+        let _ = Toast::new(Toast::POWERSHELL_APP_ID)
+            .set_progress(&period_progress(remaining, duration, paused));
+    }
+}
+
+/// Period toasts only have a progress bar on Windows.
+#[cfg(not(target_os = "windows"))]
+pub fn update_period_progress(
+    _mode: PomodoroMode,
+    _remaining: i32,
+    _duration: i32,
+    _paused: bool,
+    _settings: &NotificationSettings,
+) {
+}
+
+/// Shows a notification that the current period of `mode` has a minute left.
+///
+/// Does nothing unless minute warnings are enabled, or on Windows when the
+/// period's progress toast is already counting down.
+pub fn notify_minute_left(mode: PomodoroMode, settings: &NotificationSettings) {
+    if !settings.minute_warning || (cfg!(target_os = "windows") && period_enabled(mode, settings)) {
+        return;
+    }
+
+    let body = match mode {
+        PomodoroMode::Work => "One minute of work left. Find a place to pause.",
+        PomodoroMode::Rest => "One minute of prayer left.",
+    };
+    notify("One minute left", body, settings);
+}
+
+/// Makes `notification` play the chime, or keeps it silent.
+fn set_sound(notification: &mut Notification, sound: bool) {
+    if sound {
+        notification.sound_name(SOUND_NAME);
+    } else {
+        // Some Linux desktops chime for every notification unless told not to
+        #[cfg(all(unix, not(target_os = "macos")))]
+        notification.hint(Hint::SuppressSound(true));
+    }
+}
+
+/// Shows a notification with `summary` and `body`, chiming unless sounds are off.
+fn notify(summary: &str, body: &str, settings: &NotificationSettings) {
+    let mut notification = Notification::new();
+    notification
+        .appname("Praymodoro")
+        .summary(summary)
+        .body(body);
+    set_sound(&mut notification, settings.sound);
+    let _ = notification.show();
+}
+
+/// Shows a notification announcing that a newly chosen schedule has taken over.
+pub fn notify_schedule_changed(change: &ScheduleChange, settings: &NotificationSettings) {
+    notify(
+        "Schedule changed",
+        &format!(
+            "Now following {} instead of {}.",
+            change.to.label(),
+            change.from.label()
+        ),
+        settings,
+    );
+}
+
+/// Shows a notification explaining why a character couldn't be chosen.
+pub fn notify_character_unavailable(error: &str, settings: &NotificationSettings) {
+    notify("Couldn't change character", error, settings);
+}
+
+/// Shows the Saturday-evening reminder to plan Sunday's rest.
+///
+/// `sunday_rest` says whether the companion will be put away tomorrow.
+pub fn notify_sabbath_reminder(sunday_rest: bool, settings: &NotificationSettings) {
+    let body = if sunday_rest {
+        "Plan tomorrow as a day of rest. Your companion will rest with you."
+    } else {
+        "Plan tomorrow as a day of rest."
+    };
+    notify("The Lord's Day is tomorrow", body, settings);
+}
+
+/// Shows a notification that the document `name` couldn't be saved.
+pub fn notify_save_failed(name: &str, error: &std::io::Error, settings: &NotificationSettings) {
+    notify(
+        "Couldn't save your changes",
+        &format!(
+            "Writing {} failed: {}. Praymodoro keeps your changes while it's \
+             running and tries again on the next change.",
+            name, error
+        ),
+        settings,
+    );
+}
+
+/// Shows a notification with the report of a sprint that has just finished.
+pub fn notify_sprint_finished(sprint: &Sprint, settings: &NotificationSettings) {
+    notify("Sprint complete", &sprint.report(), settings);
+}
+
+/// Shows the result of an update check.
+pub fn notify_update_check(summary: &str, body: &str, settings: &NotificationSettings) {
+    notify(summary, body, settings);
+}
@@ -0,0 +1,248 @@
+//! Command palette: every action, searchable from the keyboard.
+//!
+//! The palette lists the same [`TrayAction`]s the tray menu, hotkeys, and
+//! companion menu trigger, so anything can be reached without the mouse.
+//! Typing filters the list with a forgiving fuzzy match.
+
+use crate::characters;
+use crate::settings::{OverlayStyle, Profile, TimerDisplay, TrayTitle, WindowLayer};
+use crate::state::AppState;
+use crate::timer::{ScheduleKind, TimerEngine};
+use crate::tray::{format_character_name, Snooze, TrayAction, OPACITY_PRESETS, SCALE_PRESETS};
+
+/// An entry in the command palette.
+pub struct Command {
+    /// Text shown and searched in the palette.
+    pub label: String,
+    /// Action run when the entry is chosen.
+    pub action: TrayAction,
+}
+
+impl Command {
+    fn new(label: impl Into<String>, action: TrayAction) -> Self {
+        Self {
+            label: label.into(),
+            action,
+        }
+    }
+}
+
+/// Returns every command, labelled to match the current state.
+pub fn commands(state: &AppState) -> Vec<Command> {
+    let toggle = |on: bool| if on { "Turn Off" } else { "Turn On" };
+
+    let mut commands = vec![
+        Command::new(
+            if state.paused { "Resume" } else { "Pause" },
+            TrayAction::TogglePause,
+        ),
+        Command::new("Skip Period", TrayAction::SkipPeriod),
+        Command::new("Restart Period", TrayAction::RestartPeriod),
+        Command::new(
+            if state.visible {
+                "Hide Character"
+            } else {
+                "Show Character"
+            },
+            TrayAction::ToggleVisibility,
+        ),
+        Command::new("Plan Today", TrayAction::OpenPlanner),
+        Command::new(
+            if state.stats.sprint.is_some() {
+                "Show Sprint"
+            } else {
+                "Start Sprint"
+            },
+            TrayAction::OpenSprint,
+        ),
+        Command::new("Copy Status", TrayAction::CopyStatus),
+    ];
+    for snooze in Snooze::ALL {
+        commands.push(Command::new(
+            format!("Snooze: {}", snooze.label()),
+            TrayAction::Snooze(*snooze),
+        ));
+    }
+    if state.snoozed_until.is_some() {
+        commands.push(Command::new("End Snooze", TrayAction::EndSnooze));
+    }
+    if state.missed_prayers > 0 {
+        commands.push(Command::new(
+            "Acknowledge Missed Prayers",
+            TrayAction::AcknowledgeMissedPrayers,
+        ));
+    }
+    commands.extend([
+        Command::new("Next Character", TrayAction::NextCharacter),
+        Command::new("Face the Other Way", TrayAction::ToggleFlip),
+    ]);
+
+    for profile in Profile::ALL {
+        commands.push(Command::new(
+            format!("Profile: {}", profile.label()),
+            TrayAction::SetProfile(*profile),
+        ));
+    }
+    for char_name in characters::available() {
+        commands.push(Command::new(
+            format!("Character: {}", format_character_name(char_name)),
+            TrayAction::SetCharacter(char_name.clone()),
+        ));
+    }
+    for intention in &state.settings.intentions {
+        commands.push(Command::new(
+            format!("Intention: {}", intention),
+            TrayAction::SetIntention(intention.clone()),
+        ));
+    }
+    if !state.settings.intention.is_empty() {
+        commands.push(Command::new(
+            "Intention: None",
+            TrayAction::SetIntention(String::new()),
+        ));
+    }
+    commands.push(Command::new("Add Intention", TrayAction::AddIntention));
+    for size in SCALE_PRESETS {
+        commands.push(Command::new(
+            format!("Size: {}%", (size * 100.0) as i32),
+            TrayAction::SetScale(*size),
+        ));
+    }
+    commands.push(Command::new("Size: Custom", TrayAction::OpenCustomScale));
+    for layer in WindowLayer::ALL {
+        commands.push(Command::new(
+            format!("Layer: {}", layer.label()),
+            TrayAction::SetLayer(*layer),
+        ));
+    }
+    for style in OverlayStyle::ALL {
+        commands.push(Command::new(
+            format!("Break Overlay Style: {}", style.label()),
+            TrayAction::SetOverlayStyle(*style),
+        ));
+    }
+    for display in TimerDisplay::ALL {
+        commands.push(Command::new(
+            format!("Timer Display: {}", display.label()),
+            TrayAction::SetTimerDisplay(*display),
+        ));
+    }
+    for title in TrayTitle::ALL {
+        commands.push(Command::new(
+            format!("Menu Bar Title: {}", title.label()),
+            TrayAction::SetTrayTitle(*title),
+        ));
+    }
+    for opacity in OPACITY_PRESETS {
+        commands.push(Command::new(
+            format!("Opacity: {}%", (opacity * 100.0) as i32),
+            TrayAction::SetOpacity(*opacity),
+        ));
+    }
+    for schedule in ScheduleKind::ALL {
+        commands.push(Command::new(
+            format!("Schedule: {}", schedule.label()),
+            TrayAction::SetSchedule(*schedule),
+        ));
+    }
+    for engine in TimerEngine::ALL {
+        commands.push(Command::new(
+            format!("Timing: {}", engine.label()),
+            TrayAction::SetEngine(*engine),
+        ));
+    }
+
+    let settings = &state.settings;
+    commands.extend([
+        Command::new(
+            format!("{} Rosary During Rest", toggle(settings.rosary_mode)),
+            TrayAction::ToggleRosary,
+        ),
+        Command::new(
+            format!("{} Touch Mode", toggle(settings.touch_mode)),
+            TrayAction::ToggleTouchMode,
+        ),
+        Command::new(
+            format!("{} Break Overlay", toggle(settings.break_overlay)),
+            TrayAction::ToggleBreakOverlay,
+        ),
+        Command::new(
+            format!("{} Breathing Pause", toggle(settings.breathing_pause)),
+            TrayAction::ToggleBreathingPause,
+        ),
+        Command::new(
+            format!("{} Reduce Motion", toggle(settings.reduce_motion)),
+            TrayAction::ToggleReduceMotion,
+        ),
+        Command::new(
+            format!("{} Saint Quotes", toggle(settings.speech_bubbles)),
+            TrayAction::ToggleSpeechBubbles,
+        ),
+        Command::new(
+            format!(
+                "{} Companion on Every Monitor",
+                toggle(settings.multi_companion)
+            ),
+            TrayAction::ToggleMultiCompanion,
+        ),
+        Command::new(
+            format!("{} Prayer Before Work", toggle(settings.prayer_before_work)),
+            TrayAction::TogglePrayerBeforeWork,
+        ),
+        Command::new(
+            format!("{} Sounds", toggle(settings.notifications.sound)),
+            TrayAction::ToggleSounds,
+        ),
+        Command::new(
+            format!("{} Start at Login", toggle(state.start_at_login)),
+            TrayAction::ToggleStartAtLogin,
+        ),
+        Command::new("Settings", TrayAction::OpenSettings),
+        Command::new("Check for Updates", TrayAction::CheckForUpdates),
+        Command::new("About Praymodoro", TrayAction::OpenAbout),
+        Command::new("Quit", TrayAction::Quit),
+    ]);
+
+    commands
+}
+
+/// Scores how well `query` matches `label`, or `None` if it doesn't match.
+///
+/// Every character of the query must appear in the label, in order and
+/// ignoring case. Higher scores are better: matches at word starts and runs
+/// of consecutive characters count extra, and shorter labels win ties.
+pub fn fuzzy_score(query: &str, label: &str) -> Option<i32> {
+    let label: Vec<char> = label.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous: Option<usize> = None;
+
+    for wanted in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = next + label[next..].iter().position(|&c| c == wanted)?;
+        score += 1;
+        if found == 0 || !label[found - 1].is_alphanumeric() {
+            score += 8;
+        }
+        if previous.is_some_and(|p| p + 1 == found) {
+            score += 4;
+        }
+        previous = Some(found);
+        next = found + 1;
+    }
+
+    Some(score * 100 - label.len() as i32)
+}
+
+/// Returns the commands matching `query`, best match first.
+///
+/// An empty query matches every command in its usual order.
+pub fn search<'a>(query: &str, commands: &'a [Command]) -> Vec<&'a Command> {
+    let mut matches: Vec<(i32, &Command)> = commands
+        .iter()
+        .filter_map(|command| fuzzy_score(query, &command.label).map(|score| (score, command)))
+        .collect();
+    if !query.trim().is_empty() {
+        matches.sort_by_key(|(score, _)| -score);
+    }
+    matches.into_iter().map(|(_, command)| command).collect()
+}
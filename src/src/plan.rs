@@ -0,0 +1,69 @@
+//! Daily session plan: a label for each half-hour work block.
+//!
+//! Each hour has two work blocks (`:00` and `:30`). The plan is sketched in the
+//! planner window, shown in the tray for the current block, and compared against
+//! completed pomodoros in the stats. It is saved as `plan.json` and only applies
+//! to the day it was written for.
+
+use crate::storage;
+use chrono::{DateTime, Local, NaiveDate, Timelike};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Planned labels for the work blocks of a single day.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DayPlan {
+    /// Local date the plan was written for.
+    pub date: Option<NaiveDate>,
+    /// Block labels keyed by [`block_index`].
+    pub blocks: BTreeMap<u32, String>,
+}
+
+impl DayPlan {
+    /// Returns this plan if it was written for `today`, or an empty plan for `today`.
+    pub fn for_day(&self, today: NaiveDate) -> DayPlan {
+        if self.date == Some(today) {
+            self.clone()
+        } else {
+            DayPlan {
+                date: Some(today),
+                blocks: BTreeMap::new(),
+            }
+        }
+    }
+
+    /// Returns the planned label for the block containing `now`, if any.
+    pub fn label_at(&self, now: DateTime<Local>) -> Option<&str> {
+        if self.date != Some(now.date_naive()) {
+            return None;
+        }
+        self.blocks
+            .get(&block_index(now.hour(), now.minute()))
+            .map(String::as_str)
+            .filter(|label| !label.trim().is_empty())
+    }
+
+    /// Returns the number of blocks with a non-empty label.
+    pub fn planned_count(&self) -> usize {
+        self.blocks
+            .values()
+            .filter(|label| !label.trim().is_empty())
+            .count()
+    }
+}
+
+/// Returns the index of the half-hour block containing `hour:minute` (0-47).
+pub fn block_index(hour: u32, minute: u32) -> u32 {
+    hour * 2 + u32::from(minute >= 30)
+}
+
+/// Loads the plan from storage, or returns an empty plan if there is none.
+pub fn load_plan() -> DayPlan {
+    storage::load("plan.json").unwrap_or_default()
+}
+
+/// Queues the plan to be saved; failures are retried and reported by [`storage::save`].
+pub fn save_plan(plan: &DayPlan) {
+    storage::save("plan.json", plan);
+}
@@ -0,0 +1,156 @@
+//! Short prayers shown during rest periods.
+//!
+//! Prayers are embedded in the binary and rotated by half-hour block, so each
+//! break gets a different prayer that stays the same for the whole break.
+//! Prayers the active saint's manifest lists as devotions come up more often.
+//! With simple prayers on, the rotation keeps to [`SIMPLE_PRAYERS`].
+//! A separate, shorter list holds the optional prayers before work.
+
+use crate::characters;
+use crate::plan::block_index;
+use chrono::{DateTime, Datelike, Local, Timelike};
+
+/// A short prayer with a display title.
+pub struct Prayer {
+    /// Stable identifier, used by character manifests to name devotions.
+    pub id: &'static str,
+    /// Title shown above the prayer text.
+    pub title: &'static str,
+    /// The prayer itself.
+    pub text: &'static str,
+}
+
+/// Prayers rotated through during rest periods.
+pub const REST_PRAYERS: &[Prayer] = &[
+    Prayer {
+        id: "our-father",
+        title: "Our Father",
+        text: "Our Father, who art in heaven, hallowed be thy name; thy kingdom come, \
+               thy will be done on earth as it is in heaven. Give us this day our daily \
+               bread, and forgive us our trespasses, as we forgive those who trespass \
+               against us; and lead us not into temptation, but deliver us from evil. Amen.",
+    },
+    Prayer {
+        id: "hail-mary",
+        title: "Hail Mary",
+        text: "Hail Mary, full of grace, the Lord is with thee. Blessed art thou among \
+               women, and blessed is the fruit of thy womb, Jesus. Holy Mary, Mother of \
+               God, pray for us sinners, now and at the hour of our death. Amen.",
+    },
+    Prayer {
+        id: "glory-be",
+        title: "Glory Be",
+        text: "Glory be to the Father, and to the Son, and to the Holy Spirit, as it was \
+               in the beginning, is now, and ever shall be, world without end. Amen.",
+    },
+    Prayer {
+        id: "come-holy-spirit",
+        title: "Come, Holy Spirit",
+        text: "Come, Holy Spirit, fill the hearts of your faithful and kindle in them the \
+               fire of your love. Send forth your Spirit and they shall be created, and \
+               you shall renew the face of the earth.",
+    },
+    Prayer {
+        id: "jesus-prayer",
+        title: "The Jesus Prayer",
+        text: "Lord Jesus Christ, Son of God, have mercy on me, a sinner.",
+    },
+    Prayer {
+        id: "augustine-late-have-i-loved",
+        title: "St. Augustine, Confessions",
+        text: "Late have I loved you, O Beauty ever ancient, ever new, late have I loved \
+               you! You were within me, but I was outside, and it was there that I \
+               searched for you.",
+    },
+    Prayer {
+        id: "augustine-restless-heart",
+        title: "St. Augustine, Confessions",
+        text: "You have made us for yourself, O Lord, and our heart is restless until \
+               it rests in you.",
+    },
+    Prayer {
+        id: "patrick-breastplate",
+        title: "St. Patrick's Breastplate",
+        text: "Christ with me, Christ before me, Christ behind me, Christ in me, Christ \
+               beneath me, Christ above me, Christ on my right, Christ on my left.",
+    },
+    Prayer {
+        id: "aquinas-grant-me",
+        title: "St. Thomas Aquinas",
+        text: "Grant me, O Lord my God, a mind to know you, a heart to seek you, wisdom \
+               to find you, conduct pleasing to you, faithful perseverance in waiting \
+               for you, and a hope of finally embracing you. Amen.",
+    },
+    Prayer {
+        id: "aquinas-before-study",
+        title: "St. Thomas Aquinas, Before Study",
+        text: "Grant me, O Lord, keenness of mind to understand, capacity to retain, \
+               method and facility in learning, subtlety in interpretation, and copious \
+               grace of expression. Amen.",
+    },
+    Prayer {
+        id: "more-give-me-the-grace",
+        title: "St. Thomas More",
+        text: "Give me the grace, good Lord, to set the world at nought, to set my mind \
+               fast upon you, and not to hang upon the blast of men's mouths.",
+    },
+];
+
+/// Ids of the short, familiar prayers rotated through with simple prayers on.
+pub const SIMPLE_PRAYERS: &[&str] = &["our-father", "hail-mary", "glory-be"];
+
+/// Prayers offered at the start of a work period, selectable in settings.
+pub const WORK_PRAYERS: &[Prayer] = &[
+    Prayer {
+        id: "creator-ineffabilis",
+        title: "St. Thomas Aquinas, Creator ineffabilis",
+        text: "Ineffable Creator, pour forth a ray of your brightness into the darkened \
+               places of my mind. Grant me keenness of mind to understand, capacity to \
+               retain, and method and ease in learning. Guide the beginning of my work, \
+               direct its progress, and bring it to completion. Amen.",
+    },
+    Prayer {
+        id: "actiones-nostras",
+        title: "Actiones nostras",
+        text: "Direct, we beg you, O Lord, our actions by your holy inspirations, and \
+               carry them on by your gracious assistance, that every prayer and work of \
+               ours may begin always from you, and through you be happily ended. Amen.",
+    },
+];
+
+/// Returns the work prayer with the given id, or the first one if it's unknown.
+pub fn work_prayer(id: &str) -> &'static Prayer {
+    WORK_PRAYERS
+        .iter()
+        .find(|prayer| prayer.id == id)
+        .unwrap_or(&WORK_PRAYERS[0])
+}
+
+/// How many times more often a saint's devotions come up than other prayers.
+const DEVOTION_WEIGHT: usize = 3;
+
+/// Returns the prayer for the rest period containing `now`.
+///
+/// The choice depends only on the day, the half-hour block, and the active
+/// `character`, so it stays stable for the whole break and changes from one
+/// break to the next. The character's devotions are weighted by
+/// [`DEVOTION_WEIGHT`]. With `simple` set, only [`SIMPLE_PRAYERS`] are used.
+pub fn rest_prayer(now: DateTime<Local>, character: &str, simple: bool) -> &'static Prayer {
+    let devotions = characters::devotions(character);
+    let rotation: Vec<&'static Prayer> = REST_PRAYERS
+        .iter()
+        .filter(|prayer| !simple || SIMPLE_PRAYERS.contains(&prayer.id))
+        .flat_map(|prayer| {
+            let weight = if devotions.iter().any(|id| id == prayer.id) {
+                DEVOTION_WEIGHT
+            } else {
+                1
+            };
+            std::iter::repeat_n(prayer, weight)
+        })
+        .collect();
+
+    let block = block_index(now.hour(), now.minute()) as usize;
+    let day = now.ordinal() as usize;
+    rotation[(day * 48 + block) % rotation.len()]
+}
@@ -0,0 +1,237 @@
+//! Opt-in presence sharing with an accountability partner.
+//!
+//! When enabled, the app periodically publishes a small status document (the
+//! current mode, when it was last running, and today's kept prayers) to a
+//! folder or URL of the user's choosing, e.g. a synced folder. The document is
+//! signed with an Ed25519 key kept in the config directory, and the partner's
+//! Praymodoro checks it against the public key they were given before showing
+//! it, so a stale or forged file can't claim prayers that weren't kept.
+
+use crate::format;
+use crate::state::{AppState, PomodoroMode};
+use crate::storage;
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How often the status is published and the partner's status is read.
+const PUBLISH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How long after its last update a partner is shown as away.
+const AWAY_AFTER_SECONDS: i64 = 5 * 60;
+
+/// File name used when publishing to or reading from a folder.
+const STATUS_FILE: &str = "praymodoro-presence.json";
+
+/// Storage document holding the hex-encoded signing key.
+const KEY_DOCUMENT: &str = "presence-key.json";
+
+/// What a partner sees about the user.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PresenceStatus {
+    /// Current mode, `"work"` or `"rest"`.
+    pub mode: String,
+    /// Whether the timer is paused.
+    pub paused: bool,
+    /// When the status was published.
+    pub last_active: DateTime<Utc>,
+    /// Rest periods prayed through to the end today.
+    pub rests_kept_today: u32,
+    /// Rest periods that ended today, kept or not.
+    pub rests_ended_today: u32,
+}
+
+impl PresenceStatus {
+    /// Returns a short description for the tray, e.g. "praying · kept 3/4 prayers".
+    pub fn label(&self) -> String {
+        let away = (Utc::now() - self.last_active).num_seconds();
+        let activity = if away > AWAY_AFTER_SECONDS {
+            format!("away {}", format::spoken(away as i32))
+        } else if self.paused {
+            "paused".to_string()
+        } else if self.mode == PomodoroMode::Rest.as_str() {
+            "praying".to_string()
+        } else {
+            "working".to_string()
+        };
+        format!(
+            "{} · kept {}/{} prayers",
+            activity, self.rests_kept_today, self.rests_ended_today
+        )
+    }
+}
+
+/// The published document: the status as JSON text plus its signature.
+///
+/// The payload is kept as text so the signed bytes are exactly the ones read
+/// back, regardless of how the status would be re-serialized.
+#[derive(Debug, Serialize, Deserialize)]
+struct SignedStatus {
+    /// [`PresenceStatus`] serialized as JSON.
+    payload: String,
+    /// Hex-encoded Ed25519 public key of the publisher.
+    public_key: String,
+    /// Hex-encoded Ed25519 signature of `payload`.
+    signature: String,
+}
+
+/// Encodes bytes as lowercase hex.
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Decodes exactly `N` bytes of hex, ignoring surrounding whitespace.
+fn from_hex<const N: usize>(hex: &str) -> Option<[u8; N]> {
+    let hex = hex.trim();
+    if hex.len() != N * 2 || !hex.is_ascii() {
+        return None;
+    }
+    let mut bytes = [0; N];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(bytes)
+}
+
+/// Generates and saves a new signing key.
+fn generate_key() -> SigningKey {
+    let mut secret = [0; 32];
+    getrandom::getrandom(&mut secret).expect("No system random number source");
+    storage::save(KEY_DOCUMENT, &to_hex(&secret));
+    SigningKey::from_bytes(&secret)
+}
+
+/// Returns the saved signing key, creating one on first use.
+fn signing_key() -> SigningKey {
+    storage::load::<String>(KEY_DOCUMENT)
+        .and_then(|hex| from_hex::<32>(&hex))
+        .map(|secret| SigningKey::from_bytes(&secret))
+        .unwrap_or_else(generate_key)
+}
+
+/// Returns the hex-encoded public key to give to a partner.
+pub fn public_key() -> String {
+    to_hex(&signing_key().verifying_key().to_bytes())
+}
+
+/// Replaces the signing key, returning the new public key.
+///
+/// Partners must be given the new key before they'll trust the status again.
+pub fn regenerate_key() -> String {
+    to_hex(&generate_key().verifying_key().to_bytes())
+}
+
+/// Returns whether `location` names a URL rather than a path.
+fn is_url(location: &str) -> bool {
+    location.starts_with("https://") || location.starts_with("http://")
+}
+
+/// Resolves a folder location to the status file inside it.
+fn status_path(location: &str) -> PathBuf {
+    let path = Path::new(location);
+    if path.is_dir() {
+        path.join(STATUS_FILE)
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// Signs `status` and writes it to `location`, a folder, file, or URL.
+fn publish(status: &PresenceStatus, location: &str) -> Result<(), String> {
+    let payload = serde_json::to_string(status).map_err(|e| e.to_string())?;
+    let key = signing_key();
+    let document = SignedStatus {
+        signature: to_hex(&key.sign(payload.as_bytes()).to_bytes()),
+        public_key: to_hex(&key.verifying_key().to_bytes()),
+        payload,
+    };
+    let json = serde_json::to_string_pretty(&document).map_err(|e| e.to_string())?;
+
+    if is_url(location) {
+        ureq::put(location)
+            .set("Content-Type", "application/json")
+            .send_string(&json)
+            .map_err(|e| e.to_string())?;
+    } else {
+        // Write beside the target and rename, so a syncing partner never reads half a file
+        let path = status_path(location);
+        let temp = path.with_extension("json.tmp");
+        fs::write(&temp, json).map_err(|e| e.to_string())?;
+        fs::rename(&temp, &path).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Reads the partner's status from `location` and checks its signature.
+fn read_partner(location: &str, partner_key: &str) -> Result<PresenceStatus, String> {
+    let key = from_hex::<32>(partner_key)
+        .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+        .ok_or("partner key is invalid")?;
+
+    let json = if is_url(location) {
+        ureq::get(location)
+            .call()
+            .map_err(|e| e.to_string())?
+            .into_string()
+            .map_err(|e| e.to_string())?
+    } else {
+        fs::read_to_string(status_path(location)).map_err(|_| "no status found".to_string())?
+    };
+    let document: SignedStatus =
+        serde_json::from_str(&json).map_err(|_| "status is unreadable".to_string())?;
+
+    let signature = from_hex::<64>(&document.signature)
+        .map(|bytes| Signature::from_bytes(&bytes))
+        .ok_or("status is unsigned")?;
+    key.verify(document.payload.as_bytes(), &signature)
+        .map_err(|_| "signature doesn't match partner key".to_string())?;
+
+    serde_json::from_str(&document.payload).map_err(|_| "status is unreadable".to_string())
+}
+
+/// Builds the status to publish from the current state.
+fn current_status(s: &AppState) -> PresenceStatus {
+    PresenceStatus {
+        mode: s.mode.as_str().to_string(),
+        paused: s.paused,
+        last_active: Utc::now(),
+        rests_kept_today: s.stats.rests_kept_today,
+        rests_ended_today: s.stats.rests_ended_today,
+    }
+}
+
+/// Publishes the user's status and refreshes the partner's, forever.
+///
+/// Does nothing until presence is enabled in Settings; changes take effect on
+/// the next round.
+pub fn run_presence(state: Arc<Mutex<AppState>>) {
+    loop {
+        let (settings, offline, status) = {
+            let s = state.lock();
+            (
+                s.settings.presence.clone(),
+                s.settings.offline,
+                current_status(&s),
+            )
+        };
+
+        let partner = if settings.enabled && !offline {
+            if !settings.publish_to.trim().is_empty() {
+                // A missing folder or offline server just means the partner sees us as away
+                let _ = publish(&status, settings.publish_to.trim());
+            }
+            (!settings.partner_from.trim().is_empty())
+                .then(|| read_partner(settings.partner_from.trim(), &settings.partner_key))
+        } else {
+            None
+        };
+        state.lock().partner = partner;
+
+        std::thread::sleep(PUBLISH_INTERVAL);
+    }
+}
@@ -0,0 +1,55 @@
+//! Short sayings of each saint, shown in the companion's speech bubble.
+//!
+//! Quotes are embedded in the binary and keyed by character id. Characters
+//! without quotes (e.g. ones added as assets) just don't speak.
+
+/// Quotes of each built-in character.
+const QUOTES: &[(&str, &[&str])] = &[
+    (
+        "augustine-of-hippo",
+        &[
+            "You have made us for yourself, O Lord, and our heart is restless until it rests in you.",
+            "Late have I loved you, Beauty ever ancient, ever new.",
+            "Give what you command, and command what you will.",
+            "Love, and do what you will.",
+        ],
+    ),
+    (
+        "thomas-aquinas",
+        &[
+            "Grace does not destroy nature, but perfects it.",
+            "Wonder is a kind of desire for knowledge.",
+            "Nothing but you, Lord.",
+        ],
+    ),
+    (
+        "saint-patrick",
+        &[
+            "Christ with me, Christ before me, Christ behind me, Christ in me.",
+            "I arise today through a mighty strength.",
+            "I was like a stone lying in deep mire, and he that is mighty came.",
+        ],
+    ),
+    (
+        "thomas-more",
+        &[
+            "The King's good servant, but God's first.",
+            "Give me the grace, good Lord, to set the world at nought.",
+            "Nothing can come but that that God wills.",
+        ],
+    ),
+];
+
+/// Returns the quotes of `character`, empty if it has none.
+pub fn quotes(character: &str) -> &'static [&'static str] {
+    QUOTES
+        .iter()
+        .find(|(id, _)| *id == character)
+        .map_or(&[], |(_, quotes)| quotes)
+}
+
+/// Returns the `n`th quote of `character`, wrapping around.
+pub fn quote(character: &str, n: usize) -> Option<&'static str> {
+    let quotes = quotes(character);
+    (!quotes.is_empty()).then(|| quotes[n % quotes.len()])
+}
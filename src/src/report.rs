@@ -0,0 +1,182 @@
+//! The monthly retrospective, as printed by `praymodoro report`.
+//!
+//! The report combines the month's counters from the stats with its
+//! liturgical context: the seasons it spans and the feasts kept along the
+//! way. It's a self-contained HTML page styled for printing, so a PDF is one
+//! "Print to PDF" away in any browser.
+
+use crate::calendar::{self, FeastRank};
+use crate::stats::DayRecord;
+use chrono::{Datelike, Days, NaiveDate};
+use std::fmt::Write;
+
+/// Styles for the report, on screen and on paper.
+const STYLE: &str = "\
+body { font-family: Georgia, serif; max-width: 40em; margin: 2em auto; color: #222; }
+h1 { font-weight: normal; border-bottom: 1px solid #999; }
+table { border-collapse: collapse; width: 100%; }
+th, td { text-align: left; padding: 0.2em 0.5em; border-bottom: 1px solid #ddd; }
+td.number { text-align: right; }
+@media print { body { margin: 0; } }";
+
+/// Returns the days of the month starting on `first`.
+pub fn month_days(first: NaiveDate) -> impl Iterator<Item = NaiveDate> {
+    first
+        .iter_days()
+        .take_while(move |date| date.month() == first.month())
+}
+
+/// Returns the number of days in the longest run of consecutive days with a
+/// completed pomodoro.
+fn longest_streak(days: &[DayRecord]) -> u32 {
+    let mut longest = 0;
+    let mut current = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for record in days.iter().filter(|record| record.completed > 0) {
+        let continues = previous.and_then(|date| date.checked_add_days(Days::new(1)));
+        current = if continues == Some(record.date) {
+            current + 1
+        } else {
+            1
+        };
+        longest = longest.max(current);
+        previous = Some(record.date);
+    }
+    longest
+}
+
+/// Returns the report for the month starting on `first`.
+///
+/// `days` are the month's records with any activity, oldest first.
+pub fn monthly(first: NaiveDate, days: &[DayRecord]) -> String {
+    let title = format!("Praymodoro — {}", first.format("%B %Y"));
+
+    let mut seasons = Vec::new();
+    for date in month_days(first) {
+        let season = calendar::season_on(date).label();
+        if !seasons.contains(&season) {
+            seasons.push(season);
+        }
+    }
+
+    let completed: u32 = days.iter().map(|record| record.completed).sum();
+    let active = days.iter().filter(|record| record.completed > 0).count() as u32;
+    let rests_kept: u32 = days.iter().map(|record| record.rests_kept).sum();
+    let rests_ended: u32 = days.iter().map(|record| record.rests_ended).sum();
+    let work_prayers: u32 = days.iter().map(|record| record.work_prayers).sum();
+
+    let mut html = String::new();
+    let _ = writeln!(html, "<!DOCTYPE html>");
+    let _ = writeln!(html, "<html><head><meta charset=\"utf-8\">");
+    let _ = writeln!(html, "<title>{}</title>", title);
+    let _ = writeln!(html, "<style>\n{}\n</style>", STYLE);
+    let _ = writeln!(html, "</head><body>");
+    let _ = writeln!(html, "<h1>{}</h1>", title);
+    let _ = writeln!(html, "<p>Season: {}</p>", seasons.join(", then "));
+
+    let _ = writeln!(html, "<h2>Focus</h2>\n<ul>");
+    let _ = writeln!(html, "<li>{} pomodoros completed</li>", completed);
+    let _ = write!(html, "<li>{} days with a completed pomodoro", active);
+    if active > 0 {
+        let _ = write!(
+            html,
+            ", {:.1} pomodoros on each",
+            completed as f32 / active as f32
+        );
+    }
+    let _ = writeln!(html, "</li>");
+    let _ = writeln!(
+        html,
+        "<li>Longest streak: {} days</li>\n</ul>",
+        longest_streak(days)
+    );
+
+    let _ = writeln!(html, "<h2>Prayer</h2>\n<ul>");
+    if rests_ended > 0 {
+        let _ = writeln!(
+            html,
+            "<li>{} of {} rest periods prayed through ({}%)</li>",
+            rests_kept,
+            rests_ended,
+            rests_kept * 100 / rests_ended
+        );
+    } else {
+        let _ = writeln!(html, "<li>No rest periods ended</li>");
+    }
+    let _ = writeln!(html, "<li>{} prayers before work</li>\n</ul>", work_prayers);
+
+    let _ = writeln!(html, "<h2>Feasts</h2>");
+    let feasts: Vec<_> = month_days(first)
+        .filter_map(|date| {
+            let feast = calendar::feast_on(date)?;
+            (feast.rank >= FeastRank::Feast).then_some((date, feast))
+        })
+        .collect();
+    if feasts.is_empty() {
+        let _ = writeln!(html, "<p>No feasts this month.</p>");
+    } else {
+        let _ = writeln!(html, "<table>");
+        let _ = writeln!(
+            html,
+            "<tr><th>Date</th><th>Celebration</th><th>Pomodoros</th><th>Prayed through</th></tr>"
+        );
+        for (date, feast) in feasts {
+            let record = days.iter().find(|record| record.date == date);
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td class=\"number\">{}</td><td class=\"number\">{}</td></tr>",
+                date.format("%-d %B"),
+                feast.name,
+                record.map_or(0, |record| record.completed),
+                record.map_or(0, |record| record.rests_kept)
+            );
+        }
+        let _ = writeln!(html, "</table>");
+    }
+
+    let _ = writeln!(html, "</body></html>");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(date: u32, completed: u32) -> DayRecord {
+        DayRecord {
+            date: NaiveDate::from_ymd_opt(2026, 8, date).unwrap(),
+            completed,
+            rests_kept: completed,
+            rests_ended: completed + 1,
+            work_prayers: 0,
+        }
+    }
+
+    #[test]
+    fn streak_counts_consecutive_active_days() {
+        let days = [
+            day(1, 2),
+            day(2, 1),
+            day(3, 0),
+            day(4, 3),
+            day(5, 1),
+            day(6, 4),
+        ];
+        assert_eq!(longest_streak(&days), 3);
+        assert_eq!(longest_streak(&[]), 0);
+    }
+
+    #[test]
+    fn report_covers_stats_and_feasts() {
+        let first = NaiveDate::from_ymd_opt(2026, 8, 1).unwrap();
+        let report = monthly(first, &[day(14, 2), day(15, 4)]);
+        assert!(report.contains("<title>Praymodoro — August 2026</title>"));
+        assert!(report.contains("Season: Ordinary Time"));
+        assert!(report.contains("6 pomodoros completed"));
+        assert!(report.contains("6 of 8 rest periods prayed through (75%)"));
+        assert!(
+            report.contains("<td>15 August</td><td>the Assumption</td><td class=\"number\">4</td>")
+        );
+        assert!(!report.contains("St. Monica"));
+    }
+}
@@ -0,0 +1,100 @@
+//! Preparing for Sunday rest.
+//!
+//! On Saturday evening the timer reminds the user to plan tomorrow as a day of
+//! rest. With Sunday rest turned on, the companion is put away for all of
+//! Sunday, as if snoozed until Monday; ending the snooze from the tray brings
+//! it back for the rest of the day.
+
+use crate::settings::SabbathSettings;
+use chrono::{
+    DateTime, Datelike, Days, Local, NaiveDate, NaiveDateTime, TimeDelta, Timelike, Weekday,
+};
+
+/// Returns whether the Saturday reminder is due at `now`.
+///
+/// `reminded` is the day the reminder was last shown, so it's shown once.
+/// While `snoozed`, the reminder waits; it's shown when the snooze ends if
+/// that's still on Saturday.
+pub fn reminder_due(
+    now: NaiveDateTime,
+    settings: &SabbathSettings,
+    reminded: Option<NaiveDate>,
+    snoozed: bool,
+) -> bool {
+    settings.reminder
+        && !snoozed
+        && now.weekday() == Weekday::Sat
+        && now.hour() >= settings.reminder_hour
+        && reminded != Some(now.date())
+}
+
+/// Returns whether Sunday rest begins at `now`.
+///
+/// `rested` is the day rest last began, so ending it early sticks.
+pub fn rest_due(now: NaiveDateTime, settings: &SabbathSettings, rested: Option<NaiveDate>) -> bool {
+    settings.sunday_rest && now.weekday() == Weekday::Sun && rested != Some(now.date())
+}
+
+/// Returns when a rest beginning at `now` ends: the following midnight.
+pub fn rest_until(now: DateTime<Local>) -> DateTime<Local> {
+    (now.date_naive() + Days::new(1))
+        .and_hms_opt(0, 0, 0)
+        .and_then(|midnight| midnight.and_local_timezone(Local).earliest())
+        .unwrap_or(now + TimeDelta::days(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(day: u32, hour: u32) -> NaiveDateTime {
+        // 2026-10-17 is a Saturday
+        NaiveDate::from_ymd_opt(2026, 10, day)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    fn settings() -> SabbathSettings {
+        SabbathSettings {
+            reminder: true,
+            reminder_hour: 18,
+            sunday_rest: true,
+        }
+    }
+
+    #[test]
+    fn reminds_once_on_saturday_evening() {
+        assert!(!reminder_due(at(17, 17), &settings(), None, false));
+        assert!(reminder_due(at(17, 18), &settings(), None, false));
+        assert!(!reminder_due(
+            at(17, 21),
+            &settings(),
+            Some(at(17, 0).date()),
+            false
+        ));
+        assert!(!reminder_due(at(16, 19), &settings(), None, false));
+
+        let off = SabbathSettings {
+            reminder: false,
+            ..settings()
+        };
+        assert!(!reminder_due(at(17, 18), &off, None, false));
+    }
+
+    #[test]
+    fn snoozed_reminder_waits_for_the_snooze_to_end() {
+        assert!(!reminder_due(at(17, 18), &settings(), None, true));
+        assert!(!reminder_due(at(17, 20), &settings(), None, true));
+        assert!(reminder_due(at(17, 21), &settings(), None, false));
+        // A snooze lasting past Saturday drops the reminder
+        assert!(!reminder_due(at(18, 9), &settings(), None, false));
+    }
+
+    #[test]
+    fn rests_once_on_sunday() {
+        assert!(!rest_due(at(17, 23), &settings(), None));
+        assert!(rest_due(at(18, 0), &settings(), None));
+        assert!(!rest_due(at(18, 12), &settings(), Some(at(18, 0).date())));
+    }
+}
@@ -0,0 +1,197 @@
+//! Monitor geometry for keeping the companion on screen and docking it to an edge.
+//!
+//! egui only reports the size of the current monitor, so monitor positions
+//! come from the `display-info` crate. All rectangles here are in logical
+//! points, matching egui's viewport coordinates.
+
+use display_info::DisplayInfo;
+use egui::{Pos2, Rect, Vec2};
+
+/// A connected monitor's bounds.
+#[derive(Clone, Debug)]
+pub struct Monitor {
+    /// Monitor name as reported by the platform, used to remember per-monitor settings.
+    pub name: String,
+    /// Monitor bounds in logical points.
+    pub rect: Rect,
+    /// Whether this is the primary monitor.
+    pub is_primary: bool,
+}
+
+/// Returns the bounds of all connected monitors.
+///
+/// Returns an empty list if the platform can't be queried.
+pub fn monitors() -> Vec<Monitor> {
+    DisplayInfo::all()
+        .map(|displays| displays.iter().map(to_monitor).collect())
+        .unwrap_or_default()
+}
+
+/// Converts a display to logical-point bounds.
+///
+/// macOS already reports points; other platforms report physical pixels.
+fn to_monitor(display: &DisplayInfo) -> Monitor {
+    let scale = if cfg!(target_os = "macos") || display.scale_factor <= 0.0 {
+        1.0
+    } else {
+        display.scale_factor
+    };
+    let min = Pos2::new(display.x as f32 / scale, display.y as f32 / scale);
+    let size = Vec2::new(display.width as f32 / scale, display.height as f32 / scale);
+    Monitor {
+        name: display.name.clone(),
+        rect: Rect::from_min_size(min, size),
+        is_primary: display.is_primary,
+    }
+}
+
+/// Returns the monitor the window belongs on.
+///
+/// That's the monitor overlapping the window the most, or — if the window is
+/// entirely off screen (e.g. its monitor was unplugged) — the primary monitor.
+pub fn home_monitor(window: Rect, monitors: &[Monitor]) -> Option<&Monitor> {
+    let overlap = |monitor: &Monitor| {
+        let intersection = monitor.rect.intersect(window);
+        if intersection.is_positive() {
+            intersection.area()
+        } else {
+            0.0
+        }
+    };
+
+    monitors
+        .iter()
+        .filter(|monitor| overlap(monitor) > 0.0)
+        .max_by(|a, b| overlap(a).total_cmp(&overlap(b)))
+        .or_else(|| monitors.iter().find(|monitor| monitor.is_primary))
+        .or_else(|| monitors.first())
+}
+
+/// Returns where to restore a window last saved at `saved`.
+///
+/// If the monitor named `monitor` is connected, the window goes back onto it,
+/// `offset` from its top-left corner, even if the monitors were rearranged
+/// since. Otherwise the saved position is kept, and [`keep_on_screen`] moves
+/// the window onto a connected monitor if it needs to.
+pub fn restore_position(saved: Pos2, monitor: &str, offset: Vec2, monitors: &[Monitor]) -> Pos2 {
+    monitors
+        .iter()
+        .find(|connected| !monitor.is_empty() && connected.name == monitor)
+        .map_or(saved, |connected| connected.rect.min + offset)
+}
+
+/// Returns where the window should be so it lies fully on a monitor.
+///
+/// The window is clamped inside its [`home_monitor`]. If `snap_distance` is
+/// positive, any edge within that many points of a monitor edge is snapped
+/// flush to it. Returns `None` if no monitors are known.
+pub fn keep_on_screen(window: Rect, monitors: &[Monitor], snap_distance: f32) -> Option<Pos2> {
+    let bounds = home_monitor(window, monitors)?.rect;
+    let size = window.size();
+
+    let clamp_axis = |pos: f32, len: f32, min: f32, max: f32| {
+        let pos = pos.min(max - len).max(min);
+        if snap_distance <= 0.0 {
+            pos
+        } else if (pos - min).abs() <= snap_distance {
+            min
+        } else if (max - (pos + len)).abs() <= snap_distance {
+            max - len
+        } else {
+            pos
+        }
+    };
+
+    Some(Pos2::new(
+        clamp_axis(window.min.x, size.x, bounds.min.x, bounds.max.x),
+        clamp_axis(window.min.y, size.y, bounds.min.y, bounds.max.y),
+    ))
+}
+
+/// Where a companion docked to a monitor's side edge sits as it slides.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Dock {
+    /// Position while slid in, flush against the edge.
+    pub shown: Pos2,
+    /// Position while slid out, leaving only a sliver on screen.
+    pub hidden: Pos2,
+    /// Window size the positions were worked out for.
+    pub size: Vec2,
+}
+
+/// Returns how to dock a window resting flush against the left or right edge
+/// of its monitor, leaving `sliver` points on screen while hidden.
+///
+/// Returns `None` if the window isn't against a side edge, or if sliding it
+/// out would put it on a neighbouring monitor instead of off screen.
+pub fn dock(window: Rect, monitors: &[Monitor], sliver: f32) -> Option<Dock> {
+    let bounds = home_monitor(window, monitors)?.rect;
+    let hidden_x = if (window.min.x - bounds.min.x).abs() < 0.5 {
+        bounds.min.x - window.width() + sliver
+    } else if (window.max.x - bounds.max.x).abs() < 0.5 {
+        bounds.max.x - sliver
+    } else {
+        return None;
+    };
+
+    let hidden = Pos2::new(hidden_x, window.min.y);
+    let hidden_rect = Rect::from_min_size(hidden, window.size());
+    let onto_neighbour = monitors
+        .iter()
+        .filter(|monitor| monitor.rect != bounds)
+        .any(|monitor| monitor.rect.intersect(hidden_rect).is_positive());
+    if onto_neighbour {
+        return None;
+    }
+
+    Some(Dock {
+        shown: window.min,
+        hidden,
+        size: window.size(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(name: &str, x: f32, is_primary: bool) -> Monitor {
+        Monitor {
+            name: name.to_string(),
+            rect: Rect::from_min_size(Pos2::new(x, 0.0), Vec2::new(1920.0, 1080.0)),
+            is_primary,
+        }
+    }
+
+    #[test]
+    fn restores_onto_a_rearranged_monitor() {
+        // The side monitor moved from the right of the laptop to its left
+        let monitors = [
+            monitor("Laptop", 0.0, true),
+            monitor("Side", -1920.0, false),
+        ];
+        let restored = restore_position(
+            Pos2::new(2020.0, 300.0),
+            "Side",
+            Vec2::new(100.0, 300.0),
+            &monitors,
+        );
+        assert_eq!(restored, Pos2::new(-1820.0, 300.0));
+    }
+
+    #[test]
+    fn keeps_the_saved_position_when_the_monitor_is_gone() {
+        let monitors = [monitor("Laptop", 0.0, true)];
+        let saved = Pos2::new(2020.0, 300.0);
+        let restored = restore_position(saved, "Side", Vec2::new(100.0, 300.0), &monitors);
+        assert_eq!(restored, saved);
+    }
+
+    #[test]
+    fn keeps_the_saved_position_without_a_saved_monitor() {
+        let monitors = [monitor("", 0.0, true)];
+        let saved = Pos2::new(500.0, 300.0);
+        let restored = restore_position(saved, "", Vec2::ZERO, &monitors);
+        assert_eq!(restored, saved);
+    }
+}
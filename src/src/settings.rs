@@ -4,21 +4,429 @@
 //! - macOS: `~/Library/Application Support/com.praymodoro.Praymodoro/settings.json`
 //! - Linux: `~/.config/praymodoro/settings.json`
 //! - Windows: `%APPDATA%\praymodoro\Praymodoro\settings.json`
+//!
+//! The location can be changed with `--data-dir`; see [`crate::storage`].
 
+use crate::calendar::FeastRank;
+use crate::format;
+use crate::storage;
+use crate::timer::{PomodoroSegment, ScheduleKind, TimerEngine, POMODORO_SEGMENTS};
 use directories::ProjectDirs;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::fs;
 use std::path::PathBuf;
 
+/// How the companion window stacks against other windows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum WindowLayer {
+    /// Floats above all other windows.
+    #[default]
+    AlwaysOnTop,
+    /// Stacks like a normal window.
+    Normal,
+    /// Sits behind all other windows, like a desktop pet.
+    Desktop,
+}
+
+impl WindowLayer {
+    /// All layers, in the order they appear in the tray menu.
+    pub const ALL: &'static [WindowLayer] = &[
+        WindowLayer::AlwaysOnTop,
+        WindowLayer::Normal,
+        WindowLayer::Desktop,
+    ];
+
+    /// Returns the human-readable name of the layer.
+    pub fn label(&self) -> &'static str {
+        match self {
+            WindowLayer::AlwaysOnTop => "Always on Top",
+            WindowLayer::Normal => "Normal",
+            WindowLayer::Desktop => "On Desktop",
+        }
+    }
+
+    /// Returns the egui window level for this layer.
+    pub fn level(&self) -> egui::WindowLevel {
+        match self {
+            WindowLayer::AlwaysOnTop => egui::WindowLevel::AlwaysOnTop,
+            WindowLayer::Normal => egui::WindowLevel::Normal,
+            WindowLayer::Desktop => egui::WindowLevel::AlwaysOnBottom,
+        }
+    }
+}
+
+/// How the break overlay appears during rest.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum OverlayStyle {
+    /// Dims the whole screen.
+    #[default]
+    Fullscreen,
+    /// A prayer window centered on the screen, leaving the rest visible.
+    Window,
+}
+
+impl OverlayStyle {
+    /// All styles, in the order they appear in the tray menu.
+    pub const ALL: &'static [OverlayStyle] = &[OverlayStyle::Fullscreen, OverlayStyle::Window];
+
+    /// Returns the human-readable name of the style.
+    pub fn label(&self) -> &'static str {
+        match self {
+            OverlayStyle::Fullscreen => "Dim Screen",
+            OverlayStyle::Window => "Prayer Window",
+        }
+    }
+}
+
+/// What the tray icon shows beside it in the menu bar.
+///
+/// Only macOS and some Linux desktops show a title next to the tray icon.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum TrayTitle {
+    /// Just the icon.
+    #[default]
+    Off,
+    /// A countdown clock, e.g. "24:13".
+    Countdown,
+    /// A countdown clock with a cross, e.g. "24:13 ✝".
+    CountdownWithCross,
+    /// Whole minutes left, e.g. "24m".
+    Minutes,
+}
+
+impl TrayTitle {
+    /// All titles, in the order they appear in the tray menu.
+    pub const ALL: &'static [TrayTitle] = &[
+        TrayTitle::Off,
+        TrayTitle::Countdown,
+        TrayTitle::CountdownWithCross,
+        TrayTitle::Minutes,
+    ];
+
+    /// Returns the human-readable name of the title.
+    pub fn label(&self) -> &'static str {
+        match self {
+            TrayTitle::Off => "Off",
+            TrayTitle::Countdown => "Countdown",
+            TrayTitle::CountdownWithCross => "Countdown with Cross",
+            TrayTitle::Minutes => "Minutes",
+        }
+    }
+
+    /// Returns the title with `remaining` seconds left, or `None` for no title.
+    pub fn text(&self, remaining: i32) -> Option<String> {
+        match self {
+            TrayTitle::Off => None,
+            TrayTitle::Countdown => Some(format::clock(remaining)),
+            TrayTitle::CountdownWithCross => Some(format!("{} ✝", format::clock(remaining))),
+            TrayTitle::Minutes => Some(format::minutes(remaining)),
+        }
+    }
+}
+
+/// What double-clicking the tray icon does.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum TrayDoubleClickAction {
+    /// Do nothing.
+    Nothing,
+    /// Show or hide the companion window.
+    #[default]
+    ToggleCompanion,
+    /// Open the planner window.
+    OpenPlanner,
+    /// Pause or resume the countdown.
+    TogglePause,
+    /// End the current period early.
+    SkipPeriod,
+}
+
+/// A part of the tray menu, placed by [`Settings::menu_layout`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum MenuSection {
+    /// Countdown, today's counts, plan label, missed prayers, and partner status.
+    Status,
+    /// Planner, sprint, pause, skip, and copy status items.
+    Session,
+    /// Profile submenu.
+    Profile,
+    /// Size submenu.
+    Size,
+    /// Layer submenu.
+    Layer,
+    /// Timer display submenu.
+    TimerDisplay,
+    /// Menu bar title submenu.
+    MenuBarTitle,
+    /// Opacity submenu.
+    Opacity,
+    /// Appearance submenu.
+    Appearance,
+    /// Character submenu.
+    Character,
+    /// Intention submenu.
+    Intention,
+    /// Schedule submenu.
+    Schedule,
+    /// Rosary, touch, break overlay, breathing, motion, quote, prayer, and sound options.
+    Options,
+    /// Show/hide, snooze, and every-monitor items.
+    Companion,
+    /// Start at login, settings, updates, and about items.
+    App,
+    /// Quit item.
+    Quit,
+    /// A separator line.
+    Separator,
+}
+
+impl MenuSection {
+    /// The default menu layout.
+    pub const DEFAULT_LAYOUT: &'static [MenuSection] = &[
+        MenuSection::Status,
+        MenuSection::Session,
+        MenuSection::Separator,
+        MenuSection::Profile,
+        MenuSection::Size,
+        MenuSection::Layer,
+        MenuSection::TimerDisplay,
+        MenuSection::MenuBarTitle,
+        MenuSection::Opacity,
+        MenuSection::Appearance,
+        MenuSection::Separator,
+        MenuSection::Character,
+        MenuSection::Intention,
+        MenuSection::Schedule,
+        MenuSection::Options,
+        MenuSection::Companion,
+        MenuSection::Separator,
+        MenuSection::App,
+        MenuSection::Quit,
+    ];
+}
+
+/// Which upcoming celebrations the saint announces the day before.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum FeastReminders {
+    /// No announcements.
+    Off,
+    /// Solemnities only.
+    Solemnities,
+    /// Feasts and solemnities.
+    #[default]
+    Feasts,
+    /// Memorials, feasts, and solemnities.
+    All,
+}
+
+impl FeastReminders {
+    /// All choices, in the order they appear in the settings window.
+    pub const ALL: &'static [FeastReminders] = &[
+        FeastReminders::Off,
+        FeastReminders::Solemnities,
+        FeastReminders::Feasts,
+        FeastReminders::All,
+    ];
+
+    /// Returns the human-readable name of the choice.
+    pub fn label(&self) -> &'static str {
+        match self {
+            FeastReminders::Off => "Off",
+            FeastReminders::Solemnities => "Solemnities",
+            FeastReminders::Feasts => "Feasts and Solemnities",
+            FeastReminders::All => "All, with Memorials",
+        }
+    }
+
+    /// Returns the lowest rank announced, or `None` if nothing is.
+    pub fn min_rank(&self) -> Option<FeastRank> {
+        match self {
+            FeastReminders::Off => None,
+            FeastReminders::Solemnities => Some(FeastRank::Solemnity),
+            FeastReminders::Feasts => Some(FeastRank::Feast),
+            FeastReminders::All => Some(FeastRank::Memorial),
+        }
+    }
+}
+
+/// A named set of settings that can be swapped in as a whole from the tray.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum Profile {
+    /// Settings for the working day.
+    #[default]
+    Work,
+    /// Settings for time at home.
+    Home,
+    /// Settings for a retreat.
+    Retreat,
+    /// Child-friendly settings, left only with the family PIN.
+    Family,
+}
+
+impl Profile {
+    /// All profiles, in the order they appear in the tray menu.
+    pub const ALL: &'static [Profile] = &[
+        Profile::Work,
+        Profile::Home,
+        Profile::Retreat,
+        Profile::Family,
+    ];
+
+    /// Returns the human-readable name of the profile.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Profile::Work => "Work",
+            Profile::Home => "Home",
+            Profile::Retreat => "Retreat",
+            Profile::Family => "Family",
+        }
+    }
+
+    /// Returns the name of the file the profile's settings are kept in.
+    fn file_name(&self) -> &'static str {
+        match self {
+            Profile::Work => "profile-work.json",
+            Profile::Home => "profile-home.json",
+            Profile::Retreat => "profile-retreat.json",
+            Profile::Family => "profile-family.json",
+        }
+    }
+
+    /// Returns the settings the profile starts with when first used, based on `current`.
+    ///
+    /// The family profile starts with shorter work periods, simple prayers, a
+    /// larger companion, and network features turned off.
+    fn first_settings(&self, current: &Settings) -> Settings {
+        let mut settings = current.clone();
+        if *self == Profile::Family {
+            settings.schedule = ScheduleKind::Family;
+            settings.engine = TimerEngine::ClockAligned;
+            settings.simple_prayers = true;
+            settings.prayer_before_work = false;
+            settings.rosary_mode = false;
+            settings.window.scale = settings.window.scale.max(FAMILY_SCALE);
+            settings.offline = true;
+            settings.presence.enabled = false;
+        }
+        settings
+    }
+}
+
+/// Smallest window scale of the family profile, for larger text.
+const FAMILY_SCALE: f32 = 1.5;
+
+/// Color of the outline drawn around the sprite and timer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum OutlineTone {
+    /// A light outline, for dark wallpapers.
+    #[default]
+    Light,
+    /// A dark outline, for light wallpapers.
+    Dark,
+}
+
+impl OutlineTone {
+    /// All tones, in the order they appear in the settings window.
+    pub const ALL: &'static [OutlineTone] = &[OutlineTone::Light, OutlineTone::Dark];
+
+    /// Returns the human-readable name of the tone.
+    pub fn label(&self) -> &'static str {
+        match self {
+            OutlineTone::Light => "Light",
+            OutlineTone::Dark => "Dark",
+        }
+    }
+
+    /// Returns the outline color for an outline of `strength` (0.0 to 1.0).
+    pub fn color(&self, strength: f32) -> egui::Color32 {
+        let alpha = (strength.clamp(0.0, 1.0) * 200.0) as u8;
+        match self {
+            OutlineTone::Light => egui::Color32::from_white_alpha(alpha),
+            OutlineTone::Dark => egui::Color32::from_black_alpha(alpha),
+        }
+    }
+}
+
+/// How the companion's timer shows the time left.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum TimerDisplay {
+    /// A countdown clock, e.g. "24:13".
+    #[default]
+    Clock,
+    /// Whole minutes left, e.g. "24m".
+    Minutes,
+    /// How much of the period is done, e.g. "3%".
+    Percent,
+    /// No numbers, only a progress ring.
+    SoftFocus,
+}
+
+impl TimerDisplay {
+    /// All displays, in the order they appear in the tray menu.
+    pub const ALL: &'static [TimerDisplay] = &[
+        TimerDisplay::Clock,
+        TimerDisplay::Minutes,
+        TimerDisplay::Percent,
+        TimerDisplay::SoftFocus,
+    ];
+
+    /// Returns the human-readable name of the display.
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimerDisplay::Clock => "Clock",
+            TimerDisplay::Minutes => "Minutes",
+            TimerDisplay::Percent => "Percent Complete",
+            TimerDisplay::SoftFocus => "Soft Focus",
+        }
+    }
+
+    /// Returns the timer text for a period, or `None` when no numbers are shown.
+    pub fn text(&self, remaining: i32, duration: i32) -> Option<String> {
+        match self {
+            TimerDisplay::Clock => Some(format::clock(remaining)),
+            TimerDisplay::Minutes => Some(format::minutes(remaining)),
+            TimerDisplay::Percent => Some(format::percent(remaining, duration)),
+            TimerDisplay::SoftFocus => None,
+        }
+    }
+}
+
 /// Window positioning and scale settings.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
 pub struct WindowSettings {
     /// Window X position on screen.
     pub x: f32,
     /// Window Y position on screen.
     pub y: f32,
+    /// Name of the monitor the window was last on, or empty if not known.
+    pub monitor: String,
+    /// Window X position relative to the left edge of [`Self::monitor`].
+    pub monitor_x: f32,
+    /// Window Y position relative to the top edge of [`Self::monitor`].
+    pub monitor_y: f32,
     /// Window scale factor (0.5 = 50%, 1.0 = 100%, 2.0 = 200%).
     pub scale: f32,
+    /// Whether the window snaps flush to monitor edges when dropped near them.
+    pub snap_to_edges: bool,
+    /// How close (in points) to a monitor edge the window must be to snap.
+    pub snap_distance: f32,
+    /// Whether resting against a side edge docks the window, sliding it mostly off screen.
+    pub edge_dock: bool,
+    /// Whether the saint is mirrored to face the other way.
+    pub flip: bool,
+    /// Whether the companion window is shown.
+    pub visible: bool,
+    /// How the window stacks against other windows.
+    pub layer: WindowLayer,
 }
 
 impl Default for WindowSettings {
@@ -26,66 +434,414 @@ impl Default for WindowSettings {
         Self {
             x: 100.0,
             y: 100.0,
+            monitor: String::new(),
+            monitor_x: 0.0,
+            monitor_y: 0.0,
+            scale: 1.0,
+            snap_to_edges: false,
+            snap_distance: 24.0,
+            edge_dock: false,
+            flip: false,
+            visible: true,
+            layer: WindowLayer::default(),
+        }
+    }
+}
+
+/// Global keyboard shortcut bindings.
+///
+/// Each binding uses the `global-hotkey` accelerator syntax (e.g. `"CmdOrCtrl+Alt+H"`).
+/// An empty string disables the shortcut.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct HotkeySettings {
+    /// Show or hide the companion window.
+    pub toggle_visibility: String,
+    /// End the current period early.
+    pub skip_period: String,
+    /// Pause or resume the countdown.
+    pub pause: String,
+    /// Cycle to the next saint character.
+    pub next_character: String,
+    /// Open the command palette.
+    pub command_palette: String,
+}
+
+impl Default for HotkeySettings {
+    fn default() -> Self {
+        Self {
+            toggle_visibility: "CmdOrCtrl+Alt+H".to_string(),
+            skip_period: "CmdOrCtrl+Alt+S".to_string(),
+            pause: "CmdOrCtrl+Alt+P".to_string(),
+            next_character: "CmdOrCtrl+Alt+N".to_string(),
+            command_palette: "CmdOrCtrl+Alt+K".to_string(),
+        }
+    }
+}
+
+/// Keys handled while the companion window has focus.
+///
+/// Each binding is an egui key name (e.g. `"Space"`, `"H"`, `"ArrowLeft"`).
+/// An empty string disables the key.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct KeymapSettings {
+    /// Pause or resume the countdown.
+    pub pause: String,
+    /// Hide the companion window.
+    pub hide: String,
+    /// Make the companion larger.
+    pub scale_up: String,
+    /// Make the companion smaller.
+    pub scale_down: String,
+    /// Nudge the companion left.
+    pub nudge_left: String,
+    /// Nudge the companion right.
+    pub nudge_right: String,
+    /// Nudge the companion up.
+    pub nudge_up: String,
+    /// Nudge the companion down.
+    pub nudge_down: String,
+}
+
+impl Default for KeymapSettings {
+    fn default() -> Self {
+        Self {
+            pause: "Space".to_string(),
+            hide: "H".to_string(),
+            scale_up: "Plus".to_string(),
+            scale_down: "Minus".to_string(),
+            nudge_left: "ArrowLeft".to_string(),
+            nudge_right: "ArrowRight".to_string(),
+            nudge_up: "ArrowUp".to_string(),
+            nudge_down: "ArrowDown".to_string(),
+        }
+    }
+}
+
+/// How the companion sprite is drawn.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct AppearanceSettings {
+    /// Strength of the soft drop shadow behind the sprite, from 0.0 (off) to 1.0.
+    pub sprite_shadow: f32,
+    /// Strength of the outline around the sprite, from 0.0 (off) to 1.0.
+    pub sprite_outline: f32,
+    /// Color of the outline.
+    pub outline_tone: OutlineTone,
+    /// Whether the shadow and outline are also drawn around the timer.
+    pub timer_effects: bool,
+    /// Opacity of the sprite and timer, from 0.25 to 1.0 (opaque).
+    pub opacity: f32,
+    /// Whether the companion fades while the pointer rests on it.
+    pub hover_fade: bool,
+    /// Opacity the companion fades to on hover, from 0.1 to 1.0.
+    pub hover_fade_opacity: f32,
+    /// Whether night variants of the sprites (e.g. `work-night.png`) are used in the evening.
+    pub night_sprites: bool,
+    /// Local hour (0-23) the evening starts, lasting until [`MORNING_HOUR`].
+    pub evening_hour: u32,
+}
+
+/// Local hour the evening ends and day sprites return.
+pub const MORNING_HOUR: u32 = 6;
+
+impl AppearanceSettings {
+    /// Returns whether night sprites should be used at `hour` (0-23, local time).
+    pub fn is_night(&self, hour: u32) -> bool {
+        self.night_sprites && (hour >= self.evening_hour || hour < MORNING_HOUR)
+    }
+}
+
+impl Default for AppearanceSettings {
+    fn default() -> Self {
+        Self {
+            sprite_shadow: 0.0,
+            sprite_outline: 0.0,
+            outline_tone: OutlineTone::default(),
+            timer_effects: true,
+            opacity: 1.0,
+            hover_fade: false,
+            hover_fade_opacity: 0.2,
+            night_sprites: true,
+            evening_hour: 19,
+        }
+    }
+}
+
+/// Opt-in sharing of the timer status with an accountability partner.
+///
+/// See [`crate::presence`]. The signing key is kept separately from these settings.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct PresenceSettings {
+    /// Whether the status is published and the partner's status is read.
+    pub enabled: bool,
+    /// Folder, file, or URL the signed status is published to.
+    pub publish_to: String,
+    /// Folder, file, or URL the partner's signed status is read from.
+    pub partner_from: String,
+    /// Partner's hex-encoded public key, used to check their status.
+    pub partner_key: String,
+}
+
+/// A talk heard a little at a time during long breaks.
+///
+/// See [`crate::lecture`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct LectureSettings {
+    /// Whether the talk plays during long breaks.
+    pub enabled: bool,
+    /// Path of the local audio file (MP3, Ogg Vorbis, or WAV) to play.
+    pub file: String,
+    /// Shortest rest period, in minutes, that the talk plays during.
+    pub min_break_minutes: u32,
+}
+
+impl Default for LectureSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            file: String::new(),
+            min_break_minutes: 10,
+        }
+    }
+}
+
+/// Saturday-evening reminder and Sunday rest preferences.
+///
+/// See [`crate::sabbath`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct SabbathSettings {
+    /// Whether to be reminded on Saturday evening to plan Sunday's rest.
+    pub reminder: bool,
+    /// Hour (0-23) on Saturday from which the reminder is shown.
+    pub reminder_hour: u32,
+    /// Whether the companion is put away for all of Sunday.
+    pub sunday_rest: bool,
+}
+
+impl Default for SabbathSettings {
+    fn default() -> Self {
+        Self {
+            reminder: false,
+            reminder_hour: 18,
+            sunday_rest: false,
+        }
+    }
+}
+
+/// A companion shown on an additional monitor, mirroring the main one.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct MonitorCompanion {
+    /// Name of the monitor the companion is on.
+    pub monitor: String,
+    /// Window X position on screen.
+    pub x: f32,
+    /// Window Y position on screen.
+    pub y: f32,
+    /// Window scale factor (0.5 = 50%, 1.0 = 100%, 2.0 = 200%).
+    pub scale: f32,
+    /// Whether the saint is mirrored to face the other way.
+    pub flip: bool,
+}
+
+impl Default for MonitorCompanion {
+    fn default() -> Self {
+        Self {
+            monitor: String::new(),
+            x: 0.0,
+            y: 0.0,
             scale: 1.0,
+            flip: false,
+        }
+    }
+}
+
+/// Which period changes show a desktop notification.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct NotificationSettings {
+    /// Notify when a rest (prayer) period begins.
+    pub rest_start: bool,
+    /// Notify when a work period begins.
+    pub work_start: bool,
+    /// Notify a minute before each period ends.
+    pub minute_warning: bool,
+    /// Play a chime with these notifications.
+    pub sound: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            rest_start: true,
+            work_start: true,
+            minute_warning: false,
+            sound: true,
         }
     }
 }
 
 /// User preferences persisted between application sessions.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+///
+/// Missing fields fall back to their defaults so settings files written by
+/// older versions keep loading.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
 pub struct Settings {
+    /// Profile these settings belong to.
+    pub profile: Profile,
     /// Window positioning and scale preferences.
     pub window: WindowSettings,
     /// Selected saint character identifier.
     pub character: String,
+    /// Schedule the timer follows.
+    pub schedule: ScheduleKind,
+    /// Division of the hour followed by the custom schedule.
+    pub custom_schedule: Vec<PomodoroSegment>,
+    /// Whether periods are clock-aligned or free-running.
+    pub engine: TimerEngine,
+    /// Whether rest periods are divided into a rosary decade countdown.
+    pub rosary_mode: bool,
+    /// Whether touch-friendly interaction is forced on, even without detected touch input.
+    pub touch_mode: bool,
+    /// Global keyboard shortcut bindings.
+    pub hotkeys: HotkeySettings,
+    /// Keys handled while the companion window has focus.
+    pub keymap: KeymapSettings,
+    /// What double-clicking the tray icon does.
+    pub tray_double_click: TrayDoubleClickAction,
+    /// What the tray icon shows beside it in the menu bar.
+    pub tray_title: TrayTitle,
+    /// Order of the tray menu's sections; sections left out are hidden.
+    ///
+    /// Read when the tray is created, so changes apply on the next launch.
+    pub menu_layout: Vec<MenuSection>,
+    /// Desktop notification preferences.
+    pub notifications: NotificationSettings,
+    /// Whether rest periods open a prayer overlay.
+    pub break_overlay: bool,
+    /// Whether the break overlay dims the screen or is a centered window.
+    pub overlay_style: OverlayStyle,
+    /// Whether the break overlay opens with a guided breathing pause before the prayer.
+    pub breathing_pause: bool,
+    /// Whether animations are replaced with still images.
+    pub reduce_motion: bool,
+    /// Whether a companion is also shown on every other monitor.
+    pub multi_companion: bool,
+    /// Position and scale of the companion on each additional monitor.
+    pub monitor_companions: Vec<MonitorCompanion>,
+    /// Whether the saint now and then says a short quote in a speech bubble.
+    pub speech_bubbles: bool,
+    /// Which celebrations the saint announces in a speech bubble the day before.
+    pub feast_reminders: FeastReminders,
+    /// Whether each work period opens with a short prayer card.
+    pub prayer_before_work: bool,
+    /// Id of the prayer shown on the prayer-before-work card.
+    pub work_prayer: String,
+    /// Whether quitting during a running work period asks for confirmation first.
+    pub confirm_quit: bool,
+    /// Saved prayer intentions, offered in the tray.
+    pub intentions: Vec<String>,
+    /// Intention shown on the break overlay, or empty for none.
+    pub intention: String,
+    /// Sprite shadow and outline preferences.
+    pub appearance: AppearanceSettings,
+    /// How the companion's timer shows the time left.
+    pub timer_display: TimerDisplay,
+    /// Accountability partner presence sharing.
+    pub presence: PresenceSettings,
+    /// Saturday-evening reminder and Sunday rest.
+    pub sabbath: SabbathSettings,
+    /// Talk played during long breaks.
+    pub lecture: LectureSettings,
+    /// Whether rest periods keep to short, familiar prayers.
+    pub simple_prayers: bool,
+    /// Whether network features (update checks and presence sharing) are turned off.
+    pub offline: bool,
+    /// PIN asked for before leaving the family profile or changing its
+    /// settings, or empty for none.
+    pub family_pin: String,
 }
 
 impl Default for Settings {
     fn default() -> Self {
         Self {
+            profile: Profile::default(),
             window: WindowSettings::default(),
             character: "augustine-of-hippo".to_string(),
+            schedule: ScheduleKind::default(),
+            custom_schedule: POMODORO_SEGMENTS.to_vec(),
+            engine: TimerEngine::default(),
+            rosary_mode: false,
+            touch_mode: false,
+            hotkeys: HotkeySettings::default(),
+            keymap: KeymapSettings::default(),
+            tray_double_click: TrayDoubleClickAction::default(),
+            tray_title: TrayTitle::default(),
+            menu_layout: MenuSection::DEFAULT_LAYOUT.to_vec(),
+            notifications: NotificationSettings::default(),
+            break_overlay: false,
+            overlay_style: OverlayStyle::default(),
+            breathing_pause: false,
+            reduce_motion: false,
+            speech_bubbles: true,
+            feast_reminders: FeastReminders::default(),
+            multi_companion: false,
+            monitor_companions: Vec::new(),
+            prayer_before_work: false,
+            work_prayer: "creator-ineffabilis".to_string(),
+            confirm_quit: false,
+            intentions: Vec::new(),
+            intention: String::new(),
+            appearance: AppearanceSettings::default(),
+            timer_display: TimerDisplay::default(),
+            presence: PresenceSettings::default(),
+            sabbath: SabbathSettings::default(),
+            lecture: LectureSettings::default(),
+            simple_prayers: false,
+            offline: false,
+            family_pin: String::new(),
         }
     }
 }
 
-/// Returns the path to the settings file.
+/// Returns the application's config directory.
 ///
 /// Uses the `directories` crate to determine the platform-specific config directory.
 /// Returns `None` if the config directory cannot be determined.
-fn settings_path() -> Option<PathBuf> {
-    ProjectDirs::from("com", "praymodoro", "Praymodoro").map(|dirs| {
-        let config_dir = dirs.config_dir();
-        config_dir.join("settings.json")
-    })
+pub fn config_dir() -> Option<PathBuf> {
+    ProjectDirs::from("com", "praymodoro", "Praymodoro").map(|dirs| dirs.config_dir().to_path_buf())
 }
 
-/// Loads settings from disk, or returns defaults if the file doesn't exist.
+/// Loads settings from storage, or returns defaults if there are none.
 ///
 /// This function silently handles errors (file not found, invalid JSON, etc.)
 /// by returning default settings.
 pub fn load_settings() -> Settings {
-    if let Some(path) = settings_path() {
-        if let Ok(contents) = fs::read_to_string(&path) {
-            if let Ok(settings) = serde_json::from_str(&contents) {
-                return settings;
-            }
-        }
-    }
-    Settings::default()
+    storage::load("settings.json").unwrap_or_default()
 }
 
-/// Saves settings to disk.
+/// Queues settings to be saved.
 ///
-/// Creates the config directory if it doesn't exist. Errors are silently ignored
-/// to avoid disrupting the application if settings cannot be saved.
+/// Returns at once; failures are retried and reported by [`storage::save`].
 pub fn save_settings(settings: &Settings) {
-    if let Some(path) = settings_path() {
-        if let Some(parent) = path.parent() {
-            let _ = fs::create_dir_all(parent);
-        }
-        if let Ok(json) = serde_json::to_string_pretty(settings) {
-            let _ = fs::write(&path, json);
-        }
-    }
+    storage::save("settings.json", settings);
+}
+
+/// Switches to `profile`, returning its settings.
+///
+/// The current settings are kept under their own profile first, so switching
+/// back restores them. A profile used for the first time starts as a copy of
+/// the current settings, adjusted by [`Profile::first_settings`].
+pub fn switch_profile(current: &Settings, profile: Profile) -> Settings {
+    storage::save(current.profile.file_name(), current);
+    let mut settings: Settings =
+        storage::load(profile.file_name()).unwrap_or_else(|| profile.first_settings(current));
+    settings.profile = profile;
+    settings
 }
@@ -3,12 +3,21 @@
 //! This module defines the core state structures including timer mode,
 //! character selection, window positioning, and user preferences.
 
+use crate::plan::DayPlan;
+use crate::presence::PresenceStatus;
 use crate::settings::Settings;
+use crate::stats::PomodoroStats;
+use crate::timer::ScheduleKind;
+use chrono::{DateTime, Local};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
-/// List of available saint characters for the desktop companion.
+/// Built-in saint characters shipped with the desktop companion.
 ///
 /// Each character has corresponding sprite assets in the `assets/characters/` directory
-/// with idle, work, and quick-break animations.
+/// with idle, work, and quick-break animations. The characters actually offered are
+/// discovered from that directory by [`crate::characters`]; this list sets their
+/// order and is the fallback when no assets can be found.
 pub const AVAILABLE_CHARACTERS: &[&str] = &[
     "augustine-of-hippo",
     "thomas-aquinas",
@@ -16,6 +25,12 @@ pub const AVAILABLE_CHARACTERS: &[&str] = &[
     "thomas-more",
 ];
 
+/// Smallest window scale (50%).
+pub const MIN_SCALE: f32 = 0.5;
+
+/// Largest window scale (200%).
+pub const MAX_SCALE: f32 = 2.0;
+
 /// Represents the current mode of the Pomodoro timer.
 ///
 /// The timer alternates between [`Work`] sessions for focused productivity
@@ -28,7 +43,8 @@ pub const AVAILABLE_CHARACTERS: &[&str] = &[
 /// - 25-30 minutes: Rest (prayer)
 /// - 30-55 minutes: Work
 /// - 55-60 minutes: Rest (prayer)
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
 pub enum PomodoroMode {
     /// Work mode - time for focused productivity.
     Work,
@@ -48,6 +64,64 @@ impl PomodoroMode {
     }
 }
 
+/// Number of beads in a rosary decade: one Our Father followed by ten Hail Marys.
+pub const ROSARY_DECADE_BEADS: usize = 11;
+
+/// Progress through a rosary decade during a rest period.
+///
+/// The rest period is divided evenly across the beads of a single decade, so
+/// the frontend can show which prayer is being said and how far along it is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RosaryProgress {
+    /// Current bead index (0 = Our Father, 1-10 = Hail Marys).
+    pub bead: usize,
+    /// Seconds remaining on the current bead.
+    pub bead_remaining_seconds: i32,
+}
+
+impl RosaryProgress {
+    /// Computes the decade progress for a point within a rest period.
+    ///
+    /// # Arguments
+    ///
+    /// * `elapsed` - Seconds elapsed since the rest period began
+    /// * `duration` - Total length of the rest period in seconds
+    pub fn at(elapsed: i32, duration: i32) -> Self {
+        let beads = ROSARY_DECADE_BEADS as i32;
+        let duration = duration.max(beads);
+        let elapsed = elapsed.clamp(0, duration - 1);
+        let bead = (elapsed * beads / duration) as usize;
+        let bead_end = (bead as i32 + 1) * duration / beads;
+        Self {
+            bead,
+            bead_remaining_seconds: bead_end - elapsed,
+        }
+    }
+
+    /// Returns the name of the prayer for the current bead.
+    pub fn prayer(&self) -> &'static str {
+        if self.bead == 0 {
+            "Our Father"
+        } else {
+            "Hail Mary"
+        }
+    }
+
+    /// Returns a short label for the current bead (e.g., "Hail Mary 3/10").
+    pub fn label(&self) -> String {
+        if self.bead == 0 {
+            self.prayer().to_string()
+        } else {
+            format!(
+                "{} {}/{}",
+                self.prayer(),
+                self.bead,
+                ROSARY_DECADE_BEADS - 1
+            )
+        }
+    }
+}
+
 /// The main application state shared between threads.
 ///
 /// This state is wrapped in `Arc<Mutex<_>>` to allow safe concurrent access
@@ -58,6 +132,8 @@ pub struct AppState {
     pub mode: PomodoroMode,
     /// Remaining seconds in the current period.
     pub remaining_seconds: i32,
+    /// Total length of the current period in seconds.
+    pub period_seconds: i32,
     /// Pre-formatted time string (MM:SS) for display.
     pub formatted_time: String,
     /// Currently selected saint character identifier.
@@ -70,8 +146,42 @@ pub struct AppState {
     pub settings: Settings,
     /// Signal flag to quit the application.
     pub should_quit: bool,
+    /// Whether the countdown is paused.
+    pub paused: bool,
+    /// Whether Praymodoro is registered to start at login, as last read from the OS.
+    pub start_at_login: bool,
+    /// Whether the current rest period has been seen, on the companion or the break overlay.
+    pub rest_seen: bool,
+    /// Rest periods that ended unseen, badging the tray icon until acknowledged.
+    pub missed_prayers: u32,
+    /// When a snooze from the tray ends, bringing the companion back.
+    ///
+    /// While snoozed the companion is hidden and notifications are silent.
+    pub snoozed_until: Option<DateTime<Local>>,
+    /// Signal flag asking the timer thread to end the current period early.
+    pub skip_requested: bool,
+    /// Signal flag asking the timer thread to start the current period over.
+    pub restart_requested: bool,
     /// Last known window position (x, y) in screen coordinates.
     pub window_position: Option<(f32, f32)>,
+    /// Rosary decade progress, present only during rest when rosary mode is enabled.
+    pub rosary: Option<RosaryProgress>,
+    /// Completed-pomodoro counters, persisted separately from settings.
+    pub stats: PomodoroStats,
+    /// Today's session plan, persisted separately from settings.
+    pub plan: DayPlan,
+    /// Schedule the clock-aligned timer is following right now.
+    ///
+    /// Lags behind `settings.schedule` until the current segment ends after a
+    /// switch. `None` while free-running.
+    pub active_schedule: Option<ScheduleKind>,
+    /// Accountability partner's last verified status, or why it couldn't be read.
+    ///
+    /// `None` while presence sharing is off or no partner is set up.
+    pub partner: Option<Result<PresenceStatus, String>>,
+    /// Context of the companion window, used by the timer thread to repaint
+    /// it when the time shown changes. Set once the window exists.
+    pub repaint: Option<egui::Context>,
 }
 
 impl AppState {
@@ -87,15 +197,57 @@ impl AppState {
         Self {
             mode: PomodoroMode::Work,
             remaining_seconds: 25 * 60,
+            period_seconds: 25 * 60,
             formatted_time: "25:00".to_string(),
             character: "augustine-of-hippo".to_string(),
             scale: 1.0,
             visible: true,
             settings: Settings::default(),
             should_quit: false,
+            paused: false,
+            start_at_login: false,
+            rest_seen: false,
+            missed_prayers: 0,
+            snoozed_until: None,
+            skip_requested: false,
+            restart_requested: false,
             window_position: None,
+            rosary: None,
+            stats: PomodoroStats::default(),
+            plan: DayPlan::default(),
+            active_schedule: None,
+            partner: None,
+            repaint: None,
         }
     }
+
+    /// Returns a one-line status summary suitable for pasting into chat.
+    ///
+    /// For example, `"Work · 17:42 remaining · 3/8 pomodoros today"` when today
+    /// has a plan, or `"Prayer · 03:10 remaining · 3 pomodoros today"` otherwise.
+    pub fn status_line(&self) -> String {
+        let mode = match self.mode {
+            PomodoroMode::Work => "Work",
+            PomodoroMode::Rest => "Prayer",
+        };
+        let paused = if self.paused { " (paused)" } else { "" };
+        let planned = self.plan.for_day(Local::now().date_naive()).planned_count();
+        let completed = self.stats.completed_today;
+        let done = if planned > 0 {
+            format!("{}/{}", completed, planned)
+        } else {
+            completed.to_string()
+        };
+        let noun = if planned == 0 && completed == 1 {
+            "pomodoro"
+        } else {
+            "pomodoros"
+        };
+        format!(
+            "{} · {} remaining{} · {} {} today",
+            mode, self.formatted_time, paused, done, noun
+        )
+    }
 }
 
 impl Default for AppState {
@@ -0,0 +1,456 @@
+//! Completed-pomodoro counters maintained by the timer.
+//!
+//! A pomodoro counts as completed when a work period runs through to rest.
+//! Counters roll over at local midnight (daily) and on Monday (weekly), and
+//! are saved next to the settings file as `stats.json` so they survive restarts.
+//! Storage writes go through a temp file and a backup so a hard kill can't
+//! corrupt them.
+//!
+//! The active sprint and the reports of finished sprints are kept here too,
+//! along with a record of each past day for the monthly report.
+
+use crate::format;
+use crate::storage;
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+/// Number of finished sprint reports kept in the stats file.
+const SPRINT_HISTORY_LIMIT: usize = 50;
+
+/// Number of past days kept in the stats file, a little over a year.
+const DAY_HISTORY_LIMIT: usize = 400;
+
+/// The counters of a single day.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DayRecord {
+    /// Local date the counters belong to.
+    pub date: NaiveDate,
+    /// Pomodoros completed.
+    pub completed: u32,
+    /// Rest periods prayed through to the end.
+    pub rests_kept: u32,
+    /// Rest periods that ended, whether prayed through or skipped.
+    pub rests_ended: u32,
+    /// Prayers before work offered.
+    pub work_prayers: u32,
+}
+
+impl DayRecord {
+    /// Returns whether anything was recorded on the day.
+    pub fn is_active(&self) -> bool {
+        self.completed > 0 || self.rests_ended > 0 || self.work_prayers > 0
+    }
+}
+
+/// A run of consecutive pomodoros toward a single goal.
+///
+/// While active, it counts completed and skipped work periods and rest periods
+/// prayed through to the end. Once finished it serves as the sprint's report.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Sprint {
+    /// What the sprint is for.
+    pub goal: String,
+    /// Number of pomodoros the sprint runs for.
+    pub target: u32,
+    /// Local time the sprint was started.
+    pub started: Option<NaiveDateTime>,
+    /// Local time the sprint finished or was ended early.
+    pub finished: Option<NaiveDateTime>,
+    /// Work periods run through to rest.
+    pub completed: u32,
+    /// Work periods cut short with Skip Period.
+    pub skipped: u32,
+    /// Rest periods prayed through to the end.
+    pub prayers_kept: u32,
+}
+
+impl Sprint {
+    /// Starts a sprint of `target` pomodoros at `now`.
+    pub fn new(goal: String, target: u32, now: NaiveDateTime) -> Self {
+        Self {
+            goal,
+            target: target.max(1),
+            started: Some(now),
+            ..Self::default()
+        }
+    }
+
+    /// Returns progress as "completed/target", e.g. "2/4".
+    pub fn progress(&self) -> String {
+        format!("{}/{}", self.completed.min(self.target), self.target)
+    }
+
+    /// Returns the share of work periods that weren't skipped, as a percentage.
+    pub fn focus_percent(&self) -> u32 {
+        let attempted = self.completed + self.skipped;
+        (self.completed * 100).checked_div(attempted).unwrap_or(100)
+    }
+
+    /// Returns a one-line summary of the sprint.
+    pub fn report(&self) -> String {
+        let goal = if self.goal.trim().is_empty() {
+            "Sprint".to_string()
+        } else {
+            format!("\"{}\"", self.goal.trim())
+        };
+        let elapsed = match (self.started, self.finished) {
+            (Some(started), Some(finished)) => {
+                format!(
+                    " in {}",
+                    format::spoken((finished - started).num_seconds() as i32)
+                )
+            }
+            _ => String::new(),
+        };
+        format!(
+            "{}: {} pomodoros{}, {}% focus, {} {} kept",
+            goal,
+            self.progress(),
+            elapsed,
+            self.focus_percent(),
+            self.prayers_kept,
+            if self.prayers_kept == 1 {
+                "prayer"
+            } else {
+                "prayers"
+            }
+        )
+    }
+}
+
+/// Point-in-time statistics, as printed by `--stats`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct StatsSummary {
+    /// Pomodoros completed today.
+    pub completed_today: u32,
+    /// Pomodoros completed this week.
+    pub completed_this_week: u32,
+    /// Pomodoros completed today in planned blocks.
+    pub completed_planned_today: u32,
+    /// Share of today's rest periods prayed through to the end, if any have ended.
+    pub rest_adherence_percent: Option<u32>,
+    /// Prayers before work offered today.
+    pub work_prayers_today: u32,
+    /// Consecutive days with a completed pomodoro.
+    pub current_streak_days: u32,
+}
+
+/// Pomodoros completed today and this week.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PomodoroStats {
+    /// Local date the daily counter belongs to.
+    pub day: Option<NaiveDate>,
+    /// Monday of the week the weekly counter belongs to.
+    pub week_start: Option<NaiveDate>,
+    /// Pomodoros completed on [`Self::day`].
+    pub completed_today: u32,
+    /// Pomodoros completed in the week starting [`Self::week_start`].
+    pub completed_this_week: u32,
+    /// Pomodoros completed today in blocks that had a planned label.
+    pub completed_planned_today: u32,
+    /// Rest periods prayed through to the end today.
+    pub rests_kept_today: u32,
+    /// Rest periods that ended today, whether prayed through or skipped.
+    pub rests_ended_today: u32,
+    /// Prayers before work offered today.
+    pub work_prayers_today: u32,
+    /// Consecutive days, ending on [`Self::last_active_day`], with a completed pomodoro.
+    pub streak_days: u32,
+    /// Last local date a pomodoro was completed.
+    pub last_active_day: Option<NaiveDate>,
+    /// The sprint in progress, if any.
+    pub sprint: Option<Sprint>,
+    /// Reports of finished sprints, oldest first.
+    pub sprint_history: Vec<Sprint>,
+    /// Counters of past days with any activity, oldest first.
+    pub day_history: Vec<DayRecord>,
+}
+
+impl PomodoroStats {
+    /// Resets the counters if `today` is a new day or a new week.
+    ///
+    /// Returns `true` if anything was reset, so the caller knows to save.
+    pub fn roll_over(&mut self, today: NaiveDate) -> bool {
+        let mut changed = false;
+
+        if self.day != Some(today) {
+            if let Some(record) = self.today().filter(DayRecord::is_active) {
+                self.day_history.push(record);
+                if self.day_history.len() > DAY_HISTORY_LIMIT {
+                    let excess = self.day_history.len() - DAY_HISTORY_LIMIT;
+                    self.day_history.drain(..excess);
+                }
+            }
+            self.day = Some(today);
+            self.completed_today = 0;
+            self.completed_planned_today = 0;
+            self.rests_kept_today = 0;
+            self.rests_ended_today = 0;
+            self.work_prayers_today = 0;
+            changed = true;
+        }
+
+        let week_start = week_start(today);
+        if self.week_start != Some(week_start) {
+            self.week_start = Some(week_start);
+            self.completed_this_week = 0;
+            changed = true;
+        }
+
+        changed
+    }
+
+    /// Returns the record of [`Self::day`], if there is one.
+    fn today(&self) -> Option<DayRecord> {
+        Some(DayRecord {
+            date: self.day?,
+            completed: self.completed_today,
+            rests_kept: self.rests_kept_today,
+            rests_ended: self.rests_ended_today,
+            work_prayers: self.work_prayers_today,
+        })
+    }
+
+    /// Returns the records of the days from `first` to `last` with any
+    /// activity, including the current day, oldest first.
+    pub fn days_between(&self, first: NaiveDate, last: NaiveDate) -> Vec<DayRecord> {
+        self.day_history
+            .iter()
+            .cloned()
+            .chain(self.today())
+            .filter(|record| record.is_active() && (first..=last).contains(&record.date))
+            .collect()
+    }
+
+    /// Returns the counters with any inconsistencies corrected.
+    ///
+    /// Planned completions can't exceed today's, and today's can't exceed the
+    /// week's; a damaged file is clamped rather than discarded.
+    fn repaired(mut self) -> Self {
+        self.completed_today = self.completed_today.min(self.completed_this_week);
+        self.completed_planned_today = self.completed_planned_today.min(self.completed_today);
+        self.rests_kept_today = self.rests_kept_today.min(self.rests_ended_today);
+        self
+    }
+
+    /// Records a completed pomodoro at `now`.
+    ///
+    /// `planned` indicates whether the block had a label in today's plan.
+    /// Returns the sprint's report if this completion finished the active sprint.
+    pub fn record_completed(&mut self, now: NaiveDateTime, planned: bool) -> Option<Sprint> {
+        self.roll_over(now.date());
+        self.completed_today += 1;
+        self.completed_this_week += 1;
+        if planned {
+            self.completed_planned_today += 1;
+        }
+
+        let today = now.date();
+        if self.last_active_day != Some(today) {
+            let continues = self.last_active_day == Some(today - Duration::days(1));
+            self.streak_days = if continues { self.streak_days + 1 } else { 1 };
+            self.last_active_day = Some(today);
+        }
+
+        let sprint = self.sprint.as_mut()?;
+        sprint.completed += 1;
+        if sprint.completed >= sprint.target {
+            self.end_sprint(now)
+        } else {
+            None
+        }
+    }
+
+    /// Records a work period cut short against the active sprint.
+    pub fn record_skipped(&mut self) {
+        if let Some(sprint) = self.sprint.as_mut() {
+            sprint.skipped += 1;
+        }
+    }
+
+    /// Records the end of a rest period on `today`.
+    ///
+    /// `kept` indicates whether it was prayed through to the end rather than skipped.
+    pub fn record_rest_ended(&mut self, today: NaiveDate, kept: bool) {
+        self.roll_over(today);
+        self.rests_ended_today += 1;
+        if kept {
+            self.rests_kept_today += 1;
+            if let Some(sprint) = self.sprint.as_mut() {
+                sprint.prayers_kept += 1;
+            }
+        }
+    }
+
+    /// Records a prayer before work offered on `today`.
+    pub fn record_work_prayer(&mut self, today: NaiveDate) {
+        self.roll_over(today);
+        self.work_prayers_today += 1;
+    }
+
+    /// Returns the number of consecutive days with a completed pomodoro, up to `today`.
+    ///
+    /// A streak stays alive through today until midnight even if nothing has
+    /// been completed yet today.
+    pub fn current_streak(&self, today: NaiveDate) -> u32 {
+        match self.last_active_day {
+            Some(day) if day == today || day == today - Duration::days(1) => self.streak_days,
+            _ => 0,
+        }
+    }
+
+    /// Returns a snapshot of the counters as of `today`.
+    pub fn summary(&self, today: NaiveDate) -> StatsSummary {
+        let mut stats = self.clone();
+        stats.roll_over(today);
+        StatsSummary {
+            completed_today: stats.completed_today,
+            completed_this_week: stats.completed_this_week,
+            completed_planned_today: stats.completed_planned_today,
+            rest_adherence_percent: (stats.rests_ended_today > 0)
+                .then(|| stats.rests_kept_today * 100 / stats.rests_ended_today),
+            work_prayers_today: stats.work_prayers_today,
+            current_streak_days: stats.current_streak(today),
+        }
+    }
+
+    /// Starts a new sprint at `now`, ending any sprint already in progress.
+    pub fn start_sprint(&mut self, goal: String, target: u32, now: NaiveDateTime) {
+        self.end_sprint(now);
+        self.sprint = Some(Sprint::new(goal, target, now));
+    }
+
+    /// Ends the active sprint at `now` and files its report.
+    ///
+    /// Returns the report, or `None` if no sprint was in progress.
+    pub fn end_sprint(&mut self, now: NaiveDateTime) -> Option<Sprint> {
+        let mut sprint = self.sprint.take()?;
+        sprint.finished = Some(now);
+        self.sprint_history.push(sprint.clone());
+        if self.sprint_history.len() > SPRINT_HISTORY_LIMIT {
+            let excess = self.sprint_history.len() - SPRINT_HISTORY_LIMIT;
+            self.sprint_history.drain(..excess);
+        }
+        Some(sprint)
+    }
+}
+
+/// Returns the Monday of the week containing `date`.
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// Loads stats from storage, recovering from an interrupted write if needed.
+///
+/// Counters are checked for consistency before being returned. Returns empty
+/// counters if nothing usable is found.
+pub fn load_stats() -> PomodoroStats {
+    storage::load::<PomodoroStats>("stats.json")
+        .map(PomodoroStats::repaired)
+        .unwrap_or_default()
+}
+
+/// Queues stats to be saved; failures are retried and reported by [`storage::save`].
+pub fn save_stats(stats: &PomodoroStats) {
+    storage::save("stats.json", stats);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Days;
+
+    fn day(day: u32) -> NaiveDate {
+        // 2026-10-12 is a Monday
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    fn complete_on(stats: &mut PomodoroStats, date: NaiveDate) {
+        stats.record_completed(date.and_hms_opt(9, 25, 0).unwrap(), false);
+    }
+
+    #[test]
+    fn skipped_day_breaks_the_streak() {
+        let mut stats = PomodoroStats::default();
+        complete_on(&mut stats, day(12));
+        complete_on(&mut stats, day(13));
+        assert_eq!(stats.current_streak(day(13)), 2);
+
+        complete_on(&mut stats, day(15));
+        assert_eq!(stats.current_streak(day(15)), 1);
+    }
+
+    #[test]
+    fn streak_stays_alive_through_today() {
+        let mut stats = PomodoroStats::default();
+        complete_on(&mut stats, day(12));
+        complete_on(&mut stats, day(13));
+
+        assert_eq!(stats.current_streak(day(14)), 2);
+        assert_eq!(stats.summary(day(14)).current_streak_days, 2);
+        assert_eq!(stats.current_streak(day(15)), 0);
+    }
+
+    #[test]
+    fn week_rolls_over_on_monday() {
+        let mut stats = PomodoroStats::default();
+        complete_on(&mut stats, day(17));
+        complete_on(&mut stats, day(18));
+        assert_eq!(stats.completed_this_week, 2);
+        assert_eq!(stats.completed_today, 1);
+
+        assert!(stats.roll_over(day(19)));
+        assert_eq!(stats.week_start, Some(day(19)));
+        assert_eq!(stats.completed_this_week, 0);
+        assert!(!stats.roll_over(day(19)));
+    }
+
+    #[test]
+    fn day_history_keeps_the_most_recent_days() {
+        let mut stats = PomodoroStats::default();
+        let first = day(12);
+        let days = DAY_HISTORY_LIMIT as u64 + 5;
+        for offset in 0..days {
+            complete_on(&mut stats, first + Days::new(offset));
+        }
+        stats.roll_over(first + Days::new(days));
+
+        assert_eq!(stats.day_history.len(), DAY_HISTORY_LIMIT);
+        assert_eq!(stats.day_history[0].date, first + Days::new(5));
+        assert_eq!(
+            stats.day_history.last().unwrap().date,
+            first + Days::new(days - 1)
+        );
+    }
+
+    #[test]
+    fn adherence_needs_an_ended_rest() {
+        let mut stats = PomodoroStats::default();
+        stats.roll_over(day(12));
+        assert_eq!(stats.summary(day(12)).rest_adherence_percent, None);
+
+        stats.record_rest_ended(day(12), true);
+        stats.record_rest_ended(day(12), false);
+        assert_eq!(stats.summary(day(12)).rest_adherence_percent, Some(50));
+    }
+
+    #[test]
+    fn damaged_counters_are_clamped() {
+        let stats = PomodoroStats {
+            completed_today: 9,
+            completed_this_week: 4,
+            completed_planned_today: 7,
+            rests_kept_today: 5,
+            rests_ended_today: 3,
+            ..Default::default()
+        }
+        .repaired();
+
+        assert_eq!(stats.completed_today, 4);
+        assert_eq!(stats.completed_planned_today, 4);
+        assert_eq!(stats.rests_kept_today, 3);
+    }
+}
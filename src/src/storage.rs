@@ -0,0 +1,457 @@
+//! Pluggable persistence for settings, stats, and the day plan.
+//!
+//! Everything the app saves is a named JSON document ("settings.json",
+//! "stats.json", ...) kept by a [`Storage`] backend. The default backend stores
+//! files in the platform config directory; `--data-dir` points it somewhere
+//! else for portable installs, and [`MemoryStorage`] keeps documents in memory
+//! when there's nowhere to write them. Business logic only calls [`load`] and
+//! [`save`], so backends can be swapped without touching it.
+//!
+//! Writes happen on a single persistence thread, so a locked file or a full
+//! disk never holds up the UI or the timer. [`save`] only queues a snapshot;
+//! [`flush`] waits for the queue to drain before the app exits.
+
+use crate::notifications::notify_save_failed;
+use crate::settings::{config_dir, load_settings};
+use parking_lot::Mutex;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Sender};
+use std::sync::OnceLock;
+use std::thread;
+use std::time::Duration;
+
+/// A place to keep named documents.
+pub trait Storage: Send + Sync {
+    /// Returns the current contents of `name`, or `None` if it doesn't exist.
+    fn read(&self, name: &str) -> Option<String>;
+
+    /// Replaces the contents of `name`.
+    fn write(&self, name: &str, contents: &str) -> io::Result<()>;
+
+    /// Returns copies of `name` left behind by interrupted writes, best first.
+    ///
+    /// Used to recover when the current contents are missing or unreadable.
+    fn recovery_copies(&self, _name: &str) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Returns the directory documents are kept in, if they're kept on disk.
+    fn location(&self) -> Option<&Path> {
+        None
+    }
+}
+
+/// Stores each document as a file in a directory.
+///
+/// Writes go through a synced temp file and keep the previous file as a
+/// backup, so a hard kill at any point leaves at least one intact copy.
+pub struct FileStorage {
+    dir: PathBuf,
+}
+
+impl FileStorage {
+    /// Creates a backend storing files in `dir`, which is created on first write.
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Returns the path of the in-progress write next to `path`.
+    fn temp_path(path: &Path) -> PathBuf {
+        path.with_extension("json.tmp")
+    }
+
+    /// Returns the path of the previous good copy next to `path`.
+    fn backup_path(path: &Path) -> PathBuf {
+        path.with_extension("json.bak")
+    }
+}
+
+impl Storage for FileStorage {
+    fn read(&self, name: &str) -> Option<String> {
+        fs::read_to_string(self.dir.join(name)).ok()
+    }
+
+    fn write(&self, name: &str, contents: &str) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.dir.join(name);
+        let temp = Self::temp_path(&path);
+        {
+            let mut file = fs::File::create(&temp)?;
+            file.write_all(contents.as_bytes())?;
+            file.sync_all()?;
+        }
+
+        if path.exists() {
+            fs::rename(&path, Self::backup_path(&path))?;
+        }
+        fs::rename(&temp, &path)
+    }
+
+    fn recovery_copies(&self, name: &str) -> Vec<String> {
+        // A fully written temp file is newer than the backup
+        let path = self.dir.join(name);
+        [Self::temp_path(&path), Self::backup_path(&path)]
+            .iter()
+            .filter_map(|candidate| fs::read_to_string(candidate).ok())
+            .collect()
+    }
+
+    fn location(&self) -> Option<&Path> {
+        Some(&self.dir)
+    }
+}
+
+/// Keeps documents in memory for the life of the process.
+#[derive(Default)]
+pub struct MemoryStorage {
+    documents: Mutex<HashMap<String, String>>,
+}
+
+impl Storage for MemoryStorage {
+    fn read(&self, name: &str) -> Option<String> {
+        self.documents.lock().get(name).cloned()
+    }
+
+    fn write(&self, name: &str, contents: &str) -> io::Result<()> {
+        self.documents
+            .lock()
+            .insert(name.to_string(), contents.to_string());
+        Ok(())
+    }
+}
+
+/// Times a write is attempted before the save is reported as failed.
+const WRITE_ATTEMPTS: u32 = 3;
+
+/// Pause between write attempts, long enough for a short-lived lock (a
+/// backup tool or virus scanner opening the file) to be released.
+const WRITE_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+/// Documents whose last save failed, so each failure is only alerted once.
+static FAILING: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Latest contents of each document queued for writing but not yet written.
+///
+/// Loads read from here first, so a save is visible at once, and a burst of
+/// saves of one document ends up as a single write of the newest snapshot.
+static PENDING: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+
+/// Queue of the persistence thread, started on first use.
+static WRITER: OnceLock<Sender<Job>> = OnceLock::new();
+
+/// Work for the persistence thread.
+enum Job {
+    /// Write the pending contents of the named document.
+    Write(String),
+    /// Signal the sender once everything queued before has been written.
+    Flush(Sender<()>),
+}
+
+/// Returns the contents queued for each document.
+fn pending() -> &'static Mutex<HashMap<String, String>> {
+    PENDING.get_or_init(Default::default)
+}
+
+/// Returns the queue of the persistence thread, starting it if needed.
+fn writer() -> &'static Sender<Job> {
+    WRITER.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for job in receiver {
+                match job {
+                    Job::Write(name) => write_pending(&name),
+                    Job::Flush(done) => {
+                        let _ = done.send(());
+                    }
+                }
+            }
+        });
+        sender
+    })
+}
+
+/// The backend in use, chosen on first use unless [`install`] was called.
+static BACKEND: OnceLock<Box<dyn Storage>> = OnceLock::new();
+
+/// Returns the backend, defaulting to files in the platform config directory.
+fn backend() -> &'static dyn Storage {
+    BACKEND
+        .get_or_init(|| match config_dir() {
+            Some(dir) => Box::new(FileStorage::new(dir)),
+            None => Box::new(MemoryStorage::default()),
+        })
+        .as_ref()
+}
+
+/// Installs the backend to use for the rest of the process.
+///
+/// Must be called before anything is loaded or saved. Returns `false` if a
+/// backend was already in use.
+pub fn install(storage: Box<dyn Storage>) -> bool {
+    BACKEND.set(storage).is_ok()
+}
+
+/// Loads and parses the document `name`.
+///
+/// Falls back to the backend's recovery copies if the current contents are
+/// missing or corrupt; a recovered copy is written back as the current one.
+/// Returns `None` if nothing usable is found.
+pub fn load<T: DeserializeOwned>(name: &str) -> Option<T> {
+    let queued = pending().lock().get(name).cloned();
+    if let Some(value) = queued.and_then(|contents| serde_json::from_str(&contents).ok()) {
+        return Some(value);
+    }
+    load_from(backend(), name)
+}
+
+/// Loads and parses the document `name` from `storage`, as [`load`] does.
+fn load_from<T: DeserializeOwned>(storage: &dyn Storage, name: &str) -> Option<T> {
+    if let Some(value) = storage
+        .read(name)
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+    {
+        return Some(value);
+    }
+
+    for contents in storage.recovery_copies(name) {
+        if let Ok(value) = serde_json::from_str(&contents) {
+            let _ = storage.write(name, &contents);
+            return Some(value);
+        }
+    }
+
+    None
+}
+
+/// Returns the directory the backend keeps documents in, if it uses one.
+pub fn location() -> Option<PathBuf> {
+    backend().location().map(Path::to_path_buf)
+}
+
+/// Queues `value` to be saved as the document `name`, returning at once.
+///
+/// The persistence thread retries a failed write a few times. If it still
+/// fails, the error is logged and the user is alerted, once until a save of
+/// that document works again. Each save writes the whole document, so nothing
+/// is lost once the problem clears.
+pub fn save<T: Serialize>(name: &str, value: &T) {
+    let json = match serde_json::to_string_pretty(value) {
+        Ok(json) => json,
+        Err(error) => {
+            eprintln!("Couldn't save {}: {}", name, error);
+            return;
+        }
+    };
+    pending().lock().insert(name.to_string(), json);
+    let _ = writer().send(Job::Write(name.to_string()));
+}
+
+/// Waits until every save queued so far has been written or has failed.
+pub fn flush() {
+    let (done, finished) = mpsc::channel();
+    if writer().send(Job::Flush(done)).is_ok() {
+        let _ = finished.recv();
+    }
+}
+
+/// Writes the queued contents of `name`, on the persistence thread.
+fn write_pending(name: &str) {
+    // Already written along with an earlier job for the same document
+    let Some(contents) = pending().lock().get(name).cloned() else {
+        return;
+    };
+    let result = write_with_retries(backend(), name, &contents);
+    {
+        let mut queued = pending().lock();
+        // A newer snapshot queued meanwhile has its own job coming
+        if queued.get(name) == Some(&contents) {
+            queued.remove(name);
+        }
+    }
+
+    let mut failing = FAILING.lock();
+    match result {
+        Ok(()) => failing.retain(|failed| failed != name),
+        Err(error) => {
+            eprintln!("Couldn't save {}: {}", name, error);
+            if !failing.iter().any(|failed| failed == name) {
+                failing.push(name.to_string());
+                let settings = load_settings().notifications;
+                notify_save_failed(name, &error, &settings);
+            }
+        }
+    }
+}
+
+/// Writes `contents` to `name`, retrying up to [`WRITE_ATTEMPTS`] times.
+fn write_with_retries(storage: &dyn Storage, name: &str, contents: &str) -> io::Result<()> {
+    let mut attempt = 1;
+    loop {
+        match storage.write(name, contents) {
+            Err(_) if attempt < WRITE_ATTEMPTS => {
+                thread::sleep(WRITE_RETRY_DELAY);
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::Settings;
+
+    /// Writes `value` as the document `name` in `storage`, as the persistence
+    /// thread does.
+    fn save_to<T: Serialize>(storage: &dyn Storage, name: &str, value: &T) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(value)?;
+        write_with_retries(storage, name, &json)
+    }
+
+    /// A directory under the system temp dir, removed when dropped.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!(
+                "praymodoro-storage-{}-{}",
+                name,
+                std::process::id()
+            ));
+            let _ = fs::remove_dir_all(&dir);
+            Self(dir)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn with_character(character: &str) -> Settings {
+        Settings {
+            character: character.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn memory_storage_round_trips() {
+        let storage = MemoryStorage::default();
+        let settings = with_character("thomas-more");
+
+        save_to(&storage, "settings.json", &settings).unwrap();
+        let loaded: Option<Settings> = load_from(&storage, "settings.json");
+
+        assert_eq!(loaded, Some(settings));
+    }
+
+    #[test]
+    fn file_storage_recovers_a_corrupt_file_from_its_backup() {
+        let dir = TempDir::new("recover");
+        let storage = FileStorage::new(dir.0.clone());
+        let first = with_character("saint-patrick");
+        let second = with_character("thomas-aquinas");
+
+        // The second save keeps the first as the backup
+        save_to(&storage, "settings.json", &first).unwrap();
+        save_to(&storage, "settings.json", &second).unwrap();
+        fs::write(dir.0.join("settings.json"), "{ \"character\": ").unwrap();
+
+        let loaded: Option<Settings> = load_from(&storage, "settings.json");
+        assert_eq!(loaded, Some(first.clone()));
+
+        // The recovered copy is written back as the current file
+        let repaired: Settings =
+            serde_json::from_str(&fs::read_to_string(dir.0.join("settings.json")).unwrap())
+                .unwrap();
+        assert_eq!(repaired, first);
+    }
+
+    #[test]
+    fn file_storage_prefers_a_finished_temp_file_over_the_backup() {
+        let dir = TempDir::new("temp");
+        let storage = FileStorage::new(dir.0.clone());
+        let backup = with_character("saint-patrick");
+        let pending = with_character("thomas-more");
+
+        // Killed after the temp file was synced but before it was renamed
+        save_to(&storage, "settings.json", &backup).unwrap();
+        save_to(&storage, "settings.json", &backup).unwrap();
+        fs::remove_file(dir.0.join("settings.json")).unwrap();
+        fs::write(
+            dir.0.join("settings.json.tmp"),
+            serde_json::to_string(&pending).unwrap(),
+        )
+        .unwrap();
+
+        let loaded: Option<Settings> = load_from(&storage, "settings.json");
+        assert_eq!(loaded, Some(pending));
+    }
+
+    #[test]
+    fn missing_file_falls_back_to_defaults() {
+        let dir = TempDir::new("missing");
+        let storage = FileStorage::new(dir.0.clone());
+
+        let loaded: Option<Settings> = load_from(&storage, "settings.json");
+        assert_eq!(loaded, None);
+        assert_eq!(loaded.unwrap_or_default(), Settings::default());
+    }
+
+    /// Fails the first `failures` writes, then stores documents in memory.
+    struct FlakyStorage {
+        failures: Mutex<u32>,
+        inner: MemoryStorage,
+    }
+
+    impl FlakyStorage {
+        fn new(failures: u32) -> Self {
+            Self {
+                failures: Mutex::new(failures),
+                inner: MemoryStorage::default(),
+            }
+        }
+    }
+
+    impl Storage for FlakyStorage {
+        fn read(&self, name: &str) -> Option<String> {
+            self.inner.read(name)
+        }
+
+        fn write(&self, name: &str, contents: &str) -> io::Result<()> {
+            let mut failures = self.failures.lock();
+            if *failures > 0 {
+                *failures -= 1;
+                return Err(io::Error::other("file is locked"));
+            }
+            self.inner.write(name, contents)
+        }
+    }
+
+    #[test]
+    fn failed_writes_are_retried() {
+        let storage = FlakyStorage::new(WRITE_ATTEMPTS - 1);
+        let settings = with_character("thomas-more");
+
+        save_to(&storage, "settings.json", &settings).unwrap();
+        let loaded: Option<Settings> = load_from(&storage, "settings.json");
+        assert_eq!(loaded, Some(settings));
+    }
+
+    #[test]
+    fn writes_failing_every_attempt_report_the_error() {
+        let storage = FlakyStorage::new(WRITE_ATTEMPTS);
+
+        let result = save_to(&storage, "settings.json", &Settings::default());
+        assert!(result.is_err());
+        assert_eq!(storage.read("settings.json"), None);
+    }
+}
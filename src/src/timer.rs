@@ -10,27 +10,118 @@
 //! - **25:00-30:00** - Rest/Prayer (5 minutes)
 //! - **30:00-55:00** - Work (25 minutes)
 //! - **55:00-60:00** - Rest/Prayer (5 minutes)
+//!
+//! An alternate [`ScheduleKind::LiturgyOfTheHours`] schedule anchors short
+//! prayer periods to the canonical hours instead, with work in between, and a
+//! [`ScheduleKind::Family`] schedule splits each hour into three 15/5 periods
+//! for children. A [`ScheduleKind::Custom`] schedule repeats the user's own
+//! division of the hour, checked by [`check_segments`].
+//!
+//! Either schedule can be swapped at runtime for a [`TimerEngine::FreeRunning`]
+//! session that cycles 25/5 from whenever it was started, ignoring the clock.
+//! Pausing, skipping, or restarting a clock-aligned period hands it over to a
+//! free-running session until that period ends; the clock schedule then takes
+//! over again.
 
-use crate::state::{AppState, PomodoroMode};
-use chrono::{Local, Timelike};
+use crate::events::{load_events, save_events, EventKind, EventLog, TimerEvent};
+use crate::format;
+use crate::notifications::{
+    notify_minute_left, notify_period_change, notify_sabbath_reminder, notify_schedule_changed,
+    notify_sprint_finished, update_period_progress,
+};
+use crate::sabbath;
+use crate::state::{AppState, PomodoroMode, RosaryProgress};
+use crate::stats::save_stats;
+use chrono::{Local, NaiveDate, NaiveDateTime, NaiveTime, SubsecRound, TimeDelta, Timelike};
+use clap::ValueEnum;
 use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+/// The schedule the timer follows.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema, ValueEnum,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum ScheduleKind {
+    /// The fixed hourly 30/5/25/5 Pomodoro schedule.
+    #[default]
+    Hourly,
+    /// Prayer anchored to Lauds, Sext, Vespers, and Compline, with work in between.
+    LiturgyOfTheHours,
+    /// The shorter hourly 15/5 schedule for children.
+    Family,
+    /// The user's own division of the hour, kept in the settings.
+    Custom,
+}
+
+impl ScheduleKind {
+    /// All schedules, in the order they appear in the tray menu.
+    pub const ALL: &'static [ScheduleKind] = &[
+        ScheduleKind::Hourly,
+        ScheduleKind::LiturgyOfTheHours,
+        ScheduleKind::Family,
+        ScheduleKind::Custom,
+    ];
+
+    /// Returns the human-readable name of the schedule.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ScheduleKind::Hourly => "Hourly (25/5)",
+            ScheduleKind::LiturgyOfTheHours => "Liturgy of the Hours",
+            ScheduleKind::Family => "Family (15/5)",
+            ScheduleKind::Custom => "Custom",
+        }
+    }
+}
+
+/// How the timer decides where periods begin and end.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum TimerEngine {
+    /// Periods follow the active [`ScheduleKind`], synchronized with the system clock.
+    #[default]
+    ClockAligned,
+    /// Periods cycle 25/5 from the moment the session started.
+    FreeRunning,
+}
+
+impl TimerEngine {
+    /// All engines, in the order they appear in the tray menu.
+    pub const ALL: &'static [TimerEngine] = &[TimerEngine::ClockAligned, TimerEngine::FreeRunning];
+
+    /// Returns the human-readable name of the engine.
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimerEngine::ClockAligned => "Clock-Aligned",
+            TimerEngine::FreeRunning => "Free-Running",
+        }
+    }
+}
+
+/// Length of a free-running work period in seconds.
+const FREE_RUNNING_WORK_SECONDS: i32 = 25 * 60;
+
+/// Length of a free-running rest period in seconds.
+const FREE_RUNNING_REST_SECONDS: i32 = 5 * 60;
 
 /// Represents a time period within the Pomodoro schedule.
-struct PomodoroSegment {
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct PomodoroSegment {
     /// Start minute within the hour (0-59).
-    start_minute: u32,
+    pub start_minute: u32,
     /// End minute within the hour (0-60, where 60 = start of next hour).
-    end_minute: u32,
+    pub end_minute: u32,
     /// Mode for this time period (Work or Rest).
-    mode: PomodoroMode,
+    pub mode: PomodoroMode,
 }
 
 /// The fixed hourly Pomodoro schedule.
 ///
 /// These segments repeat every hour, synchronized with the system clock.
-const POMODORO_SEGMENTS: &[PomodoroSegment] = &[
+pub const POMODORO_SEGMENTS: &[PomodoroSegment] = &[
     PomodoroSegment {
         start_minute: 0,
         end_minute: 25,
@@ -53,45 +144,615 @@ const POMODORO_SEGMENTS: &[PomodoroSegment] = &[
     },
 ];
 
-/// Determines the current Pomodoro period based on system time.
+/// The hourly [`ScheduleKind::Family`] schedule of three 15/5 periods.
+const FAMILY_SEGMENTS: &[PomodoroSegment] = &[
+    PomodoroSegment {
+        start_minute: 0,
+        end_minute: 15,
+        mode: PomodoroMode::Work,
+    },
+    PomodoroSegment {
+        start_minute: 15,
+        end_minute: 20,
+        mode: PomodoroMode::Rest,
+    },
+    PomodoroSegment {
+        start_minute: 20,
+        end_minute: 35,
+        mode: PomodoroMode::Work,
+    },
+    PomodoroSegment {
+        start_minute: 35,
+        end_minute: 40,
+        mode: PomodoroMode::Rest,
+    },
+    PomodoroSegment {
+        start_minute: 40,
+        end_minute: 55,
+        mode: PomodoroMode::Work,
+    },
+    PomodoroSegment {
+        start_minute: 55,
+        end_minute: 60,
+        mode: PomodoroMode::Rest,
+    },
+];
+
+/// A prayer anchor in the [`ScheduleKind::LiturgyOfTheHours`] schedule.
+struct CanonicalHour {
+    /// Hour of the day the prayer begins (0-23).
+    hour: u32,
+    /// Length of the prayer period in minutes.
+    duration_minutes: u32,
+}
+
+/// The canonical hours used as prayer anchors, in chronological order.
+///
+/// Lauds (6:00), Sext (12:00), Vespers (18:00), and Compline (21:00).
+const CANONICAL_HOURS: &[CanonicalHour] = &[
+    CanonicalHour {
+        hour: 6,
+        duration_minutes: 15,
+    },
+    CanonicalHour {
+        hour: 12,
+        duration_minutes: 15,
+    },
+    CanonicalHour {
+        hour: 18,
+        duration_minutes: 15,
+    },
+    CanonicalHour {
+        hour: 21,
+        duration_minutes: 15,
+    },
+];
+
+/// Seconds in a day, used to wrap from Compline to the next morning's Lauds.
+const SECONDS_PER_DAY: i32 = 24 * 60 * 60;
+
+/// Seconds before the end of a period that the minute warning is shown.
+const MINUTE_WARNING_SECONDS: i32 = 60;
+
+/// The period the timer is currently in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Period {
+    /// Mode for this period (Work or Rest).
+    mode: PomodoroMode,
+    /// Seconds remaining until the period ends.
+    remaining: i32,
+    /// Total length of the period in seconds.
+    duration: i32,
+}
+
+/// Checks that `segments` divide the hour into alternating work and rest.
 ///
-/// Returns the current mode (Work/Rest) and remaining seconds in that period.
-fn get_current_period() -> (PomodoroMode, i32) {
-    let now = Local::now();
+/// The segments must be in order and cover the hour from `:00` to `:60`
+/// without gaps or overlaps.
+pub fn check_segments(segments: &[PomodoroSegment]) -> Result<(), String> {
+    let mut end = 0;
+    let mut last_mode = None;
+    for segment in segments {
+        if segment.start_minute < end {
+            return Err(format!(
+                "the period starting at :{:02} overlaps the one ending at :{:02}",
+                segment.start_minute, end
+            ));
+        }
+        if segment.start_minute > end {
+            return Err(format!(
+                "nothing is scheduled from :{:02} to :{:02}",
+                end, segment.start_minute
+            ));
+        }
+        if segment.end_minute <= segment.start_minute {
+            return Err(format!(
+                "the period starting at :{:02} ends before it starts",
+                segment.start_minute
+            ));
+        }
+        if last_mode == Some(segment.mode) {
+            return Err(format!(
+                "the period starting at :{:02} has the same mode as the one before it",
+                segment.start_minute
+            ));
+        }
+        end = segment.end_minute;
+        last_mode = Some(segment.mode);
+    }
+    if end != 60 {
+        return Err(format!(
+            "the schedule ends at :{:02} instead of the top of the hour",
+            end
+        ));
+    }
+    Ok(())
+}
+
+/// Determines the period of `schedule` at local time `now`.
+///
+/// `custom` is the division of the hour used by [`ScheduleKind::Custom`]; if it
+/// doesn't pass [`check_segments`], the hourly schedule is followed instead.
+fn get_current_period(
+    schedule: ScheduleKind,
+    custom: &[PomodoroSegment],
+    now: NaiveTime,
+) -> Period {
+    match schedule {
+        ScheduleKind::Hourly => get_hourly_period(now),
+        ScheduleKind::LiturgyOfTheHours => get_liturgy_period(now),
+        ScheduleKind::Family => get_segment_period(FAMILY_SEGMENTS, now),
+        ScheduleKind::Custom if check_segments(custom).is_ok() => get_segment_period(custom, now),
+        ScheduleKind::Custom => get_hourly_period(now),
+    }
+}
+
+/// Determines the period within the hourly Pomodoro schedule at `now`.
+fn get_hourly_period(now: NaiveTime) -> Period {
+    get_segment_period(POMODORO_SEGMENTS, now)
+}
+
+/// Determines the period within an hour divided into `segments` at `now`.
+fn get_segment_period(segments: &[PomodoroSegment], now: NaiveTime) -> Period {
     let minutes = now.minute();
     let seconds = now.second();
 
-    let segment = POMODORO_SEGMENTS
+    let segment = segments
         .iter()
         .find(|s| minutes >= s.start_minute && minutes < s.end_minute)
-        .unwrap_or(&POMODORO_SEGMENTS[0]);
+        .unwrap_or(&segments[0]);
 
     let current_second = (minutes * 60 + seconds) as i32;
     let end_second = (segment.end_minute * 60) as i32;
     let remaining = end_second - current_second;
 
-    (segment.mode, remaining)
+    Period {
+        mode: segment.mode,
+        remaining,
+        duration: ((segment.end_minute - segment.start_minute) * 60) as i32,
+    }
+}
+
+/// Determines the period within the Liturgy of the Hours schedule at `now`.
+///
+/// Prayer runs for the length of each canonical hour anchor; everything else
+/// is work, counting down to the next anchor (wrapping past midnight).
+fn get_liturgy_period(now: NaiveTime) -> Period {
+    let current_second = (now.hour() * 3600 + now.minute() * 60 + now.second()) as i32;
+
+    let last = &CANONICAL_HOURS[CANONICAL_HOURS.len() - 1];
+    let mut previous_end = (last.hour * 3600 + last.duration_minutes * 60) as i32 - SECONDS_PER_DAY;
+
+    for anchor in CANONICAL_HOURS {
+        let start = (anchor.hour * 3600) as i32;
+        let end = start + (anchor.duration_minutes * 60) as i32;
+        if current_second < start {
+            return Period {
+                mode: PomodoroMode::Work,
+                remaining: start - current_second,
+                duration: start - previous_end,
+            };
+        }
+        if current_second < end {
+            return Period {
+                mode: PomodoroMode::Rest,
+                remaining: end - current_second,
+                duration: end - start,
+            };
+        }
+        previous_end = end;
+    }
+
+    let next_start = (CANONICAL_HOURS[0].hour * 3600) as i32 + SECONDS_PER_DAY;
+    Period {
+        mode: PomodoroMode::Work,
+        remaining: next_start - current_second,
+        duration: next_start - previous_end,
+    }
+}
+
+/// A newly chosen schedule taking over from the previous one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScheduleChange {
+    /// Schedule followed until now.
+    pub from: ScheduleKind,
+    /// Schedule followed from now on.
+    pub to: ScheduleKind,
 }
 
-/// Formats seconds into MM:SS display format.
+/// One second of the clock-aligned engine.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct ClockTick {
+    /// The current period.
+    period: Period,
+    /// Schedule the current period belongs to.
+    schedule: ScheduleKind,
+    /// Set on the tick a newly chosen schedule takes over.
+    change: Option<ScheduleChange>,
+}
+
+/// Determines the clock-aligned period at local time `now`.
 ///
-/// # Examples
+/// `active` is the schedule the last period belonged to (`None` on the first
+/// tick), `requested` the schedule in the settings, `custom` the custom
+/// schedule's segments, and `last` the period shown on the previous tick.
 ///
-/// ```
-/// assert_eq!(format_time(90), "01:30");
-/// assert_eq!(format_time(3661), "61:01");
-/// ```
-fn format_time(seconds: i32) -> String {
-    let mins = seconds / 60;
-    let secs = seconds % 60;
-    format!("{:02}:{:02}", mins, secs)
+/// A schedule switched mid-period doesn't cut the period short: the old
+/// schedule is followed until its current segment ends, and `requested` takes
+/// over from that boundary, reported as a [`ScheduleChange`].
+fn clock_tick(
+    active: Option<ScheduleKind>,
+    requested: ScheduleKind,
+    custom: &[PomodoroSegment],
+    last: Option<Period>,
+    now: NaiveTime,
+) -> ClockTick {
+    let current = active.unwrap_or(requested);
+    let period = get_current_period(current, custom, now);
+    let boundary = last.is_some_and(|last| last.mode != period.mode);
+    if current != requested && boundary {
+        ClockTick {
+            period: get_current_period(requested, custom, now),
+            schedule: requested,
+            change: Some(ScheduleChange {
+                from: current,
+                to: requested,
+            }),
+        }
+    } else {
+        ClockTick {
+            period,
+            schedule: current,
+            change: None,
+        }
+    }
+}
+
+/// A free-running session that cycles work and rest independently of the clock.
+struct FreeRunningSession {
+    /// Mode of the current period.
+    mode: PomodoroMode,
+    /// Length of the current period in seconds.
+    duration: i32,
+    /// When the current period ends.
+    ends_at: Instant,
+    /// Time left in the current period while paused.
+    paused_remaining: Option<Duration>,
+}
+
+impl FreeRunningSession {
+    /// Starts a session that picks up exactly where `period` left off.
+    ///
+    /// Switching engines mid-period keeps the current mode and remaining time,
+    /// so the countdown doesn't jump; only later periods are free-running.
+    fn continuing(period: &Period, now: Instant) -> Self {
+        Self {
+            mode: period.mode,
+            duration: period.duration,
+            ends_at: now + Duration::from_secs(period.remaining.max(0) as u64),
+            paused_remaining: None,
+        }
+    }
+
+    /// Switches to the next period, which starts at `start`.
+    fn advance(&mut self, start: Instant) {
+        let (mode, duration) = match self.mode {
+            PomodoroMode::Work => (PomodoroMode::Rest, FREE_RUNNING_REST_SECONDS),
+            PomodoroMode::Rest => (PomodoroMode::Work, FREE_RUNNING_WORK_SECONDS),
+        };
+        self.mode = mode;
+        self.duration = duration;
+        self.ends_at = start + Duration::from_secs(duration as u64);
+    }
+
+    /// Pauses or resumes the countdown. Repeated calls with the same value are no-ops.
+    fn set_paused(&mut self, paused: bool, now: Instant) {
+        match (paused, self.paused_remaining) {
+            (true, None) => {
+                self.paused_remaining = Some(self.ends_at.saturating_duration_since(now));
+            }
+            (false, Some(remaining)) => {
+                self.ends_at = now + remaining;
+                self.paused_remaining = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Ends the current period immediately and starts the next one.
+    fn skip(&mut self, now: Instant) {
+        self.advance(now);
+        if self.paused_remaining.is_some() {
+            self.paused_remaining = Some(self.ends_at - now);
+        }
+    }
+
+    /// Starts the current period over from its full length.
+    fn restart(&mut self, now: Instant) {
+        self.ends_at = now + Duration::from_secs(self.duration as u64);
+        if self.paused_remaining.is_some() {
+            self.paused_remaining = Some(self.ends_at - now);
+        }
+    }
+
+    /// Returns the current period, or `None` once it has run out.
+    ///
+    /// Used for a handover from the clock, which lasts only until the period
+    /// it was handed ends.
+    fn current_period_until_end(&mut self, now: Instant) -> Option<Period> {
+        if self.paused_remaining.is_none() && now >= self.ends_at {
+            None
+        } else {
+            Some(self.current_period(now))
+        }
+    }
+
+    /// Returns the current period, advancing through any periods that have ended.
+    fn current_period(&mut self, now: Instant) -> Period {
+        let remaining = match self.paused_remaining {
+            Some(remaining) => remaining,
+            None => {
+                while now >= self.ends_at {
+                    self.advance(self.ends_at);
+                }
+                self.ends_at - now
+            }
+        };
+
+        Period {
+            mode: self.mode,
+            remaining: remaining.as_secs_f32().ceil() as i32,
+            duration: self.duration,
+        }
+    }
+}
+
+/// What the user has asked of the timer on one tick.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Controls {
+    /// Schedule in the settings.
+    schedule: ScheduleKind,
+    /// Engine in the settings.
+    engine: TimerEngine,
+    /// Whether the countdown is paused.
+    paused: bool,
+    /// Set on the tick the current period is skipped.
+    skip: bool,
+    /// Set on the tick the current period is restarted.
+    restart: bool,
+}
+
+/// One tick of the engine.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct EngineTick {
+    /// The period shown on the previous tick.
+    previous: Option<Period>,
+    /// The current period.
+    period: Period,
+    /// Whether the previous period was skipped.
+    skipped: bool,
+    /// Set on the tick a newly chosen schedule takes over.
+    change: Option<ScheduleChange>,
+}
+
+/// The state the timer carries from one tick to the next.
+///
+/// Time is passed in rather than read, so a recorded [`EventLog`] can be
+/// replayed through it.
+#[derive(Default)]
+struct Engine {
+    /// The free-running session, while that engine is chosen.
+    session: Option<FreeRunningSession>,
+    /// A clock-aligned period taken off the clock by a pause, skip or restart.
+    handover: Option<FreeRunningSession>,
+    /// The period shown on the previous tick.
+    last_period: Option<Period>,
+    /// Schedule the clock-aligned period belongs to.
+    active_schedule: Option<ScheduleKind>,
+}
+
+impl Engine {
+    /// Works out the period at local time `now`, which is the moment `instant`.
+    fn tick(
+        &mut self,
+        controls: Controls,
+        custom: &[PomodoroSegment],
+        now: NaiveTime,
+        instant: Instant,
+    ) -> EngineTick {
+        let Controls {
+            schedule,
+            engine,
+            paused,
+            skip,
+            restart,
+        } = controls;
+        let mut change = None;
+        let (period, skipped) = match engine {
+            TimerEngine::ClockAligned => {
+                self.session = None;
+                // Pausing, skipping, or restarting takes the current period off the clock
+                if self.handover.is_none() && (paused || skip || restart) {
+                    self.handover = self
+                        .last_period
+                        .map(|last| FreeRunningSession::continuing(&last, instant));
+                }
+                let handed_over = self.handover.as_mut().and_then(|handover| {
+                    handover.set_paused(paused, instant);
+                    if skip {
+                        handover.skip(instant);
+                    }
+                    if restart {
+                        handover.restart(instant);
+                    }
+                    handover.current_period_until_end(instant)
+                });
+                if let Some(period) = handed_over {
+                    (period, skip)
+                } else {
+                    self.handover = None;
+                    let tick = clock_tick(
+                        self.active_schedule,
+                        schedule,
+                        custom,
+                        self.last_period,
+                        now,
+                    );
+                    self.active_schedule = Some(tick.schedule);
+                    change = tick.change;
+                    (tick.period, false)
+                }
+            }
+            TimerEngine::FreeRunning => {
+                self.active_schedule = None;
+                self.handover = None;
+                let last_period = self.last_period;
+                let session = self.session.get_or_insert_with(|| {
+                    let previous =
+                        last_period.unwrap_or_else(|| get_current_period(schedule, custom, now));
+                    FreeRunningSession::continuing(&previous, instant)
+                });
+                session.set_paused(paused, instant);
+                if skip {
+                    session.skip(instant);
+                }
+                if restart {
+                    session.restart(instant);
+                }
+                (session.current_period(instant), skip)
+            }
+        };
+        let previous = self.last_period.replace(period);
+        EngineTick {
+            previous,
+            period,
+            skipped,
+            change,
+        }
+    }
+}
+
+/// The period the engine showed at one moment of a replay.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReplayedPeriod {
+    /// Local time of the moment.
+    pub at: NaiveDateTime,
+    /// Mode of the period.
+    pub mode: PomodoroMode,
+    /// Seconds remaining in the period.
+    pub remaining: i32,
+}
+
+/// What the engine does when fed a recorded [`EventLog`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Replay {
+    /// The period shown right after each event, in the order of the log.
+    pub at_events: Vec<ReplayedPeriod>,
+    /// Every change of mode, as the first moment of the new period.
+    pub changes: Vec<ReplayedPeriod>,
+}
+
+/// Feeds `log` through the engine, one second at a time, from its first event
+/// to its last.
+///
+/// Each event sets the controls it recorded, skipping or restarting the period
+/// if that's what it was, and each [`EventKind::Started`] starts a fresh
+/// engine, as launching the app does. Nothing is known about what happened
+/// while the app wasn't running, so mode changes the replay finds between the
+/// last event before a start and that start are left out. `custom` stands in
+/// for the custom schedule, which the log doesn't record.
+pub fn replay(log: &EventLog, custom: &[PomodoroSegment]) -> Replay {
+    let mut replay = Replay::default();
+    let (Some(first), Some(last)) = (log.events.first(), log.events.last()) else {
+        return replay;
+    };
+    let start = Instant::now();
+    let mut engine = Engine::default();
+    let mut controls = Controls {
+        schedule: first.schedule,
+        engine: first.engine,
+        paused: first.paused,
+        skip: false,
+        restart: false,
+    };
+    let mut pending = log.events.iter().peekable();
+    let mut last_event_at = first.at;
+    let mut at = first.at;
+    while at <= last.at {
+        controls.skip = false;
+        controls.restart = false;
+        let mut applied = 0;
+        while let Some(event) = pending.next_if(|event| event.at <= at) {
+            if event.kind == EventKind::Started {
+                engine = Engine::default();
+                replay.changes.retain(|change| change.at <= last_event_at);
+            }
+            controls.schedule = event.schedule;
+            controls.engine = event.engine;
+            controls.paused = event.paused;
+            controls.skip |= event.kind == EventKind::Skipped;
+            controls.restart |= event.kind == EventKind::Restarted;
+            last_event_at = event.at;
+            applied += 1;
+        }
+        let instant = start + (at - first.at).to_std().unwrap_or_default();
+        let tick = engine.tick(controls, custom, at.time(), instant);
+        let shown = ReplayedPeriod {
+            at,
+            mode: tick.period.mode,
+            remaining: tick.period.remaining,
+        };
+        if tick
+            .previous
+            .is_some_and(|previous| previous.mode != shown.mode)
+        {
+            replay.changes.push(shown);
+        }
+        for _ in 0..applied {
+            replay.at_events.push(shown);
+        }
+        at += TimeDelta::seconds(1);
+    }
+    replay
+}
+
+/// Returns what to log for a tick with `controls`, after a tick with `last`.
+fn tick_events(last: Option<Controls>, controls: Controls, mode_changed: bool) -> Vec<EventKind> {
+    let Some(last) = last else {
+        return vec![EventKind::Started];
+    };
+    let settings_changed = controls.schedule != last.schedule || controls.engine != last.engine;
+    [
+        (controls.paused && !last.paused, EventKind::Paused),
+        (!controls.paused && last.paused, EventKind::Resumed),
+        (controls.skip, EventKind::Skipped),
+        (controls.restart, EventKind::Restarted),
+        (settings_changed, EventKind::SettingsChanged),
+        (mode_changed, EventKind::PeriodStarted),
+    ]
+    .into_iter()
+    .filter_map(|(happened, kind)| happened.then_some(kind))
+    .collect()
 }
 
 /// Runs the timer loop in a background thread.
 ///
 /// Updates the shared application state every second with the current mode
-/// and remaining time. This function never returns and should be spawned
-/// in a separate thread.
+/// and remaining time. The engine and schedule are re-read each tick, so they
+/// can be switched at runtime, and pause/skip/restart requests are applied to
+/// the free-running session. Pausing, skipping, or restarting a clock-aligned
+/// period hands it over to a free-running session just until that period
+/// ends, without changing the saved engine. A clock-aligned schedule switched
+/// mid-period doesn't cut the period short: the current segment of the old
+/// schedule runs to its end, and the new schedule takes over from that
+/// boundary.
+///
+/// Each work period that runs through to rest (without being skipped) is
+/// counted as a completed pomodoro, and every change of mode or schedule shows
+/// a desktop notification. The companion window is repainted whenever the time or mode
+/// shown changes. New periods, pauses, skips, restarts, and changes of schedule
+/// or engine are recorded in the [`EventLog`] for [`replay`]. This function
+/// never returns and should be spawned in a separate thread.
 ///
 /// # Arguments
 ///
@@ -107,17 +768,471 @@ fn format_time(seconds: i32) -> String {
 /// });
 /// ```
 pub fn run_timer(state: Arc<Mutex<AppState>>) {
+    let mut engine = Engine::default();
+    let mut last_controls: Option<Controls> = None;
+    let mut events = load_events();
+    let mut sabbath_reminded: Option<NaiveDate> = None;
+    let mut sabbath_rested: Option<NaiveDate> = None;
+
     loop {
-        let (mode, remaining) = get_current_period();
-        let formatted = format_time(remaining);
+        let now = Local::now();
+        let (controls, custom) = {
+            let mut s = state.lock();
+            let controls = Controls {
+                schedule: s.settings.schedule,
+                engine: s.settings.engine,
+                paused: s.paused,
+                skip: std::mem::take(&mut s.skip_requested),
+                restart: std::mem::take(&mut s.restart_requested),
+            };
+            (controls, s.settings.custom_schedule.clone())
+        };
+        let paused = controls.paused;
+        let EngineTick {
+            previous: last_period,
+            period,
+            skipped,
+            change: schedule_change,
+        } = engine.tick(controls, &custom, now.time(), Instant::now());
 
-        {
+        let mode_changed = last_period.is_some_and(|last| last.mode != period.mode);
+        let work_ended = last_period.is_some_and(|last| {
+            last.mode == PomodoroMode::Work && period.mode == PomodoroMode::Rest
+        });
+        let rest_ended = last_period.is_some_and(|last| {
+            last.mode == PomodoroMode::Rest && period.mode == PomodoroMode::Work
+        });
+        let completed = !skipped && work_ended;
+        let minute_left = !paused
+            && last_period.is_some_and(|last| {
+                last.mode == period.mode
+                    && last.remaining > MINUTE_WARNING_SECONDS
+                    && period.remaining <= MINUTE_WARNING_SECONDS
+            });
+        let mut finished_sprint = None;
+        let formatted = format::clock(period.remaining);
+
+        let changed_stats = {
             let mut s = state.lock();
-            s.mode = mode;
-            s.remaining_seconds = remaining;
+            let today = now.date_naive();
+            let stats_changed = if completed {
+                let planned = s.plan.label_at(now).is_some();
+                finished_sprint = s.stats.record_completed(now.naive_local(), planned);
+                true
+            } else if rest_ended {
+                s.stats.record_rest_ended(today, !skipped);
+                true
+            } else if work_ended && s.stats.sprint.is_some() {
+                s.stats.record_skipped();
+                true
+            } else {
+                s.stats.roll_over(today)
+            };
+
+            // Bring the companion back once a snooze runs out
+            if s.snoozed_until.is_some_and(|until| now >= until) {
+                s.snoozed_until = None;
+                s.visible = true;
+                if let Some(ctx) = &s.repaint {
+                    ctx.send_viewport_cmd_to(
+                        egui::ViewportId::ROOT,
+                        egui::ViewportCommand::Visible(true),
+                    );
+                }
+            }
+
+            // Put the companion away for Sunday rest
+            if sabbath::rest_due(now.naive_local(), &s.settings.sabbath, sabbath_rested) {
+                sabbath_rested = Some(today);
+                s.snoozed_until = Some(sabbath::rest_until(now));
+                s.visible = false;
+                if let Some(ctx) = &s.repaint {
+                    ctx.send_viewport_cmd_to(
+                        egui::ViewportId::ROOT,
+                        egui::ViewportCommand::Visible(false),
+                    );
+                }
+            }
+
+            // A rest that ended without being seen is a missed prayer
+            if rest_ended && !skipped && !s.rest_seen {
+                s.missed_prayers += 1;
+            }
+            s.rest_seen = period.mode == PomodoroMode::Rest && (s.rest_seen || s.visible);
+
+            let changed = s.mode != period.mode || s.formatted_time != formatted;
+            s.active_schedule = engine.active_schedule;
+            s.mode = period.mode;
+            s.remaining_seconds = period.remaining;
+            s.period_seconds = period.duration;
             s.formatted_time = formatted;
+            s.rosary = if period.mode == PomodoroMode::Rest && s.settings.rosary_mode {
+                Some(RosaryProgress::at(
+                    period.duration - period.remaining,
+                    period.duration,
+                ))
+            } else {
+                None
+            };
+            if changed {
+                if let Some(ctx) = &s.repaint {
+                    ctx.request_repaint();
+                }
+            }
+            stats_changed.then(|| s.stats.clone())
+        };
+        // Saved once the lock is released, so a slow disk never holds up the UI
+        if let Some(stats) = changed_stats {
+            save_stats(&stats);
+        }
+
+        let logged = tick_events(last_controls, controls, mode_changed);
+        if !logged.is_empty() {
+            for kind in logged {
+                events.record(TimerEvent {
+                    at: now.naive_local().trunc_subsecs(0),
+                    kind,
+                    schedule: controls.schedule,
+                    engine: controls.engine,
+                    paused,
+                    mode: period.mode,
+                    remaining: period.remaining,
+                });
+            }
+            save_events(&events);
+        }
+        last_controls = Some(controls);
+
+        let (settings, sabbath_settings, snoozed) = {
+            let s = state.lock();
+            (
+                s.settings.notifications.clone(),
+                s.settings.sabbath.clone(),
+                s.snoozed_until.is_some(),
+            )
+        };
+        if sabbath::reminder_due(
+            now.naive_local(),
+            &sabbath_settings,
+            sabbath_reminded,
+            snoozed,
+        ) {
+            sabbath_reminded = Some(now.date_naive());
+            notify_sabbath_reminder(sabbath_settings.sunday_rest, &settings);
+        }
+        if let Some(change) = schedule_change.filter(|_| !snoozed) {
+            notify_schedule_changed(&change, &settings);
+        }
+        if mode_changed && !snoozed {
+            notify_period_change(period.mode, period.duration, &settings);
+        }
+        if minute_left && !snoozed {
+            notify_minute_left(period.mode, &settings);
+        }
+        if !mode_changed && !snoozed {
+            update_period_progress(
+                period.mode,
+                period.remaining,
+                period.duration,
+                paused,
+                &settings,
+            );
+        }
+        if let Some(sprint) = finished_sprint.filter(|_| !snoozed) {
+            notify_sprint_finished(&sprint, &settings);
         }
 
         std::thread::sleep(Duration::from_secs(1));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(hour: u32, minute: u32, second: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, second).unwrap()
+    }
+
+    fn period(mode: PomodoroMode, remaining: i32, duration: i32) -> Period {
+        Period {
+            mode,
+            remaining,
+            duration,
+        }
+    }
+
+    #[test]
+    fn hourly_work_ends_at_25() {
+        let work = get_hourly_period(at(9, 24, 59));
+        assert_eq!(work, period(PomodoroMode::Work, 1, 25 * 60));
+
+        let rest = get_hourly_period(at(9, 25, 0));
+        assert_eq!(rest, period(PomodoroMode::Rest, 5 * 60, 5 * 60));
+    }
+
+    #[test]
+    fn hourly_rest_ends_at_30() {
+        let rest = get_hourly_period(at(9, 29, 59));
+        assert_eq!(rest, period(PomodoroMode::Rest, 1, 5 * 60));
+
+        let work = get_hourly_period(at(9, 30, 0));
+        assert_eq!(work, period(PomodoroMode::Work, 25 * 60, 25 * 60));
+    }
+
+    #[test]
+    fn hourly_work_ends_at_55() {
+        let work = get_hourly_period(at(9, 54, 59));
+        assert_eq!(work, period(PomodoroMode::Work, 1, 25 * 60));
+
+        let rest = get_hourly_period(at(9, 55, 0));
+        assert_eq!(rest, period(PomodoroMode::Rest, 5 * 60, 5 * 60));
+    }
+
+    #[test]
+    fn hourly_rest_ends_at_the_top_of_the_hour() {
+        let rest = get_hourly_period(at(9, 59, 59));
+        assert_eq!(rest, period(PomodoroMode::Rest, 1, 5 * 60));
+
+        let work = get_hourly_period(at(10, 0, 0));
+        assert_eq!(work, period(PomodoroMode::Work, 25 * 60, 25 * 60));
+    }
+
+    #[test]
+    fn hourly_rolls_over_midnight() {
+        let rest = get_hourly_period(at(23, 59, 59));
+        assert_eq!(rest, period(PomodoroMode::Rest, 1, 5 * 60));
+
+        let work = get_hourly_period(at(0, 0, 0));
+        assert_eq!(work, period(PomodoroMode::Work, 25 * 60, 25 * 60));
+    }
+
+    #[test]
+    fn family_alternates_15_and_5() {
+        let work = get_current_period(ScheduleKind::Family, &[], at(9, 14, 59));
+        assert_eq!(work, period(PomodoroMode::Work, 1, 15 * 60));
+
+        let rest = get_current_period(ScheduleKind::Family, &[], at(9, 35, 0));
+        assert_eq!(rest, period(PomodoroMode::Rest, 5 * 60, 5 * 60));
+
+        let work = get_current_period(ScheduleKind::Family, &[], at(9, 40, 0));
+        assert_eq!(work, period(PomodoroMode::Work, 15 * 60, 15 * 60));
+    }
+
+    fn segment(start_minute: u32, end_minute: u32, mode: PomodoroMode) -> PomodoroSegment {
+        PomodoroSegment {
+            start_minute,
+            end_minute,
+            mode,
+        }
+    }
+
+    #[test]
+    fn custom_segments_must_cover_the_hour() {
+        assert_eq!(check_segments(POMODORO_SEGMENTS), Ok(()));
+        assert_eq!(check_segments(FAMILY_SEGMENTS), Ok(()));
+
+        let gap = [
+            segment(0, 50, PomodoroMode::Work),
+            segment(51, 60, PomodoroMode::Rest),
+        ];
+        assert!(check_segments(&gap).unwrap_err().contains(":50 to :51"));
+
+        let overlap = [
+            segment(0, 50, PomodoroMode::Work),
+            segment(45, 60, PomodoroMode::Rest),
+        ];
+        assert!(check_segments(&overlap).unwrap_err().contains("overlaps"));
+
+        let short = [segment(0, 50, PomodoroMode::Work)];
+        assert!(check_segments(&short).unwrap_err().contains("ends at :50"));
+
+        let repeated = [
+            segment(0, 30, PomodoroMode::Work),
+            segment(30, 60, PomodoroMode::Work),
+        ];
+        assert!(check_segments(&repeated).is_err());
+        assert!(check_segments(&[]).is_err());
+    }
+
+    #[test]
+    fn custom_schedule_falls_back_to_hourly_when_invalid() {
+        let custom = [
+            segment(0, 50, PomodoroMode::Work),
+            segment(50, 60, PomodoroMode::Rest),
+        ];
+        let rest = get_current_period(ScheduleKind::Custom, &custom, at(9, 50, 0));
+        assert_eq!(rest, period(PomodoroMode::Rest, 10 * 60, 10 * 60));
+
+        let fallback = get_current_period(ScheduleKind::Custom, &custom[..1], at(9, 50, 0));
+        assert_eq!(fallback, get_hourly_period(at(9, 50, 0)));
+    }
+
+    #[test]
+    fn liturgy_work_wraps_past_midnight_to_lauds() {
+        let compline_end = 21 * 3600 + 15 * 60;
+        let lauds = 6 * 3600;
+        let overnight = SECONDS_PER_DAY - compline_end + lauds;
+
+        let before_midnight = get_liturgy_period(at(23, 59, 59));
+        assert_eq!(
+            before_midnight,
+            period(PomodoroMode::Work, lauds + 1, overnight)
+        );
+
+        let after_midnight = get_liturgy_period(at(0, 0, 0));
+        assert_eq!(after_midnight, period(PomodoroMode::Work, lauds, overnight));
+
+        let at_lauds = get_liturgy_period(at(6, 0, 0));
+        assert_eq!(at_lauds, period(PomodoroMode::Rest, 15 * 60, 15 * 60));
+    }
+
+    #[test]
+    fn clock_tick_keeps_the_schedule_mid_period() {
+        let last = get_hourly_period(at(9, 10, 0));
+        let tick = clock_tick(
+            Some(ScheduleKind::Hourly),
+            ScheduleKind::LiturgyOfTheHours,
+            &[],
+            Some(last),
+            at(9, 10, 1),
+        );
+
+        assert_eq!(tick.schedule, ScheduleKind::Hourly);
+        assert_eq!(tick.period, get_hourly_period(at(9, 10, 1)));
+        assert_eq!(tick.change, None);
+    }
+
+    #[test]
+    fn clock_tick_switches_schedule_at_the_boundary() {
+        let last = get_hourly_period(at(9, 24, 59));
+        let tick = clock_tick(
+            Some(ScheduleKind::Hourly),
+            ScheduleKind::LiturgyOfTheHours,
+            &[],
+            Some(last),
+            at(9, 25, 0),
+        );
+
+        assert_eq!(tick.schedule, ScheduleKind::LiturgyOfTheHours);
+        assert_eq!(tick.period, get_liturgy_period(at(9, 25, 0)));
+        assert_eq!(
+            tick.change,
+            Some(ScheduleChange {
+                from: ScheduleKind::Hourly,
+                to: ScheduleKind::LiturgyOfTheHours,
+            })
+        );
+    }
+
+    #[test]
+    fn clock_tick_switches_schedule_at_the_hour_rollover() {
+        let last = get_hourly_period(at(9, 59, 59));
+        let tick = clock_tick(
+            Some(ScheduleKind::Hourly),
+            ScheduleKind::LiturgyOfTheHours,
+            &[],
+            Some(last),
+            at(10, 0, 0),
+        );
+
+        assert_eq!(tick.schedule, ScheduleKind::LiturgyOfTheHours);
+        assert!(tick.change.is_some());
+    }
+
+    #[test]
+    fn clock_tick_adopts_the_requested_schedule_on_the_first_tick() {
+        let tick = clock_tick(
+            None,
+            ScheduleKind::LiturgyOfTheHours,
+            &[],
+            None,
+            at(12, 5, 0),
+        );
+
+        assert_eq!(tick.schedule, ScheduleKind::LiturgyOfTheHours);
+        assert_eq!(tick.period, period(PomodoroMode::Rest, 10 * 60, 15 * 60));
+        assert_eq!(tick.change, None);
+    }
+
+    fn event(time: NaiveTime, kind: EventKind, mode: PomodoroMode, remaining: i32) -> TimerEvent {
+        TimerEvent {
+            at: NaiveDate::from_ymd_opt(2026, 10, 16)
+                .unwrap()
+                .and_time(time),
+            kind,
+            schedule: ScheduleKind::Hourly,
+            engine: TimerEngine::ClockAligned,
+            paused: false,
+            mode,
+            remaining,
+        }
+    }
+
+    fn change_times(replay: &Replay) -> Vec<NaiveTime> {
+        replay
+            .changes
+            .iter()
+            .map(|change| change.at.time())
+            .collect()
+    }
+
+    #[test]
+    fn replay_follows_the_clock_and_a_skip() {
+        let log = EventLog {
+            events: vec![
+                event(at(9, 24, 0), EventKind::Started, PomodoroMode::Work, 60),
+                event(
+                    at(9, 25, 0),
+                    EventKind::PeriodStarted,
+                    PomodoroMode::Rest,
+                    300,
+                ),
+                event(at(9, 27, 0), EventKind::Skipped, PomodoroMode::Work, 1500),
+                event(
+                    at(9, 27, 0),
+                    EventKind::PeriodStarted,
+                    PomodoroMode::Work,
+                    1500,
+                ),
+                event(
+                    at(9, 55, 0),
+                    EventKind::PeriodStarted,
+                    PomodoroMode::Rest,
+                    300,
+                ),
+            ],
+        };
+        let replay = replay(&log, &[]);
+
+        assert_eq!(
+            change_times(&replay),
+            [at(9, 25, 0), at(9, 27, 0), at(9, 55, 0)]
+        );
+        assert_eq!(replay.at_events.len(), log.events.len());
+        assert_eq!(replay.at_events[2].mode, PomodoroMode::Work);
+        assert_eq!(replay.at_events[2].remaining, 1500);
+        assert!(log.discrepancies(&replay).is_empty());
+    }
+
+    #[test]
+    fn replay_leaves_out_the_time_the_app_was_closed() {
+        let log = EventLog {
+            events: vec![
+                event(at(9, 0, 0), EventKind::Started, PomodoroMode::Work, 1500),
+                TimerEvent {
+                    paused: true,
+                    ..event(at(9, 10, 0), EventKind::Paused, PomodoroMode::Work, 900)
+                },
+                event(at(11, 20, 0), EventKind::Started, PomodoroMode::Work, 300),
+                TimerEvent {
+                    paused: true,
+                    ..event(at(11, 26, 0), EventKind::Paused, PomodoroMode::Rest, 240)
+                },
+            ],
+        };
+        let replay = replay(&log, &[]);
+
+        assert_eq!(change_times(&replay), [at(11, 25, 0)]);
+    }
+}
@@ -1,17 +1,123 @@
 //! System tray icon management with context menu.
 //!
 //! Provides a system tray icon that allows users to:
-//! - View the countdown timer
+//! - View the countdown timer, optionally as a title beside the icon
+//! - Pause/resume or skip the current period
+//! - Switch between settings profiles
+//! - See how many pomodoros were completed today and this week
+//! - See and edit today's session plan
+//! - Copy a status line to the clipboard
+//! - Check for updates
 //! - Toggle character visibility
+//! - Snooze the companion and notifications for a while
 //! - Change character size (50% to 200%)
-//! - Switch between saint characters
+//! - Switch between saint characters (or cycle to the next one)
+//! - Choose the timer schedule
+//! - Toggle the rosary decade countdown during rest
+//! - Toggle touch-friendly interaction
+//! - Toggle the fullscreen break overlay
 //! - Quit the application
+//!
+//! Double-clicking the icon (where the platform reports it) triggers the
+//! configurable [`TrayDoubleClickAction`].
 
-use crate::state::{AppState, PomodoroMode, AVAILABLE_CHARACTERS};
-use muda::{CheckMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem, Submenu};
+use crate::characters;
+use crate::presence::PresenceStatus;
+use crate::settings::{
+    MenuSection, OverlayStyle, Profile, Settings, TimerDisplay, TrayDoubleClickAction, TrayTitle,
+    WindowLayer,
+};
+use crate::state::{AppState, PomodoroMode};
+use crate::stats::Sprint;
+use crate::timer::{ScheduleKind, TimerEngine};
+use chrono::{DateTime, Days, Local, TimeDelta};
+use image::imageops::FilterType;
+use muda::{
+    CheckMenuItem, Icon as MenuIcon, IconMenuItem, IsMenuItem, Menu, MenuEvent, MenuItem,
+    PredefinedMenuItem, Submenu,
+};
 use parking_lot::Mutex;
+use serde::Serialize;
+use std::hash::{DefaultHasher, Hasher};
+use std::io;
+use std::sync::mpsc::{self, Receiver};
 use std::sync::Arc;
-use tray_icon::{Icon, TrayIcon, TrayIconBuilder};
+use std::time::{Duration, Instant};
+use tray_icon::{Icon, MouseButton, TrayIcon, TrayIconBuilder, TrayIconEvent};
+
+/// Size in pixels of the saint portraits in the Character submenu.
+const PORTRAIT_SIZE: u32 = 32;
+
+/// Window scale presets offered in the Size menus.
+pub const SCALE_PRESETS: &[f32] = &[0.5, 0.75, 1.0, 1.25, 1.5, 2.0];
+
+/// Sprite shadow and outline strengths offered in the Appearance menu.
+pub const EFFECT_PRESETS: &[(f32, &str)] = &[(0.0, "Off"), (0.5, "Subtle"), (1.0, "Strong")];
+
+/// Window opacity options offered in the tray menu.
+pub const OPACITY_PRESETS: &[f32] = &[0.25, 0.5, 0.75, 1.0];
+
+/// Color of the dot drawn on the tray icon while prayers are missed.
+const BADGE_COLOR: [u8; 4] = [220, 50, 47, 255];
+
+/// How often the tooltip's time left is refreshed.
+const TOOLTIP_REFRESH: Duration = Duration::from_secs(60);
+
+/// Minimum time between handled double-clicks.
+///
+/// Some platforms report a burst of click events for one double-click; this
+/// keeps a single gesture from toggling twice.
+const DOUBLE_CLICK_DEBOUNCE: Duration = Duration::from_millis(500);
+
+impl TrayDoubleClickAction {
+    /// Returns the tray action this double-click setting maps to.
+    fn action(&self) -> TrayAction {
+        match self {
+            TrayDoubleClickAction::Nothing => TrayAction::None,
+            TrayDoubleClickAction::ToggleCompanion => TrayAction::ToggleVisibility,
+            TrayDoubleClickAction::OpenPlanner => TrayAction::OpenPlanner,
+            TrayDoubleClickAction::TogglePause => TrayAction::TogglePause,
+            TrayDoubleClickAction::SkipPeriod => TrayAction::SkipPeriod,
+        }
+    }
+}
+
+/// How long a snooze from the tray lasts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Snooze {
+    /// Thirty minutes.
+    HalfHour,
+    /// One hour.
+    Hour,
+    /// Until midnight.
+    RestOfDay,
+}
+
+impl Snooze {
+    /// All snoozes, in the order they appear in the tray menu.
+    pub const ALL: &'static [Snooze] = &[Snooze::HalfHour, Snooze::Hour, Snooze::RestOfDay];
+
+    /// Returns the human-readable name of the snooze.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Snooze::HalfHour => "30 Minutes",
+            Snooze::Hour => "1 Hour",
+            Snooze::RestOfDay => "Rest of Day",
+        }
+    }
+
+    /// Returns when a snooze starting at `now` ends.
+    pub fn until(&self, now: DateTime<Local>) -> DateTime<Local> {
+        match self {
+            Snooze::HalfHour => now + TimeDelta::minutes(30),
+            Snooze::Hour => now + TimeDelta::hours(1),
+            Snooze::RestOfDay => (now.date_naive() + Days::new(1))
+                .and_hms_opt(0, 0, 0)
+                .and_then(|midnight| midnight.and_local_timezone(Local).earliest())
+                .unwrap_or(now + TimeDelta::days(1)),
+        }
+    }
+}
 
 /// Actions that can be triggered from the tray menu.
 #[derive(Clone, Debug)]
@@ -20,10 +126,86 @@ pub enum TrayAction {
     None,
     /// Toggle the visibility of the character window.
     ToggleVisibility,
+    /// Hide the companion and silence notifications for a while.
+    Snooze(Snooze),
+    /// End a snooze early, bringing the companion back.
+    EndSnooze,
+    /// Open the planner window for today's session plan.
+    OpenPlanner,
+    /// Open the sprint window to start or review a sprint.
+    OpenSprint,
+    /// Open the window for entering a custom size.
+    OpenCustomScale,
+    /// Clear the missed-prayer badge.
+    AcknowledgeMissedPrayers,
+    /// Open the command palette.
+    OpenCommandPalette,
+    /// Open the settings window.
+    OpenSettings,
+    /// Open the About window.
+    OpenAbout,
+    /// Mirror the companion to face the other way.
+    ToggleFlip,
+    /// Copy a one-line status summary to the clipboard.
+    CopyStatus,
+    /// Check GitHub for a newer release.
+    CheckForUpdates,
+    /// Pause or resume the countdown.
+    TogglePause,
+    /// End the current period early.
+    SkipPeriod,
+    /// Start the current period over from its full length.
+    RestartPeriod,
     /// Change the selected saint character.
     SetCharacter(String),
+    /// Cycle to the next saint character.
+    NextCharacter,
     /// Change the window scale (0.5 to 2.0).
     SetScale(f32),
+    /// Change how the window stacks against other windows.
+    SetLayer(WindowLayer),
+    /// Change the strength of the sprite's drop shadow (0.0 to 1.0).
+    SetSpriteShadow(f32),
+    /// Change the strength of the sprite's outline (0.0 to 1.0).
+    SetSpriteOutline(f32),
+    /// Change the opacity of the sprite and timer (0.25 to 1.0).
+    SetOpacity(f32),
+    /// Change how the companion's timer shows the time left.
+    SetTimerDisplay(TimerDisplay),
+    /// Change what the tray icon shows beside it in the menu bar.
+    SetTrayTitle(TrayTitle),
+    /// Swap in the settings of another profile.
+    SetProfile(Profile),
+    /// Change the timer schedule.
+    SetSchedule(ScheduleKind),
+    /// Switch between clock-aligned and free-running timing.
+    SetEngine(TimerEngine),
+    /// Toggle the rosary decade countdown during rest periods.
+    ToggleRosary,
+    /// Toggle touch-friendly interaction on the companion window.
+    ToggleTouchMode,
+    /// Toggle the fullscreen prayer overlay during rest periods.
+    ToggleBreakOverlay,
+    /// Change whether the break overlay dims the screen or is a centered window.
+    SetOverlayStyle(OverlayStyle),
+    /// Toggle the guided breathing pause at the start of the break overlay.
+    ToggleBreathingPause,
+    /// Toggle replacing animations with still images.
+    ToggleReduceMotion,
+    /// Toggle the prayer card at the start of each work period.
+    TogglePrayerBeforeWork,
+    /// Toggle the saint's speech bubbles.
+    ToggleSpeechBubbles,
+    /// Toggle the chime played with period-change notifications.
+    ToggleSounds,
+    /// Toggle showing a companion on every monitor.
+    ToggleMultiCompanion,
+    /// Toggle launching Praymodoro at login.
+    ToggleStartAtLogin,
+    /// Pray for a saved intention during rests (empty for none).
+    SetIntention(String),
+    /// Open the window for adding a prayer intention.
+    AddIntention,
     /// Quit the application.
     Quit,
 }
@@ -34,69 +216,518 @@ pub enum TrayAction {
 /// controlling the application. Menu items are automatically updated to
 /// reflect the current application state.
 pub struct TrayManager {
-    _tray: TrayIcon,
+    /// The tray icon itself, kept alive for as long as the manager.
+    tray: TrayIcon,
+    /// Title last shown beside the icon.
+    title: Option<String>,
     /// Menu item showing the countdown timer.
     countdown_item: MenuItem,
+    /// Menu item showing today's work periods and prayed rests.
+    today_item: MenuItem,
+    /// Menu item showing completed-pomodoro counts.
+    completed_item: MenuItem,
+    /// Menu item showing the planned label for the current block.
+    plan_item: MenuItem,
+    /// Menu item counting missed prayers, clicked to acknowledge them.
+    missed_item: MenuItem,
+    /// Whether the tray icon is showing the missed-prayer badge.
+    badged: bool,
+    /// Menu item showing the accountability partner's status.
+    partner_item: MenuItem,
+    /// Menu ID for opening the planner.
+    planner_id: muda::MenuId,
+    /// Menu item opening the sprint window, showing sprint progress.
+    sprint_item: MenuItem,
+    /// Menu item to pause or resume the countdown.
+    pause_item: MenuItem,
+    /// Menu ID for skipping the current period.
+    skip_id: muda::MenuId,
+    /// Menu ID for restarting the current period.
+    restart_id: muda::MenuId,
+    /// Menu ID for copying the status line.
+    copy_status_id: muda::MenuId,
     /// Checkbox to show/hide the character window.
     show_check: CheckMenuItem,
+    /// Snooze submenu, titled with the snooze's end while snoozed.
+    snooze_submenu: Submenu,
+    /// Snooze length items.
+    snooze_items: Vec<(Snooze, MenuItem)>,
+    /// Menu item ending the snooze early.
+    end_snooze_item: MenuItem,
+    /// Checkbox to show a companion on every monitor.
+    multi_companion_check: CheckMenuItem,
+    /// Checkbox to launch Praymodoro at login.
+    login_check: CheckMenuItem,
     /// Size option checkboxes (50%, 75%, 100%, 125%, 150%, 200%).
     size_checks: Vec<(f32, CheckMenuItem)>,
-    /// Character selection checkboxes.
-    char_checks: Vec<(String, CheckMenuItem)>,
+    /// Size submenu, titled with the exact size when it isn't one of the presets.
+    size_submenu: Submenu,
+    /// Menu ID for entering a custom size.
+    custom_size_id: muda::MenuId,
+    /// Settings profile checkboxes.
+    profile_checks: Vec<(Profile, CheckMenuItem)>,
+    /// Window layer checkboxes.
+    layer_checks: Vec<(WindowLayer, CheckMenuItem)>,
+    /// Timer display checkboxes.
+    display_checks: Vec<(TimerDisplay, CheckMenuItem)>,
+    /// Menu bar title checkboxes.
+    title_checks: Vec<(TrayTitle, CheckMenuItem)>,
+    /// Opacity option checkboxes (25%, 50%, 75%, 100%).
+    opacity_checks: Vec<(f32, CheckMenuItem)>,
+    /// Sprite shadow strength checkboxes.
+    shadow_checks: Vec<(f32, CheckMenuItem)>,
+    /// Sprite outline strength checkboxes.
+    outline_checks: Vec<(f32, CheckMenuItem)>,
+    /// Character selection items, each with the saint's portrait.
+    char_items: Vec<(String, IconMenuItem)>,
+    /// Intention submenu, rebuilt when the saved intentions change.
+    intention_submenu: Submenu,
+    /// Checkbox for praying without a particular intention.
+    no_intention_check: CheckMenuItem,
+    /// Saved intention checkboxes.
+    intention_checks: Vec<(String, CheckMenuItem)>,
+    /// Menu ID for adding an intention.
+    add_intention_id: muda::MenuId,
+    /// Menu ID for cycling to the next character.
+    next_char_id: muda::MenuId,
+    /// Schedule selection checkboxes.
+    schedule_checks: Vec<(ScheduleKind, CheckMenuItem)>,
+    /// Timer engine selection checkboxes.
+    engine_checks: Vec<(TimerEngine, CheckMenuItem)>,
+    /// Checkbox to enable the rosary decade countdown during rest.
+    rosary_check: CheckMenuItem,
+    /// Checkbox to enable touch-friendly interaction.
+    touch_check: CheckMenuItem,
+    /// Checkbox to enable the fullscreen break overlay.
+    overlay_check: CheckMenuItem,
+    /// Break overlay style checkboxes.
+    overlay_style_checks: Vec<(OverlayStyle, CheckMenuItem)>,
+    /// Checkbox to enable the breathing pause in the break overlay.
+    breathing_check: CheckMenuItem,
+    /// Checkbox to reduce motion.
+    reduce_motion_check: CheckMenuItem,
+    /// Checkbox to enable the saint's speech bubbles.
+    quotes_check: CheckMenuItem,
+    /// Checkbox to enable the prayer before work.
+    work_prayer_check: CheckMenuItem,
+    /// Checkbox to enable notification sounds.
+    sounds_check: CheckMenuItem,
+    /// Menu ID for opening the settings window.
+    settings_id: muda::MenuId,
+    /// Menu ID for checking for updates.
+    update_id: muda::MenuId,
+    /// Menu ID for opening the About window.
+    about_id: muda::MenuId,
     /// Menu ID for the quit action.
     quit_id: muda::MenuId,
+    /// When the last double-click was handled, for debouncing.
+    last_double_click: Option<Instant>,
+    /// Mode and count last shown in the tooltip, and when it was last refreshed.
+    tooltip: Option<(String, Instant)>,
+    /// Fingerprint of the state the menu was last updated for.
+    shown: Option<u64>,
+    /// Menu events, forwarded by the event handler installed in [`TrayManager::new`].
+    menu_events: Receiver<MenuEvent>,
+    /// Tray icon events, forwarded by the event handler installed in [`TrayManager::new`].
+    icon_events: Receiver<TrayIconEvent>,
+}
+
+/// Everything in the application state the tray menu shows.
+///
+/// The menu is only updated when this changes, rather than on every frame.
+/// It borrows from the state and is compared by [`MenuSnapshot::fingerprint`],
+/// so checking costs no copies.
+#[derive(Serialize)]
+struct MenuSnapshot<'a> {
+    settings: &'a Settings,
+    mode: &'a str,
+    formatted_time: &'a str,
+    remaining_seconds: i32,
+    paused: bool,
+    rosary: Option<(usize, i32)>,
+    character: &'a str,
+    scale: f32,
+    visible: bool,
+    start_at_login: bool,
+    active_schedule: Option<ScheduleKind>,
+    completed_today: u32,
+    completed_this_week: u32,
+    completed_planned_today: u32,
+    rests_kept_today: u32,
+    sprint: Option<&'a Sprint>,
+    plan_label: Option<&'a str>,
+    planned: usize,
+    partner: Option<Result<&'a PresenceStatus, &'a str>>,
+    missed_prayers: u32,
+    snoozed_until: Option<DateTime<Local>>,
+}
+
+impl<'a> MenuSnapshot<'a> {
+    /// Takes the parts of `state` the menu shows.
+    fn of(state: &'a AppState) -> Self {
+        let now = Local::now();
+        Self {
+            settings: &state.settings,
+            mode: state.mode.as_str(),
+            formatted_time: &state.formatted_time,
+            remaining_seconds: state.remaining_seconds,
+            paused: state.paused,
+            rosary: state
+                .rosary
+                .map(|rosary| (rosary.bead, rosary.bead_remaining_seconds)),
+            character: &state.character,
+            scale: state.scale,
+            visible: state.visible,
+            start_at_login: state.start_at_login,
+            active_schedule: state.active_schedule,
+            completed_today: state.stats.completed_today,
+            completed_this_week: state.stats.completed_this_week,
+            completed_planned_today: state.stats.completed_planned_today,
+            rests_kept_today: state.stats.rests_kept_today,
+            sprint: state.stats.sprint.as_ref(),
+            plan_label: state.plan.label_at(now),
+            planned: state.plan.for_day(now.date_naive()).planned_count(),
+            partner: state
+                .partner
+                .as_ref()
+                .map(|partner| partner.as_ref().map_err(String::as_str)),
+            missed_prayers: state.missed_prayers,
+            snoozed_until: state.snoozed_until,
+        }
+    }
+
+    /// Returns a hash of the snapshot.
+    ///
+    /// Settings hold floats, which can't be hashed directly, so the snapshot's
+    /// serialized form is hashed instead.
+    fn fingerprint(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        let _ = serde_json::to_writer(HashWriter(&mut hasher), self);
+        hasher.finish()
+    }
+}
+
+/// Feeds bytes written to it into a hasher.
+struct HashWriter<'a>(&'a mut DefaultHasher);
+
+impl io::Write for HashWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
 }
 
 impl TrayManager {
     /// Creates a new tray icon with context menu.
     ///
-    /// The menu is constructed with:
-    /// - Countdown display (updates automatically)
+    /// Menu and icon events wake `ctx`, so they're handled right away rather
+    /// than on the next scheduled frame.
+    ///
+    /// The menu's sections are placed in the order given by `layout`, leaving
+    /// out any that aren't listed; an empty layout falls back to
+    /// [`MenuSection::DEFAULT_LAYOUT`]. With the default layout the menu is
+    /// constructed with:
+    /// - Countdown display (updates automatically) and when the next prayer begins
+    /// - Today's work and prayer counts
+    /// - Completed-pomodoro counts
+    /// - Planned label for the current block and a planner shortcut
+    /// - Missed prayers, acknowledged by clicking
+    /// - Accountability partner status
+    /// - Sprint window shortcut with sprint progress
+    /// - Pause and skip controls
+    /// - Copy status action
+    /// - Profile submenu with settings profiles
     /// - Size submenu with percentage options
-    /// - Character submenu with available saints
+    /// - Layer submenu with window stacking options
+    /// - Timer display submenu
+    /// - Menu bar title submenu
+    /// - Opacity submenu with percentage options
+    /// - Appearance submenu with sprite shadow and outline strengths
+    /// - Character submenu with a portrait of each available saint and a "Next Character" item
+    /// - Intention submenu with saved prayer intentions and an "Add Intention…" item
+    /// - Schedule submenu with available schedules and timer engines
+    /// - Rosary checkbox
+    /// - Touch mode checkbox
+    /// - Break overlay checkbox and style submenu
+    /// - Breathing pause checkbox
+    /// - Reduce motion checkbox
+    /// - Saint quotes checkbox
+    /// - Prayer before work checkbox
     /// - Show/hide checkbox
+    /// - Snooze submenu with snooze lengths and an "End Snooze" item
+    /// - Every-monitor checkbox
+    /// - Start at login checkbox
+    /// - Settings window shortcut
+    /// - Check for updates
+    /// - About window shortcut
     /// - Quit option
-    pub fn new() -> Self {
+    pub fn new(ctx: &egui::Context, layout: &[MenuSection]) -> Self {
         // Create menu items
         let countdown_item = MenuItem::new("Work for: 25:00", false, None);
+        let today_item = MenuItem::new("Today: 0 work · 0 prayed", false, None);
+        let completed_item = MenuItem::new("Completed: 0 today · 0 this week", false, None);
+        let plan_item = MenuItem::new("Planned: —", false, None);
+        let missed_item = MenuItem::new("No Missed Prayers", false, None);
+        let partner_item = MenuItem::new("Partner: —", false, None);
+        let planner_item = MenuItem::new("Plan Today…", true, None);
+        let planner_id = planner_item.id().clone();
+        let sprint_item = MenuItem::new("Start Sprint…", true, None);
+        let pause_item = MenuItem::new("Pause", true, None);
+        let skip_item = MenuItem::new("Skip Period", true, None);
+        let skip_id = skip_item.id().clone();
+        let restart_item = MenuItem::new("Restart Period", true, None);
+        let restart_id = restart_item.id().clone();
+        let copy_status_item = MenuItem::new("Copy Status", true, None);
+        let copy_status_id = copy_status_item.id().clone();
         let show_check = CheckMenuItem::new("Show Character", true, true, None);
+
+        // Snooze submenu with lengths and an item ending it early
+        let snooze_submenu = Submenu::new("Snooze", true);
+        let mut snooze_items = Vec::new();
+        for snooze in Snooze::ALL {
+            let item = MenuItem::new(snooze.label(), true, None);
+            let _ = snooze_submenu.append(&item);
+            snooze_items.push((*snooze, item));
+        }
+        let end_snooze_item = MenuItem::new("End Snooze", false, None);
+        let _ = snooze_submenu.append(&PredefinedMenuItem::separator());
+        let _ = snooze_submenu.append(&end_snooze_item);
+        let multi_companion_check = CheckMenuItem::new("On Every Monitor", true, false, None);
+        let login_check = CheckMenuItem::new("Start at Login", true, false, None);
+        let rosary_check = CheckMenuItem::new("Rosary During Rest", true, false, None);
+        let touch_check = CheckMenuItem::new("Touch Mode", true, false, None);
+        let overlay_check = CheckMenuItem::new("Break Overlay", true, false, None);
+        let breathing_check = CheckMenuItem::new("Breathing Pause", true, false, None);
+        let reduce_motion_check = CheckMenuItem::new("Reduce Motion", true, false, None);
+        let quotes_check = CheckMenuItem::new("Saint Quotes", true, true, None);
+        let work_prayer_check = CheckMenuItem::new("Prayer Before Work", true, false, None);
+        let sounds_check = CheckMenuItem::new("Sounds", true, true, None);
+        let settings_item = MenuItem::new("Settings…", true, None);
+        let settings_id = settings_item.id().clone();
+        let update_item = MenuItem::new("Check for Updates…", true, None);
+        let update_id = update_item.id().clone();
+        let about_item = MenuItem::new("About Praymodoro…", true, None);
+        let about_id = about_item.id().clone();
         let quit_item = MenuItem::new("Quit", true, None);
         let quit_id = quit_item.id().clone();
 
         // Size submenu with check items
         let size_submenu = Submenu::new("Size", true);
-        let sizes: Vec<f32> = vec![0.5, 0.75, 1.0, 1.25, 1.5, 2.0];
         let mut size_checks = Vec::new();
-        for size in &sizes {
+        for size in SCALE_PRESETS {
             let label = format!("{}%", (size * 100.0) as i32);
             let check = CheckMenuItem::new(&label, true, *size == 1.0, None);
             let _ = size_submenu.append(&check);
             size_checks.push((*size, check));
         }
+        let _ = size_submenu.append(&PredefinedMenuItem::separator());
+        let custom_size_item = MenuItem::new("Custom…", true, None);
+        let custom_size_id = custom_size_item.id().clone();
+        let _ = size_submenu.append(&custom_size_item);
+
+        // Layer submenu with check items
+        let layer_submenu = Submenu::new("Layer", true);
+        let mut layer_checks = Vec::new();
+        for layer in WindowLayer::ALL {
+            let check =
+                CheckMenuItem::new(layer.label(), true, *layer == WindowLayer::default(), None);
+            let _ = layer_submenu.append(&check);
+            layer_checks.push((*layer, check));
+        }
+
+        // Break overlay style submenu with check items
+        let overlay_style_submenu = Submenu::new("Break Overlay Style", true);
+        let mut overlay_style_checks = Vec::new();
+        for style in OverlayStyle::ALL {
+            let check =
+                CheckMenuItem::new(style.label(), true, *style == OverlayStyle::default(), None);
+            let _ = overlay_style_submenu.append(&check);
+            overlay_style_checks.push((*style, check));
+        }
+
+        // Timer display submenu with check items
+        let display_submenu = Submenu::new("Timer Display", true);
+        let mut display_checks = Vec::new();
+        for display in TimerDisplay::ALL {
+            let check = CheckMenuItem::new(
+                display.label(),
+                true,
+                *display == TimerDisplay::default(),
+                None,
+            );
+            let _ = display_submenu.append(&check);
+            display_checks.push((*display, check));
+        }
+
+        // Profile submenu with check items
+        let profile_submenu = Submenu::new("Profile", true);
+        let mut profile_checks = Vec::new();
+        for profile in Profile::ALL {
+            let check =
+                CheckMenuItem::new(profile.label(), true, *profile == Profile::default(), None);
+            let _ = profile_submenu.append(&check);
+            profile_checks.push((*profile, check));
+        }
+
+        // Menu bar title submenu with check items
+        let title_submenu = Submenu::new("Menu Bar Title", true);
+        let mut title_checks = Vec::new();
+        for title in TrayTitle::ALL {
+            let check =
+                CheckMenuItem::new(title.label(), true, *title == TrayTitle::default(), None);
+            let _ = title_submenu.append(&check);
+            title_checks.push((*title, check));
+        }
+
+        // Opacity submenu with check items
+        let opacity_submenu = Submenu::new("Opacity", true);
+        let mut opacity_checks = Vec::new();
+        for opacity in OPACITY_PRESETS {
+            let label = format!("{}%", (opacity * 100.0) as i32);
+            let check = CheckMenuItem::new(&label, true, *opacity == 1.0, None);
+            let _ = opacity_submenu.append(&check);
+            opacity_checks.push((*opacity, check));
+        }
+
+        // Appearance submenu with shadow and outline strengths
+        let appearance_submenu = Submenu::new("Appearance", true);
+        let shadow_submenu = Submenu::new("Shadow", true);
+        let outline_submenu = Submenu::new("Outline", true);
+        let mut shadow_checks = Vec::new();
+        let mut outline_checks = Vec::new();
+        for (strength, label) in EFFECT_PRESETS {
+            let check = CheckMenuItem::new(*label, true, *strength == 0.0, None);
+            let _ = shadow_submenu.append(&check);
+            shadow_checks.push((*strength, check));
+
+            let check = CheckMenuItem::new(*label, true, *strength == 0.0, None);
+            let _ = outline_submenu.append(&check);
+            outline_checks.push((*strength, check));
+        }
+        let _ = appearance_submenu.append(&shadow_submenu);
+        let _ = appearance_submenu.append(&outline_submenu);
 
         // Character submenu with check items
         let char_submenu = Submenu::new("Character", true);
-        let mut char_checks = Vec::new();
-        for (i, char_name) in AVAILABLE_CHARACTERS.iter().enumerate() {
+        let mut char_items = Vec::new();
+        for char_name in characters::available() {
             let display_name = format_character_name(char_name);
-            let check = CheckMenuItem::new(&display_name, true, i == 0, None);
-            let _ = char_submenu.append(&check);
-            char_checks.push((char_name.to_string(), check));
+            let item = IconMenuItem::new(&display_name, true, load_portrait(char_name), None);
+            let _ = char_submenu.append(&item);
+            char_items.push((char_name.clone(), item));
         }
+        let _ = char_submenu.append(&PredefinedMenuItem::separator());
+        let next_char_item = MenuItem::new("Next Character", true, None);
+        let next_char_id = next_char_item.id().clone();
+        let _ = char_submenu.append(&next_char_item);
+
+        // Intention submenu; the saved intentions are filled in by `poll_events`
+        let intention_submenu = Submenu::new("Intention", true);
+        let no_intention_check = CheckMenuItem::new("None", true, true, None);
+        let add_intention_item = MenuItem::new("Add Intention…", true, None);
+        let add_intention_id = add_intention_item.id().clone();
+        let _ = intention_submenu.append(&no_intention_check);
+        let _ = intention_submenu.append(&PredefinedMenuItem::separator());
+        let _ = intention_submenu.append(&add_intention_item);
 
-        // Build menu
+        // Schedule submenu with check items
+        let schedule_submenu = Submenu::new("Schedule", true);
+        let mut schedule_checks = Vec::new();
+        for schedule in ScheduleKind::ALL {
+            let check = CheckMenuItem::new(
+                schedule.label(),
+                true,
+                *schedule == ScheduleKind::default(),
+                None,
+            );
+            let _ = schedule_submenu.append(&check);
+            schedule_checks.push((*schedule, check));
+        }
+        let _ = schedule_submenu.append(&PredefinedMenuItem::separator());
+        let mut engine_checks = Vec::new();
+        for engine in TimerEngine::ALL {
+            let check = CheckMenuItem::new(
+                engine.label(),
+                true,
+                *engine == TimerEngine::default(),
+                None,
+            );
+            let _ = schedule_submenu.append(&check);
+            engine_checks.push((*engine, check));
+        }
+
+        // Build menu from the layout, each section at most once
         let menu = Menu::new();
-        let _ = menu.append(&countdown_item);
-        let _ = menu.append(&PredefinedMenuItem::separator());
-        let _ = menu.append(&size_submenu);
-        let _ = menu.append(&PredefinedMenuItem::separator());
-        let _ = menu.append(&char_submenu);
-        let _ = menu.append(&show_check);
-        let _ = menu.append(&PredefinedMenuItem::separator());
-        let _ = menu.append(&quit_item);
+        let layout = if layout.is_empty() {
+            MenuSection::DEFAULT_LAYOUT
+        } else {
+            layout
+        };
+        let mut placed = Vec::new();
+        for section in layout {
+            if *section != MenuSection::Separator {
+                if placed.contains(section) {
+                    continue;
+                }
+                placed.push(*section);
+            }
+            let items: Vec<&dyn IsMenuItem> = match section {
+                MenuSection::Status => vec![
+                    &countdown_item,
+                    &today_item,
+                    &completed_item,
+                    &plan_item,
+                    &missed_item,
+                    &partner_item,
+                ],
+                MenuSection::Session => vec![
+                    &planner_item,
+                    &sprint_item,
+                    &pause_item,
+                    &skip_item,
+                    &restart_item,
+                    &copy_status_item,
+                ],
+                MenuSection::Profile => vec![&profile_submenu],
+                MenuSection::Size => vec![&size_submenu],
+                MenuSection::Layer => vec![&layer_submenu],
+                MenuSection::TimerDisplay => vec![&display_submenu],
+                MenuSection::MenuBarTitle => vec![&title_submenu],
+                MenuSection::Opacity => vec![&opacity_submenu],
+                MenuSection::Appearance => vec![&appearance_submenu],
+                MenuSection::Character => vec![&char_submenu],
+                MenuSection::Intention => vec![&intention_submenu],
+                MenuSection::Schedule => vec![&schedule_submenu],
+                MenuSection::Options => vec![
+                    &rosary_check,
+                    &touch_check,
+                    &overlay_check,
+                    &overlay_style_submenu,
+                    &breathing_check,
+                    &reduce_motion_check,
+                    &quotes_check,
+                    &work_prayer_check,
+                    &sounds_check,
+                ],
+                MenuSection::Companion => {
+                    vec![&show_check, &snooze_submenu, &multi_companion_check]
+                }
+                MenuSection::App => vec![&login_check, &settings_item, &update_item, &about_item],
+                MenuSection::Quit => vec![&quit_item],
+                MenuSection::Separator => {
+                    let _ = menu.append(&PredefinedMenuItem::separator());
+                    continue;
+                }
+            };
+            let _ = menu.append_items(&items);
+        }
 
         // Load tray icon
-        let icon = load_tray_icon();
+        let icon = load_tray_icon(false);
 
         let tray = TrayIconBuilder::new()
             .with_menu(Box::new(menu))
@@ -106,73 +737,596 @@ impl TrayManager {
             .build()
             .expect("Failed to create tray icon");
 
+        // Forward menu and icon events to channels, waking the window for each one
+        let (menu_sender, menu_events) = mpsc::channel();
+        let repaint = ctx.clone();
+        MenuEvent::set_event_handler(Some(move |event| {
+            let _ = menu_sender.send(event);
+            repaint.request_repaint();
+        }));
+        let (icon_sender, icon_events) = mpsc::channel();
+        let repaint = ctx.clone();
+        TrayIconEvent::set_event_handler(Some(move |event| {
+            let _ = icon_sender.send(event);
+            repaint.request_repaint();
+        }));
+
         Self {
-            _tray: tray,
+            tray,
+            title: None,
             countdown_item,
+            today_item,
+            completed_item,
+            plan_item,
+            missed_item,
+            badged: false,
+            partner_item,
+            planner_id,
+            sprint_item,
+            pause_item,
+            skip_id,
+            restart_id,
+            copy_status_id,
             show_check,
+            snooze_submenu,
+            snooze_items,
+            end_snooze_item,
+            multi_companion_check,
+            login_check,
             size_checks,
-            char_checks,
+            custom_size_id,
+            size_submenu,
+            layer_checks,
+            display_checks,
+            profile_checks,
+            title_checks,
+            opacity_checks,
+            shadow_checks,
+            outline_checks,
+            char_items,
+            next_char_id,
+            intention_submenu,
+            no_intention_check,
+            intention_checks: Vec::new(),
+            add_intention_id,
+            schedule_checks,
+            engine_checks,
+            rosary_check,
+            touch_check,
+            overlay_check,
+            overlay_style_checks,
+            breathing_check,
+            reduce_motion_check,
+            quotes_check,
+            work_prayer_check,
+            sounds_check,
+            settings_id,
+            update_id,
+            about_id,
             quit_id,
+            last_double_click: None,
+            tooltip: None,
+            shown: None,
+            menu_events,
+            icon_events,
         }
     }
 
     /// Polls for tray menu events and updates menu state.
     ///
-    /// Should be called frequently (typically in the main UI update loop).
-    /// Returns any action that should be taken in response to menu interactions.
+    /// Called from the main UI update loop, which events wake. Menu items are
+    /// only updated when the state they show has changed. Returns the actions
+    /// to take in response to every menu interaction since the last call.
     ///
     /// # Arguments
     ///
     /// * `state` - Current application state for updating menu checkboxes
-    pub fn poll_events(&mut self, state: &Arc<Mutex<AppState>>) -> TrayAction {
-        // Update countdown label
+    pub fn poll_events(&mut self, state: &Arc<Mutex<AppState>>) -> Vec<TrayAction> {
+        let double_click = state.lock().settings.tray_double_click;
+
+        // Update the menu only when something it shows has changed
         {
             let s = state.lock();
-            let mode_label = if s.mode == PomodoroMode::Work {
-                "Work for:"
-            } else {
-                "Pray for:"
-            };
-            let _ = self.countdown_item.set_text(format!("{} {}", mode_label, s.formatted_time));
+            let fingerprint = MenuSnapshot::of(&s).fingerprint();
+            if self.shown != Some(fingerprint) {
+                // Update countdown label
+                let mode_label = if s.paused {
+                    "Paused:"
+                } else if s.mode == PomodoroMode::Work {
+                    "Work for:"
+                } else {
+                    "Pray for:"
+                };
+                let mut countdown = match s.rosary {
+                    Some(rosary) => {
+                        format!("{} {} · {}", mode_label, s.formatted_time, rosary.label())
+                    }
+                    None => format!("{} {}", mode_label, s.formatted_time),
+                };
+                // Say when the next prayer begins, at a glance
+                if !s.paused && s.mode == PomodoroMode::Work {
+                    let prayer_at = Local::now() + TimeDelta::seconds(s.remaining_seconds.into());
+                    countdown.push_str(&format!(" · Prayer at {}", prayer_at.format("%H:%M")));
+                }
+                self.countdown_item.set_text(countdown);
+
+                // Update today's session line
+                self.today_item.set_text(format!(
+                    "Today: {} work · {} prayed",
+                    s.stats.completed_today, s.stats.rests_kept_today
+                ));
+
+                // Update pause label
+                self.pause_item
+                    .set_text(if s.paused { "Resume" } else { "Pause" });
+
+                // Update completed counts
+                let planned = s.plan.for_day(Local::now().date_naive()).planned_count();
+                let completed = if planned > 0 {
+                    format!(
+                        "Completed: {} today ({}/{} planned) · {} this week",
+                        s.stats.completed_today,
+                        s.stats.completed_planned_today,
+                        planned,
+                        s.stats.completed_this_week
+                    )
+                } else {
+                    format!(
+                        "Completed: {} today · {} this week",
+                        s.stats.completed_today, s.stats.completed_this_week
+                    )
+                };
+                self.completed_item.set_text(completed);
+
+                // Update planned label for the current block
+                let label = s.plan.label_at(Local::now()).unwrap_or("—");
+                self.plan_item.set_text(format!("Planned: {}", label));
+
+                // Update missed prayers, badging the icon while there are any
+                let missed = s.missed_prayers;
+                self.missed_item.set_text(match missed {
+                    0 => "No Missed Prayers".to_string(),
+                    1 => "Missed a Prayer — Acknowledge".to_string(),
+                    n => format!("Missed {} Prayers — Acknowledge", n),
+                });
+                self.missed_item.set_enabled(missed > 0);
+                if self.badged != (missed > 0) {
+                    self.badged = missed > 0;
+                    let _ = self.tray.set_icon(Some(load_tray_icon(self.badged)));
+                }
+
+                // Update partner status
+                let partner = match &s.partner {
+                    Some(Ok(status)) => format!("Partner: {}", status.label()),
+                    Some(Err(e)) => format!("Partner: {}", e),
+                    None => "Partner: —".to_string(),
+                };
+                self.partner_item.set_text(partner);
+
+                // Update sprint progress
+                let sprint = match &s.stats.sprint {
+                    Some(sprint) => format!("Sprint {}…", sprint.progress()),
+                    None => "Start Sprint…".to_string(),
+                };
+                self.sprint_item.set_text(sprint);
+
+                // Update show check to match state
+                self.show_check.set_checked(s.visible);
+
+                // Update snooze submenu with when the snooze ends
+                match s.snoozed_until {
+                    Some(until) => {
+                        self.snooze_submenu
+                            .set_text(format!("Snoozed Until {}", until.format("%H:%M")));
+                    }
+                    None => self.snooze_submenu.set_text("Snooze"),
+                }
+                self.end_snooze_item.set_enabled(s.snoozed_until.is_some());
+
+                self.multi_companion_check
+                    .set_checked(s.settings.multi_companion);
+                self.login_check.set_checked(s.start_at_login);
+
+                // Update size checks
+                for (size, check) in &self.size_checks {
+                    check.set_checked((*size - s.scale).abs() < 0.01);
+                }
+                let preset = SCALE_PRESETS
+                    .iter()
+                    .any(|size| (*size - s.scale).abs() < 0.01);
+                self.size_submenu.set_text(if preset {
+                    "Size".to_string()
+                } else {
+                    format!("Size ({}%)", (s.scale * 100.0).round())
+                });
+
+                // Update layer checks
+                for (layer, check) in &self.layer_checks {
+                    check.set_checked(*layer == s.settings.window.layer);
+                }
+
+                // Update timer display checks
+                for (display, check) in &self.display_checks {
+                    check.set_checked(*display == s.settings.timer_display);
+                }
+
+                // Refresh the tooltip once a minute, or right away when the mode or count changes
+                let mode_name = if s.paused {
+                    "Paused"
+                } else if s.mode == PomodoroMode::Work {
+                    "Work"
+                } else {
+                    "Prayer"
+                };
+                let status = format!("{} · {} done", mode_name, s.stats.completed_today);
+                let stale = self.tooltip.as_ref().is_none_or(|(shown, refreshed)| {
+                    *shown != status || refreshed.elapsed() >= TOOLTIP_REFRESH
+                });
+                if stale {
+                    let _ = self.tray.set_tooltip(Some(format!(
+                        "{} · {} left · {} done",
+                        mode_name, s.formatted_time, s.stats.completed_today
+                    )));
+                    self.tooltip = Some((status, Instant::now()));
+                }
+
+                // Update profile checks
+                for (profile, check) in &self.profile_checks {
+                    check.set_checked(*profile == s.settings.profile);
+                }
+
+                // Update menu bar title and its checks
+                for (title, check) in &self.title_checks {
+                    check.set_checked(*title == s.settings.tray_title);
+                }
+                let title = s.settings.tray_title.text(s.remaining_seconds);
+                if title != self.title {
+                    self.tray.set_title(title.as_deref());
+                    self.title = title;
+                }
+
+                // Update appearance checks
+                let appearance = &s.settings.appearance;
+                for (opacity, check) in &self.opacity_checks {
+                    check.set_checked((*opacity - appearance.opacity).abs() < 0.01);
+                }
+                for (strength, check) in &self.shadow_checks {
+                    check.set_checked((*strength - appearance.sprite_shadow).abs() < 0.01);
+                }
+                for (strength, check) in &self.outline_checks {
+                    check.set_checked((*strength - appearance.sprite_outline).abs() < 0.01);
+                }
+
+                // Mark the current character; icon items can't show a check
+                for (char_name, item) in &self.char_items {
+                    let name = format_character_name(char_name);
+                    item.set_text(if *char_name == s.character {
+                        format!("✓ {}", name)
+                    } else {
+                        name
+                    });
+                }
+
+                // Update intention items, rebuilding them if the saved list changed
+                let saved = &s.settings.intentions;
+                let shown = self.intention_checks.iter().map(|(intention, _)| intention);
+                if !shown.eq(saved.iter()) {
+                    for (_, check) in self.intention_checks.drain(..) {
+                        let _ = self.intention_submenu.remove(&check);
+                    }
+                    for (position, intention) in saved.iter().enumerate() {
+                        let check = CheckMenuItem::new(intention, true, false, None);
+                        let _ = self.intention_submenu.insert(&check, position + 1);
+                        self.intention_checks.push((intention.clone(), check));
+                    }
+                }
+                self.no_intention_check
+                    .set_checked(s.settings.intention.is_empty());
+                for (intention, check) in &self.intention_checks {
+                    check.set_checked(*intention == s.settings.intention);
+                }
+
+                // Update schedule checks
+                // A newly chosen schedule is marked until the current segment ends
+                let pending = s
+                    .active_schedule
+                    .is_some_and(|active| active != s.settings.schedule);
+                for (schedule, check) in &self.schedule_checks {
+                    let selected = *schedule == s.settings.schedule;
+                    check.set_checked(selected);
+                    check.set_text(if selected && pending {
+                        format!("{} (from next period)", schedule.label())
+                    } else {
+                        schedule.label().to_string()
+                    });
+                }
+
+                // Update engine checks
+                for (engine, check) in &self.engine_checks {
+                    check.set_checked(*engine == s.settings.engine);
+                }
+
+                // Update rosary check
+                self.rosary_check.set_checked(s.settings.rosary_mode);
+
+                // Update touch mode check
+                self.touch_check.set_checked(s.settings.touch_mode);
 
-            // Update show check to match state
-            let _ = self.show_check.set_checked(s.visible);
+                // Update break overlay check
+                self.overlay_check.set_checked(s.settings.break_overlay);
+                for (style, check) in &self.overlay_style_checks {
+                    check.set_checked(*style == s.settings.overlay_style);
+                }
+
+                // Update breathing pause and reduce motion checks
+                self.breathing_check.set_checked(s.settings.breathing_pause);
+                self.reduce_motion_check
+                    .set_checked(s.settings.reduce_motion);
+
+                // Update saint quotes check
+                self.quotes_check.set_checked(s.settings.speech_bubbles);
+
+                // Update prayer before work check
+                self.work_prayer_check
+                    .set_checked(s.settings.prayer_before_work);
 
-            // Update size checks
-            for (size, check) in &self.size_checks {
-                let _ = check.set_checked((*size - s.scale).abs() < 0.01);
+                // Update sounds check
+                self.sounds_check
+                    .set_checked(s.settings.notifications.sound);
+                self.shown = Some(fingerprint);
             }
+        }
+
+        let mut actions = Vec::new();
 
-            // Update character checks
-            for (char_name, check) in &self.char_checks {
-                let _ = check.set_checked(*char_name == s.character);
+        // Check for icon double-clicks, draining the hover and single-click
+        // events so one can't sit queued behind them
+        let double_clicked = self.icon_events.try_iter().fold(false, |clicked, event| {
+            let double_click = matches!(
+                event,
+                TrayIconEvent::DoubleClick {
+                    button: MouseButton::Left,
+                    ..
+                }
+            );
+            clicked || double_click
+        });
+        if double_clicked {
+            let now = Instant::now();
+            let debounced = self
+                .last_double_click
+                .is_some_and(|last| now - last < DOUBLE_CLICK_DEBOUNCE);
+            if !debounced {
+                self.last_double_click = Some(now);
+                actions.push(double_click.action());
             }
         }
 
-        // Check for menu events
-        if let Ok(event) = MenuEvent::receiver().try_recv() {
-            // Check if quit
-            if event.id == self.quit_id {
-                return TrayAction::Quit;
+        // Handle every queued menu event, so a burst of clicks isn't spread
+        // over several frames
+        let events: Vec<MenuEvent> = self.menu_events.try_iter().collect();
+        if !events.is_empty() {
+            // Clicking a check item toggles it natively; refresh the menu next
+            // time in case the action leaves the state unchanged
+            self.shown = None;
+        }
+        actions.extend(events.iter().map(|event| self.menu_action(event)));
+
+        actions
+    }
+
+    /// Returns the action for a click on the menu item `event` names.
+    fn menu_action(&self, event: &MenuEvent) -> TrayAction {
+        // Check if quit
+        if event.id == self.quit_id {
+            return TrayAction::Quit;
+        }
+
+        // Check if missed prayers acknowledged
+        if event.id == *self.missed_item.id() {
+            return TrayAction::AcknowledgeMissedPrayers;
+        }
+
+        // Check if settings
+        if event.id == self.settings_id {
+            return TrayAction::OpenSettings;
+        }
+
+        // Check if update check
+        if event.id == self.update_id {
+            return TrayAction::CheckForUpdates;
+        }
+
+        // Check if about
+        if event.id == self.about_id {
+            return TrayAction::OpenAbout;
+        }
+
+        // Check if planner
+        if event.id == self.planner_id {
+            return TrayAction::OpenPlanner;
+        }
+
+        // Check if sprint
+        if event.id == *self.sprint_item.id() {
+            return TrayAction::OpenSprint;
+        }
+
+        // Check if pause, skip, or restart
+        if event.id == *self.pause_item.id() {
+            return TrayAction::TogglePause;
+        }
+        if event.id == self.skip_id {
+            return TrayAction::SkipPeriod;
+        }
+        if event.id == self.restart_id {
+            return TrayAction::RestartPeriod;
+        }
+
+        // Check if copy status
+        if event.id == self.copy_status_id {
+            return TrayAction::CopyStatus;
+        }
+
+        // Check if show toggle
+        if event.id == *self.show_check.id() {
+            return TrayAction::ToggleVisibility;
+        }
+        if event.id == *self.multi_companion_check.id() {
+            return TrayAction::ToggleMultiCompanion;
+        }
+
+        // Check snooze items
+        for (snooze, item) in &self.snooze_items {
+            if event.id == *item.id() {
+                return TrayAction::Snooze(*snooze);
             }
+        }
+        if event.id == *self.end_snooze_item.id() {
+            return TrayAction::EndSnooze;
+        }
+
+        // Check if start at login toggle
+        if event.id == *self.login_check.id() {
+            return TrayAction::ToggleStartAtLogin;
+        }
+
+        // Check if rosary toggle
+        if event.id == *self.rosary_check.id() {
+            return TrayAction::ToggleRosary;
+        }
 
-            // Check if show toggle
-            if event.id == *self.show_check.id() {
-                return TrayAction::ToggleVisibility;
+        // Check if touch mode toggle
+        if event.id == *self.touch_check.id() {
+            return TrayAction::ToggleTouchMode;
+        }
+
+        // Check if break overlay toggle
+        if event.id == *self.overlay_check.id() {
+            return TrayAction::ToggleBreakOverlay;
+        }
+        for (style, check) in &self.overlay_style_checks {
+            if event.id == *check.id() {
+                return TrayAction::SetOverlayStyle(*style);
             }
+        }
 
-            // Check size items
-            for (size, check) in &self.size_checks {
-                if event.id == *check.id() {
-                    return TrayAction::SetScale(*size);
-                }
+        // Check if breathing pause or reduce motion toggle
+        if event.id == *self.breathing_check.id() {
+            return TrayAction::ToggleBreathingPause;
+        }
+        if event.id == *self.reduce_motion_check.id() {
+            return TrayAction::ToggleReduceMotion;
+        }
+
+        // Check if saint quotes toggle
+        if event.id == *self.quotes_check.id() {
+            return TrayAction::ToggleSpeechBubbles;
+        }
+
+        // Check if sounds toggle
+        if event.id == *self.sounds_check.id() {
+            return TrayAction::ToggleSounds;
+        }
+
+        // Check if prayer before work toggle
+        if event.id == *self.work_prayer_check.id() {
+            return TrayAction::TogglePrayerBeforeWork;
+        }
+
+        // Check size items
+        for (size, check) in &self.size_checks {
+            if event.id == *check.id() {
+                return TrayAction::SetScale(*size);
             }
+        }
+        if event.id == self.custom_size_id {
+            return TrayAction::OpenCustomScale;
+        }
 
-            // Check character items
-            for (char_name, check) in &self.char_checks {
-                if event.id == *check.id() {
-                    return TrayAction::SetCharacter(char_name.clone());
-                }
+        // Check layer items
+        for (layer, check) in &self.layer_checks {
+            if event.id == *check.id() {
+                return TrayAction::SetLayer(*layer);
+            }
+        }
+
+        // Check timer display items
+        for (display, check) in &self.display_checks {
+            if event.id == *check.id() {
+                return TrayAction::SetTimerDisplay(*display);
+            }
+        }
+
+        // Check profile items
+        for (profile, check) in &self.profile_checks {
+            if event.id == *check.id() {
+                return TrayAction::SetProfile(*profile);
+            }
+        }
+
+        // Check menu bar title items
+        for (title, check) in &self.title_checks {
+            if event.id == *check.id() {
+                return TrayAction::SetTrayTitle(*title);
+            }
+        }
+
+        // Check opacity items
+        for (opacity, check) in &self.opacity_checks {
+            if event.id == *check.id() {
+                return TrayAction::SetOpacity(*opacity);
+            }
+        }
+
+        // Check appearance items
+        for (strength, check) in &self.shadow_checks {
+            if event.id == *check.id() {
+                return TrayAction::SetSpriteShadow(*strength);
+            }
+        }
+        for (strength, check) in &self.outline_checks {
+            if event.id == *check.id() {
+                return TrayAction::SetSpriteOutline(*strength);
+            }
+        }
+
+        // Check intention items
+        if event.id == *self.no_intention_check.id() {
+            return TrayAction::SetIntention(String::new());
+        }
+        for (intention, check) in &self.intention_checks {
+            if event.id == *check.id() {
+                return TrayAction::SetIntention(intention.clone());
+            }
+        }
+        if event.id == self.add_intention_id {
+            return TrayAction::AddIntention;
+        }
+
+        // Check if next character
+        if event.id == self.next_char_id {
+            return TrayAction::NextCharacter;
+        }
+
+        // Check character items
+        for (char_name, item) in &self.char_items {
+            if event.id == *item.id() {
+                return TrayAction::SetCharacter(char_name.clone());
+            }
+        }
+
+        // Check schedule items
+        for (schedule, check) in &self.schedule_checks {
+            if event.id == *check.id() {
+                return TrayAction::SetSchedule(*schedule);
+            }
+        }
+
+        // Check engine items
+        for (engine, check) in &self.engine_checks {
+            if event.id == *check.id() {
+                return TrayAction::SetEngine(*engine);
             }
         }
 
@@ -183,17 +1337,49 @@ impl TrayManager {
 /// Loads the tray icon from embedded assets.
 ///
 /// Uses the `tray-iconTemplate@2x.png` which follows macOS naming conventions
-/// for template images (automatically adapts to dark/light mode).
-fn load_tray_icon() -> Icon {
+/// for template images (automatically adapts to dark/light mode). With
+/// `badge` set, a dot is drawn in the top-right corner to flag missed prayers.
+fn load_tray_icon(badge: bool) -> Icon {
     let icon_bytes = include_bytes!("../assets/tray-iconTemplate@2x.png");
-    let image = image::load_from_memory(icon_bytes)
+    let mut image = image::load_from_memory(icon_bytes)
         .expect("Failed to load tray icon")
         .to_rgba8();
     let (width, height) = image.dimensions();
+    if badge {
+        let radius = width.min(height) as f32 / 6.0;
+        let center = (width as f32 - radius, radius);
+        for (x, y, pixel) in image.enumerate_pixels_mut() {
+            let dx = x as f32 + 0.5 - center.0;
+            let dy = y as f32 + 0.5 - center.1;
+            if dx * dx + dy * dy <= radius * radius {
+                *pixel = image::Rgba(BADGE_COLOR);
+            }
+        }
+    }
     let rgba = image.into_raw();
     Icon::from_rgba(rgba, width, height).expect("Failed to create tray icon")
 }
 
+/// Makes a small portrait of a character from the top of its work sprite,
+/// for the Character submenu.
+///
+/// Returns `None` if the sprite can't be read, leaving the item without an icon.
+fn load_portrait(character: &str) -> Option<MenuIcon> {
+    let path = characters::sprite_paths(character, "work")
+        .into_iter()
+        .chain(characters::sprite_paths(character, "work_0"))
+        .find(|path| path.is_file())?;
+    let image = image::open(path).ok()?;
+
+    // The saint's head and shoulders fill a square at the top of the sprite
+    let side = image.width().min(image.height());
+    let portrait = image
+        .crop_imm(0, 0, side, side)
+        .resize_exact(PORTRAIT_SIZE, PORTRAIT_SIZE, FilterType::Triangle)
+        .to_rgba8();
+    MenuIcon::from_rgba(portrait.into_raw(), PORTRAIT_SIZE, PORTRAIT_SIZE).ok()
+}
+
 /// Formats a character identifier into a human-readable display name.
 ///
 /// Converts kebab-case identifiers to Title Case, filtering out common words like "of".
@@ -204,7 +1390,7 @@ fn load_tray_icon() -> Icon {
 /// assert_eq!(format_character_name("augustine-of-hippo"), "Augustine Hippo");
 /// assert_eq!(format_character_name("thomas-aquinas"), "Thomas Aquinas");
 /// ```
-fn format_character_name(name: &str) -> String {
+pub fn format_character_name(name: &str) -> String {
     name.split('-')
         .filter(|s| *s != "of")
         .map(|word| {
@@ -0,0 +1,214 @@
+//! A terminal frontend for SSH sessions and servers, as `praymodoro tui`.
+//!
+//! The same timer thread as the desktop app drives a full-screen terminal
+//! view of the countdown, today's stats, and the saint drawn in ASCII. It
+//! reads the same settings and stats files, so pomodoros completed here count
+//! toward the same streak. Only built with the `tui` feature.
+//!
+//! Keys: `p` or space pauses, `s` skips the period, `r` restarts it, and `q`
+//! or Esc quits.
+
+use crate::characters;
+use crate::format;
+use crate::plan;
+use crate::prayers::rest_prayer;
+use crate::settings;
+use crate::state::{AppState, PomodoroMode};
+use crate::stats;
+use crate::storage;
+use crate::timer;
+use crate::tray::format_character_name;
+use chrono::Local;
+use parking_lot::Mutex;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Margin};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Gauge, Paragraph};
+use ratatui::Frame;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How long to wait for a key before redrawing.
+const FRAME_INTERVAL: Duration = Duration::from_millis(250);
+
+/// The saint at work, book in hand.
+const WORK_ART: &[&str] = &[
+    r"    .-~~~-.    ",
+    r"   (       )   ",
+    r"    `-~~~-'    ",
+    r"    /o   o\    ",
+    r"    \  -  /    ",
+    r"  .-'`---'`-.  ",
+    r" /  .-----.  \ ",
+    r"|  |  ___  |  |",
+    r"   | |___| |   ",
+    r"   |_______|   ",
+];
+
+/// The saint at prayer, hands folded.
+const REST_ART: &[&str] = &[
+    r"    .-~~~-.    ",
+    r"   (       )   ",
+    r"    `-~~~-'    ",
+    r"    /-   -\    ",
+    r"    \  o  /    ",
+    r"  .-'`---'`-.  ",
+    r" /     /\    \ ",
+    r"|     /  \    |",
+    r"   |       |   ",
+    r"   |_______|   ",
+];
+
+/// Runs the terminal frontend until the user quits, returning the exit code.
+pub fn run() -> i32 {
+    let state = Arc::new(Mutex::new(AppState::new()));
+    {
+        let mut s = state.lock();
+        s.settings = settings::load_settings();
+        s.character = s.settings.character.clone();
+        s.stats = stats::load_stats();
+        s.plan = plan::load_plan();
+        // There's no window to show or hide here
+        s.visible = true;
+    }
+
+    let state_for_timer = Arc::clone(&state);
+    std::thread::spawn(move || {
+        timer::run_timer(state_for_timer);
+    });
+
+    let mut terminal = ratatui::init();
+    let result = (|| -> std::io::Result<()> {
+        loop {
+            let snapshot = state.lock().clone();
+            terminal.draw(|frame| draw(frame, &snapshot))?;
+
+            if !event::poll(FRAME_INTERVAL)? {
+                continue;
+            }
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            let mut s = state.lock();
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Char('p') | KeyCode::Char(' ') => s.paused = !s.paused,
+                KeyCode::Char('s') => s.skip_requested = true,
+                KeyCode::Char('r') => s.restart_requested = true,
+                _ => {}
+            }
+        }
+    })();
+    ratatui::restore();
+    storage::flush();
+
+    match result {
+        Ok(()) => 0,
+        Err(error) => {
+            eprintln!("{}", error);
+            1
+        }
+    }
+}
+
+/// Draws one frame of the view of `s`.
+fn draw(frame: &mut Frame, s: &AppState) {
+    let now = Local::now();
+    let accent = match s.mode {
+        PomodoroMode::Work => Color::Yellow,
+        PomodoroMode::Rest => Color::Cyan,
+    };
+    let name = if characters::validate(&s.character).is_ok() {
+        format_character_name(&s.character)
+    } else {
+        String::from("Praymodoro")
+    };
+
+    let block = Block::bordered()
+        .title(format!(" {} ", name))
+        .title_bottom(" p pause · s skip · r restart · q quit ")
+        .border_style(Style::new().fg(accent));
+    let inner = block.inner(frame.area());
+    frame.render_widget(block, frame.area());
+
+    let [art_area, info_area] =
+        Layout::horizontal([Constraint::Length(19), Constraint::Min(20)]).areas(inner);
+
+    let art = match s.mode {
+        PomodoroMode::Work => WORK_ART,
+        PomodoroMode::Rest => REST_ART,
+    };
+    frame.render_widget(
+        Paragraph::new(art.iter().map(|line| Line::raw(*line)).collect::<Vec<_>>())
+            .style(Style::new().fg(accent)),
+        art_area.inner(Margin::new(2, 1)),
+    );
+
+    let [mode_area, time_area, gauge_area, _, detail_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Min(0),
+    ])
+    .areas(info_area.inner(Margin::new(1, 1)));
+
+    let mode = match (s.mode, s.paused) {
+        (PomodoroMode::Work, false) => "Work",
+        (PomodoroMode::Rest, false) => "Prayer",
+        (PomodoroMode::Work, true) => "Work (paused)",
+        (PomodoroMode::Rest, true) => "Prayer (paused)",
+    };
+    frame.render_widget(Paragraph::new(mode), mode_area);
+    frame.render_widget(
+        Paragraph::new(s.formatted_time.as_str())
+            .style(Style::new().fg(accent).add_modifier(Modifier::BOLD)),
+        time_area,
+    );
+    frame.render_widget(
+        Gauge::default()
+            .gauge_style(Style::new().fg(accent))
+            .ratio(f64::from(format::complete_fraction(
+                s.remaining_seconds,
+                s.period_seconds,
+            )))
+            .label(format::percent(s.remaining_seconds, s.period_seconds)),
+        gauge_area,
+    );
+
+    let summary = s.stats.summary(now.date_naive());
+    let planned = s.plan.for_day(now.date_naive()).planned_count();
+    let mut lines = Vec::new();
+    if s.mode == PomodoroMode::Rest {
+        let prayer = rest_prayer(now, &s.character, s.settings.simple_prayers);
+        lines.push(Line::styled(
+            prayer.title,
+            Style::new().add_modifier(Modifier::ITALIC),
+        ));
+        if let Some(rosary) = &s.rosary {
+            lines.push(Line::raw(rosary.label()));
+        }
+        lines.push(Line::raw(""));
+    }
+    lines.push(Line::raw(if planned > 0 {
+        format!("Today: {}/{} pomodoros", summary.completed_today, planned)
+    } else {
+        format!("Today: {} pomodoros", summary.completed_today)
+    }));
+    lines.push(Line::raw(format!(
+        "This week: {} pomodoros",
+        summary.completed_this_week
+    )));
+    if let Some(percent) = summary.rest_adherence_percent {
+        lines.push(Line::raw(format!("Prayed through: {}%", percent)));
+    }
+    lines.push(Line::raw(format!(
+        "Streak: {} days",
+        summary.current_streak_days
+    )));
+    frame.render_widget(Paragraph::new(lines), detail_area);
+}
@@ -0,0 +1,82 @@
+//! Update checks against GitHub releases.
+//!
+//! Praymodoro doesn't install updates itself; it compares the running version
+//! with the latest GitHub release and shows a desktop notification pointing
+//! at the download when a newer one is available.
+
+use crate::notifications::notify_update_check;
+use crate::settings::NotificationSettings;
+use serde::Deserialize;
+
+/// GitHub API endpoint for the latest published release.
+const LATEST_RELEASE_URL: &str =
+    "https://api.github.com/repos/JohnVonDrashek/praymodoro/releases/latest";
+
+/// The subset of the GitHub release response we need.
+#[derive(Debug, Deserialize)]
+struct Release {
+    /// Release tag, e.g. `"v1.0.3"`.
+    tag_name: String,
+    /// Web page for the release.
+    html_url: String,
+}
+
+/// Parses a version like `"v1.2.3"` or `"1.2.3"` into comparable parts.
+///
+/// Missing or non-numeric components are treated as zero.
+fn parse_version(version: &str) -> (u64, u64, u64) {
+    let mut parts = version
+        .trim_start_matches('v')
+        .split('.')
+        .map(|part| part.parse().unwrap_or(0));
+    (
+        parts.next().unwrap_or(0),
+        parts.next().unwrap_or(0),
+        parts.next().unwrap_or(0),
+    )
+}
+
+/// Fetches the latest release from GitHub.
+fn fetch_latest_release() -> Result<Release, String> {
+    ureq::get(LATEST_RELEASE_URL)
+        .set(
+            "User-Agent",
+            concat!("praymodoro/", env!("CARGO_PKG_VERSION")),
+        )
+        .set("Accept", "application/vnd.github+json")
+        .call()
+        .map_err(|e| e.to_string())?
+        .into_json()
+        .map_err(|e| e.to_string())
+}
+
+/// Checks for a newer release in a background thread and notifies the user.
+///
+/// When `manual` is true (the user chose "Check for Updates"), a notification
+/// is also shown if already up to date or if the check failed; automatic
+/// checks stay silent unless there is something new. The notification chimes
+/// as `settings` say.
+pub fn check_for_updates(manual: bool, settings: NotificationSettings) {
+    std::thread::spawn(move || {
+        let current = env!("CARGO_PKG_VERSION");
+        let (summary, body) = match fetch_latest_release() {
+            Ok(release) if parse_version(&release.tag_name) > parse_version(current) => (
+                "Praymodoro update available".to_string(),
+                format!(
+                    "Version {} is available (you have {}). Download it from {}",
+                    release.tag_name.trim_start_matches('v'),
+                    current,
+                    release.html_url
+                ),
+            ),
+            Ok(_) if manual => (
+                "Praymodoro is up to date".to_string(),
+                format!("You're running the latest version ({}).", current),
+            ),
+            Err(e) if manual => ("Couldn't check for updates".to_string(), e),
+            _ => return,
+        };
+
+        notify_update_check(&summary, &body, &settings);
+    });
+}